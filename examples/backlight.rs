@@ -1,4 +1,4 @@
-//! Toggles the display backlight on and off every second.
+//! Blinks the display backlight, then fades it down and back up with PWM.
 
 #![no_std]
 #![no_main]
@@ -21,15 +21,27 @@ esp_bootloader_esp_idf::esp_app_desc!();
 
 #[embassy_executor::task]
 async fn backlight_task(backlight: &'static mut Backlight) {
-    info!("Backlight task started — toggling every second");
+    info!("Backlight task started");
 
     loop {
-        backlight.toggle();
-        info!(
-            "Backlight: {}",
-            if backlight.is_on() { "ON" } else { "OFF" }
-        );
-        Timer::after(Duration::from_secs(1)).await;
+        for _ in 0..4 {
+            backlight.toggle();
+            info!(
+                "Backlight: {}",
+                if backlight.is_on() { "ON" } else { "OFF" }
+            );
+            Timer::after(Duration::from_secs(1)).await;
+        }
+
+        for level in [128, 32, 8, 255] {
+            info!("Brightness {}", level);
+            backlight.set_brightness(level);
+            Timer::after(Duration::from_secs(1)).await;
+        }
+
+        info!("Fading");
+        backlight.fade_to(0, Duration::from_secs(2)).await;
+        backlight.fade_to(255, Duration::from_secs(2)).await;
     }
 }
 
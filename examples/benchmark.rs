@@ -0,0 +1,263 @@
+//! On-device benchmark for the display and LED drivers.
+//!
+//! Runs a fixed set of workloads and logs one machine-readable line per
+//! result over defmt:
+//!
+//! ```text
+//! BENCH <name> <value> <unit>
+//! ```
+//!
+//! Grep the log for `BENCH` and diff the numbers between firmware builds to
+//! catch driver performance regressions. The suite repeats every few seconds.
+
+#![no_std]
+#![no_main]
+
+use defmt::info;
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_time::{
+    Duration,
+    Instant,
+    Timer,
+};
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        iso_8859_1::{
+            FONT_6X10,
+            FONT_10X20,
+        },
+    },
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::Rectangle,
+    text::Text,
+};
+use esp_backtrace as _;
+use esp_hal::timer::timg::TimerGroup;
+use esp_println as _;
+use palette::Srgb;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+const W: u32 = 320;
+const H: u32 = 170;
+
+/// Side length of the square sprite used for the blit benchmark.
+const BLIT_SIZE: u32 = 64;
+
+/// Iteration counts — large enough to average out timer resolution.
+const FILL_ROUNDS: u32 = 20;
+const BLIT_ROUNDS: u32 = 100;
+const PIXEL_ROUNDS: u32 = 20;
+const TEXT_ROUNDS: u32 = 20;
+const FLUSH_ROUNDS: u32 = 10;
+const LED_ROUNDS: u32 = 100;
+
+const TEXT_LINE: &str = "The quick brown fox jumps over the lazy dog";
+
+/// Log one result line in the stable `BENCH <name> <value> <unit>` format.
+fn report(name: &str, value: u64, unit: &str) {
+    info!("BENCH {=str} {=u64} {=str}", name, value, unit);
+}
+
+/// Convert `count` items done in `elapsed` into items per second.
+fn per_second(count: u64, elapsed: Duration) -> u64 {
+    let us = elapsed.as_micros().max(1);
+    count * 1_000_000 / us
+}
+
+/// Full-screen solid fills, reported in pixels per second.
+fn bench_fill(display: &mut Display) {
+    let area = Rectangle::new(Point::zero(), Size::new(W, H));
+    let colors = [Rgb565::RED, Rgb565::GREEN, Rgb565::BLUE, Rgb565::BLACK];
+
+    let start = Instant::now();
+    for i in 0..FILL_ROUNDS {
+        display
+            .fill_solid(&area, colors[i as usize % colors.len()])
+            .unwrap();
+    }
+    let elapsed = start.elapsed();
+
+    report(
+        "fill_rate",
+        per_second(u64::from(FILL_ROUNDS * W * H), elapsed),
+        "px/s",
+    );
+}
+
+/// Copies of a RAM-resident sprite, reported in pixels per second.
+fn bench_blit(display: &mut Display) {
+    let mut sprite = [Rgb565::BLACK; (BLIT_SIZE * BLIT_SIZE) as usize];
+    for (i, px) in sprite.iter_mut().enumerate() {
+        let x = (i as u32 % BLIT_SIZE) as u8;
+        let y = (i as u32 / BLIT_SIZE) as u8;
+        *px = Rgb565::new(x >> 1, y, (x ^ y) >> 1);
+    }
+
+    let start = Instant::now();
+    for i in 0..BLIT_ROUNDS {
+        let x = (i * 37) % (W - BLIT_SIZE);
+        let y = (i * 23) % (H - BLIT_SIZE);
+        let area = Rectangle::new(
+            Point::new(x as i32, y as i32),
+            Size::new(BLIT_SIZE, BLIT_SIZE),
+        );
+        display
+            .fill_contiguous(&area, sprite.iter().copied())
+            .unwrap();
+    }
+    let elapsed = start.elapsed();
+
+    report(
+        "blit_rate",
+        per_second(u64::from(BLIT_ROUNDS * BLIT_SIZE * BLIT_SIZE), elapsed),
+        "px/s",
+    );
+}
+
+/// Individually addressed pixels through `draw_iter`, the path images and
+/// text take, reported in pixels per second.
+fn bench_pixels(display: &mut Display) {
+    let area = Rectangle::new(Point::zero(), Size::new(BLIT_SIZE, BLIT_SIZE));
+
+    let start = Instant::now();
+    for i in 0..PIXEL_ROUNDS {
+        let color = Rgb565::new(i as u8, 0, 31 - i as u8);
+        display
+            .draw_iter(area.points().map(|p| Pixel(p, color)))
+            .unwrap();
+    }
+    let elapsed = start.elapsed();
+
+    report(
+        "pixel_rate",
+        per_second(u64::from(PIXEL_ROUNDS * BLIT_SIZE * BLIT_SIZE), elapsed),
+        "px/s",
+    );
+}
+
+/// Text rendering with the small and large mono fonts, in characters per second.
+fn bench_text(display: &mut Display) {
+    display.clear(Rgb565::BLACK).unwrap();
+
+    let small = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+    let start = Instant::now();
+    for i in 0..TEXT_ROUNDS {
+        let y = 10 + (i % 16) as i32 * 10;
+        Text::new(TEXT_LINE, Point::new(0, y), small)
+            .draw(display)
+            .unwrap();
+    }
+    let elapsed = start.elapsed();
+    report(
+        "text_6x10",
+        per_second(u64::from(TEXT_ROUNDS) * TEXT_LINE.len() as u64, elapsed),
+        "chars/s",
+    );
+
+    let large = MonoTextStyle::new(&FONT_10X20, Rgb565::YELLOW);
+    let start = Instant::now();
+    for i in 0..TEXT_ROUNDS {
+        let y = 20 + (i % 8) as i32 * 20;
+        Text::new(TEXT_LINE, Point::new(0, y), large)
+            .draw(display)
+            .unwrap();
+    }
+    let elapsed = start.elapsed();
+    report(
+        "text_10x20",
+        per_second(u64::from(TEXT_ROUNDS) * TEXT_LINE.len() as u64, elapsed),
+        "chars/s",
+    );
+}
+
+/// Latency of pushing one complete frame of streamed pixels, in microseconds.
+fn bench_flush(display: &mut Display) {
+    let area = Rectangle::new(Point::zero(), Size::new(W, H));
+
+    let start = Instant::now();
+    for round in 0..FLUSH_ROUNDS {
+        let frame = (0..W * H).map(|i| {
+            let x = i % W;
+            let y = i / W;
+            Rgb565::new(((x + round) & 31) as u8, (y & 63) as u8, 0)
+        });
+        display.fill_contiguous(&area, frame).unwrap();
+    }
+    let elapsed = start.elapsed();
+
+    report(
+        "flush_latency",
+        elapsed.as_micros() / u64::from(FLUSH_ROUNDS),
+        "us",
+    );
+}
+
+/// Time for a complete LED strip update, in microseconds.
+async fn bench_leds(leds: &mut Leds<'_>) {
+    let start = Instant::now();
+    for i in 0..LED_ROUNDS {
+        leds.fill(Srgb::new((i % 8) as u8, 0, 0));
+        leds.update().await;
+    }
+    let elapsed = start.elapsed();
+    leds.clear();
+    leds.update().await;
+
+    report(
+        "led_update",
+        elapsed.as_micros() / u64::from(LED_ROUNDS),
+        "us",
+    );
+}
+
+#[embassy_executor::task]
+async fn bench_task(
+    display: &'static mut Display<'static>,
+    backlight: &'static mut Backlight,
+    leds: &'static mut Leds<'static>,
+) {
+    backlight.on();
+    info!("Benchmark task started");
+
+    let mut run = 0u32;
+    loop {
+        info!("BENCH-RUN {=u32}", run);
+        bench_fill(display);
+        bench_blit(display);
+        bench_pixels(display);
+        bench_text(display);
+        bench_flush(display);
+        bench_leds(leds).await;
+        info!("BENCH-DONE {=u32}", run);
+
+        run += 1;
+        Timer::after(Duration::from_secs(5)).await;
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    esp_alloc::heap_allocator!(size: 64 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let display = mk_static!(Display<'static>, resources.display.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    let leds = mk_static!(Leds<'static>, resources.leds.into());
+    spawner.must_spawn(bench_task(display, backlight, leds));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
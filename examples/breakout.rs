@@ -2,31 +2,48 @@
 //!
 //! - Left/Right buttons move the paddle
 //! - Ball bounces off walls, paddle, and bricks
-//! - LEDs flash when a brick is destroyed
+//! - LEDs flash when a brick is hit
 //! - Press A to launch the ball / restart after game over
+//! - Press Select to toggle the frame profiler bar in the HUD
+//!
+//! The boards are [`level`] files in `examples/assets/breakout`, played in
+//! order. Each legend entry describes a brick:
+//!
+//! ```text
+//! B = 4060ff hits=2 points=3 drop=wide:15
+//! ```
+//!
+//! A colour, then optionally how many hits it takes (`0` never breaks,
+//! default 1), the points for breaking it (default 1), and a power-up it
+//! drops with the given percent chance: `wide` widens the paddle for a while,
+//! `life` gives an extra life. Up to 10 columns and 8 rows are used.
 
 #![no_std]
 #![no_main]
 
-use defmt::info;
+use defmt::{info, warn};
 #[allow(clippy::wildcard_imports)]
 use disobey2026badge::*;
 use embassy_executor::Spawner;
-use embassy_time::{Duration, Timer};
+use embassy_futures::select::select;
+use embassy_time::{Duration, Instant, Timer};
 use embedded_graphics::{
     mono_font::{MonoTextStyle, iso_8859_1::FONT_6X10},
     pixelcolor::Rgb565,
     prelude::*,
-    primitives::{PrimitiveStyle, Rectangle},
-    text::Text,
+    primitives::{PrimitiveStyle, PrimitiveStyleBuilder, Rectangle, StrokeAlignment},
+    text::{Alignment, Text},
 };
 use esp_backtrace as _;
 use esp_hal::timer::timg::TimerGroup;
 use esp_println as _;
+use level::Level;
 use palette::Srgb;
 
 extern crate alloc;
 
+use alloc::format;
+
 esp_bootloader_esp_idf::esp_app_desc!();
 
 // Display dimensions
@@ -35,6 +52,7 @@ const H: i32 = 170;
 
 // Paddle
 const PADDLE_W: i32 = 40;
+const PADDLE_WIDE_W: i32 = 64;
 const PADDLE_H: i32 = 6;
 const PADDLE_Y: i32 = H - 12;
 const PADDLE_SPEED: i32 = 6;
@@ -44,59 +62,202 @@ const BALL_SIZE: i32 = 4;
 
 // Bricks
 const BRICK_COLS: usize = 10;
-const BRICK_ROWS: usize = 4;
+const BRICK_ROWS: usize = 8;
 const BRICK_W: i32 = 28;
 const BRICK_H: i32 = 10;
 const BRICK_GAP: i32 = 2;
 const BRICK_OFFSET_X: i32 = (W - (BRICK_W + BRICK_GAP) * BRICK_COLS as i32 + BRICK_GAP) / 2;
 const BRICK_OFFSET_Y: i32 = 20;
 
+// Falling power-ups
+const CAPSULE_W: i32 = 12;
+const CAPSULE_H: i32 = 5;
+const CAPSULE_SPEED: i32 = 2;
+const WIDE_TICKS: u16 = 500;
+const MAX_LIVES: u8 = 5;
+
 // LED flash duration in game ticks
-const LED_FLASH_TICKS: u8 = 6;
+const LED_FLASH_TICKS: u32 = 6;
+
+// Time the LEDs take to crossfade to a new state
+const LED_FADE: Duration = Duration::from_millis(120);
 
-// Game tick rate
-const TICK_MS: u64 = 20;
+// Game frame rate
+const FPS: u32 = 50;
 
-const BRICK_COLORS: [Rgb565; BRICK_ROWS] = [
-    Rgb565::RED,
-    Rgb565::CSS_ORANGE,
-    Rgb565::CSS_YELLOW,
-    Rgb565::GREEN,
+// Profiler bar, centered in the HUD between score and lives
+const PROFILE_BAR: Rectangle = Rectangle::new(Point::new(W / 2 - 50, 4), Size::new(100, 5));
+
+// Boards, played in order
+const LEVELS: [&[u8]; 3] = [
+    include_bytes!("assets/breakout/classic.txt"),
+    include_bytes!("assets/breakout/checkers.txt"),
+    include_bytes!("assets/breakout/fortress.txt"),
 ];
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PowerUp {
+    Wide,
+    Life,
+}
+
+impl PowerUp {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "wide" => Some(Self::Wide),
+            "life" => Some(Self::Life),
+            _ => None,
+        }
+    }
+
+    fn color(self) -> Rgb565 {
+        match self {
+            Self::Wide => Rgb565::CYAN,
+            Self::Life => Rgb565::RED,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Brick {
+    color: Rgb565,
+    /// Hits left; 0 for bricks that never break.
+    hits: u8,
+    max_hits: u8,
+    points: u8,
+    /// Power-up and the percent chance of dropping it.
+    drop: Option<(PowerUp, u8)>,
+}
+
+/// Parse a legend entry like `4060ff hits=2 points=3 drop=wide:15`.
+fn parse_brick(value: &str) -> Option<Brick> {
+    let mut words = value.split_whitespace();
+    let mut brick = Brick {
+        color: level::parse_color(words.next()?)?,
+        hits: 1,
+        max_hits: 1,
+        points: 1,
+        drop: None,
+    };
+    for word in words {
+        match word.split_once('=')? {
+            ("hits", n) => brick.max_hits = n.parse().ok()?,
+            ("points", n) => brick.points = n.parse().ok()?,
+            ("drop", d) => {
+                let (name, chance) = d.split_once(':')?;
+                brick.drop = Some((PowerUp::parse(name)?, chance.parse().ok()?));
+            }
+            _ => return None,
+        }
+    }
+    brick.hits = brick.max_hits;
+    Some(brick)
+}
+
+type Bricks = [[Option<Brick>; BRICK_COLS]; BRICK_ROWS];
+
+/// Build the brick grid of a level, skipping cells without a valid legend entry.
+fn load_bricks(level: &Level) -> Bricks {
+    if level.width() > BRICK_COLS || level.height() > BRICK_ROWS {
+        warn!("Level is larger than {}x{}, cropping", BRICK_COLS, BRICK_ROWS);
+    }
+    let mut bricks = [[None; BRICK_COLS]; BRICK_ROWS];
+    for (row, line) in level.rows().take(BRICK_ROWS).enumerate() {
+        for (col, cell) in line.chars().take(BRICK_COLS).enumerate() {
+            if matches!(cell, '.' | ' ') {
+                continue;
+            }
+            bricks[row][col] = level.legend(cell).and_then(parse_brick);
+            if bricks[row][col].is_none() {
+                warn!("No valid legend entry for brick '{}'", cell);
+            }
+        }
+    }
+    bricks
+}
+
+fn brick_area(row: usize, col: usize) -> Rectangle {
+    Rectangle::new(
+        Point::new(
+            BRICK_OFFSET_X + col as i32 * (BRICK_W + BRICK_GAP),
+            BRICK_OFFSET_Y + row as i32 * (BRICK_H + BRICK_GAP),
+        ),
+        Size::new(BRICK_W as u32, BRICK_H as u32),
+    )
+}
+
+/// A power-up falling towards the paddle.
+#[derive(Clone, Copy)]
+struct Capsule {
+    x: i32,
+    y: i32,
+    power_up: PowerUp,
+}
+
+impl Capsule {
+    fn area(&self) -> Rectangle {
+        Rectangle::new(
+            Point::new(self.x, self.y),
+            Size::new(CAPSULE_W as u32, CAPSULE_H as u32),
+        )
+    }
+}
+
 struct Game {
     paddle_x: i32,
+    paddle_w: i32,
+    wide_ticks: u16,
     ball_x: i32,
     ball_y: i32,
     ball_dx: i32,
     ball_dy: i32,
-    bricks: [[bool; BRICK_COLS]; BRICK_ROWS],
+    bricks: Bricks,
+    /// Breakable bricks at the start of the level.
+    total: u16,
+    capsule: Option<Capsule>,
+    rng: u32,
     score: u16,
     lives: u8,
     launched: bool,
     game_over: bool,
-    led_flash: u8,
+    led_flash: Cooldown,
 }
 
 impl Game {
-    fn new() -> Self {
-        Self {
+    fn new(bricks: Bricks, score: u16, lives: u8) -> Self {
+        let mut game = Self {
             paddle_x: W / 2 - PADDLE_W / 2,
+            paddle_w: PADDLE_W,
+            wide_ticks: 0,
             ball_x: W / 2,
             ball_y: PADDLE_Y - BALL_SIZE - 1,
             ball_dx: 2,
             ball_dy: -2,
-            bricks: [[true; BRICK_COLS]; BRICK_ROWS],
-            score: 0,
-            lives: 3,
+            bricks,
+            total: 0,
+            capsule: None,
+            rng: Instant::now().as_ticks() as u32 | 1,
+            score,
+            lives,
             launched: false,
             game_over: false,
-            led_flash: 0,
-        }
+            led_flash: Cooldown::new(),
+        };
+        game.total = game.bricks_remaining();
+        game
+    }
+
+    /// A pseudo-random percentage, 0..100.
+    fn roll(&mut self) -> u8 {
+        // xorshift32
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+        (self.rng % 100) as u8
     }
 
     fn reset_ball(&mut self) {
-        self.ball_x = self.paddle_x + PADDLE_W / 2;
+        self.ball_x = self.paddle_x + self.paddle_w / 2;
         self.ball_y = PADDLE_Y - BALL_SIZE - 1;
         self.ball_dx = 2;
         self.ball_dy = -2;
@@ -106,8 +267,8 @@ impl Game {
     fn bricks_remaining(&self) -> u16 {
         let mut count = 0u16;
         for row in &self.bricks {
-            for &b in row {
-                if b {
+            for brick in row.iter().flatten() {
+                if brick.max_hits > 0 {
                     count += 1;
                 }
             }
@@ -120,8 +281,36 @@ impl Game {
             return;
         }
 
-        if self.led_flash > 0 {
-            self.led_flash -= 1;
+        self.led_flash.tick();
+
+        if self.wide_ticks > 0 {
+            self.wide_ticks -= 1;
+            if self.wide_ticks == 0 {
+                self.paddle_w = PADDLE_W;
+            }
+        }
+
+        // Move the falling power-up and catch it with the paddle
+        if let Some(capsule) = &mut self.capsule {
+            capsule.y += CAPSULE_SPEED;
+            let caught = capsule.y + CAPSULE_H >= PADDLE_Y
+                && capsule.y <= PADDLE_Y + PADDLE_H
+                && capsule.x + CAPSULE_W > self.paddle_x
+                && capsule.x < self.paddle_x + self.paddle_w;
+            if caught {
+                match capsule.power_up {
+                    PowerUp::Wide => {
+                        self.paddle_w = PADDLE_WIDE_W;
+                        self.paddle_x = self.paddle_x.min(W - PADDLE_WIDE_W);
+                        self.wide_ticks = WIDE_TICKS;
+                    }
+                    PowerUp::Life => self.lives = (self.lives + 1).min(MAX_LIVES),
+                }
+                self.led_flash.start(LED_FLASH_TICKS);
+            }
+            if caught || capsule.y >= H {
+                self.capsule = None;
+            }
         }
 
         // Move ball
@@ -145,6 +334,7 @@ impl Game {
         // Ball fell below paddle
         if self.ball_y + BALL_SIZE >= H {
             self.lives = self.lives.saturating_sub(1);
+            self.capsule = None;
             if self.lives == 0 {
                 self.game_over = true;
             } else {
@@ -158,12 +348,12 @@ impl Game {
             && self.ball_y + BALL_SIZE >= PADDLE_Y
             && self.ball_y + BALL_SIZE <= PADDLE_Y + PADDLE_H
             && self.ball_x + BALL_SIZE > self.paddle_x
-            && self.ball_x < self.paddle_x + PADDLE_W
+            && self.ball_x < self.paddle_x + self.paddle_w
         {
             self.ball_dy = -self.ball_dy.abs();
             // Angle based on where ball hits paddle
             let hit_pos = self.ball_x + BALL_SIZE / 2 - self.paddle_x;
-            let third = PADDLE_W / 3;
+            let third = self.paddle_w / 3;
             if hit_pos < third {
                 self.ball_dx = -3;
             } else if hit_pos > third * 2 {
@@ -177,20 +367,36 @@ impl Game {
         // Brick collisions
         for row in 0..BRICK_ROWS {
             for col in 0..BRICK_COLS {
-                if !self.bricks[row][col] {
+                let Some(mut brick) = self.bricks[row][col] else {
                     continue;
-                }
-                let bx = BRICK_OFFSET_X + col as i32 * (BRICK_W + BRICK_GAP);
-                let by = BRICK_OFFSET_Y + row as i32 * (BRICK_H + BRICK_GAP);
+                };
+                let Point { x: bx, y: by } = brick_area(row, col).top_left;
 
                 if self.ball_x + BALL_SIZE > bx
                     && self.ball_x < bx + BRICK_W
                     && self.ball_y + BALL_SIZE > by
                     && self.ball_y < by + BRICK_H
                 {
-                    self.bricks[row][col] = false;
-                    self.score += (BRICK_ROWS - row) as u16;
-                    self.led_flash = LED_FLASH_TICKS;
+                    if brick.max_hits > 0 {
+                        brick.hits -= 1;
+                        self.led_flash.start(LED_FLASH_TICKS);
+                        if brick.hits > 0 {
+                            self.bricks[row][col] = Some(brick);
+                        } else {
+                            self.bricks[row][col] = None;
+                            self.score += u16::from(brick.points);
+                            if let Some((power_up, chance)) = brick.drop
+                                && self.capsule.is_none()
+                                && self.roll() < chance
+                            {
+                                self.capsule = Some(Capsule {
+                                    x: bx + (BRICK_W - CAPSULE_W) / 2,
+                                    y: by,
+                                    power_up,
+                                });
+                            }
+                        }
+                    }
 
                     // Determine bounce direction
                     let ball_cx = self.ball_x + BALL_SIZE / 2;
@@ -223,14 +429,46 @@ struct PrevState {
     ball_x: i32,
     ball_y: i32,
     paddle_x: i32,
+    paddle_w: i32,
+    capsule: Option<Capsule>,
     score: u16,
     lives: u8,
-    bricks: [[bool; BRICK_COLS]; BRICK_ROWS],
+    bricks: Bricks,
+}
+
+impl PrevState {
+    fn new(game: &Game) -> Self {
+        Self {
+            ball_x: game.ball_x,
+            ball_y: game.ball_y,
+            paddle_x: game.paddle_x,
+            paddle_w: game.paddle_w,
+            capsule: game.capsule,
+            score: game.score,
+            lives: game.lives,
+            bricks: game.bricks,
+        }
+    }
 }
 
 const BLACK: PrimitiveStyle<Rgb565> = PrimitiveStyle::with_fill(Rgb565::BLACK);
 const WHITE: PrimitiveStyle<Rgb565> = PrimitiveStyle::with_fill(Rgb565::WHITE);
 
+/// Draw one brick cell: solid when whole, an outline once damaged.
+fn draw_brick(display: &mut Display, row: usize, col: usize, brick: Option<Brick>) {
+    let style = match brick {
+        None => BLACK,
+        Some(b) if b.hits == b.max_hits => PrimitiveStyle::with_fill(b.color),
+        Some(b) => PrimitiveStyleBuilder::new()
+            .fill_color(Rgb565::BLACK)
+            .stroke_color(b.color)
+            .stroke_width(2)
+            .stroke_alignment(StrokeAlignment::Inside)
+            .build(),
+    };
+    brick_area(row, col).into_styled(style).draw(display).unwrap();
+}
+
 /// Draw the full initial game screen (once per round).
 fn draw_initial(display: &mut Display, game: &Game) {
     // Clear once
@@ -242,19 +480,16 @@ fn draw_initial(display: &mut Display, game: &Game) {
     // All bricks
     for row in 0..BRICK_ROWS {
         for col in 0..BRICK_COLS {
-            let x = BRICK_OFFSET_X + col as i32 * (BRICK_W + BRICK_GAP);
-            let y = BRICK_OFFSET_Y + row as i32 * (BRICK_H + BRICK_GAP);
-            Rectangle::new(Point::new(x, y), Size::new(BRICK_W as u32, BRICK_H as u32))
-                .into_styled(PrimitiveStyle::with_fill(BRICK_COLORS[row]))
-                .draw(display)
-                .unwrap();
+            if game.bricks[row][col].is_some() {
+                draw_brick(display, row, col, game.bricks[row][col]);
+            }
         }
     }
 
     // Paddle
     Rectangle::new(
         Point::new(game.paddle_x, PADDLE_Y),
-        Size::new(PADDLE_W as u32, PADDLE_H as u32),
+        Size::new(game.paddle_w as u32, PADDLE_H as u32),
     )
     .into_styled(WHITE)
     .draw(display)
@@ -310,34 +545,47 @@ fn draw_frame(display: &mut Display, game: &Game, prev: &PrevState) {
     .unwrap();
 
     // Erase old paddle (only the parts that aren't covered by new position)
-    if prev.paddle_x != game.paddle_x {
+    if prev.paddle_x != game.paddle_x || prev.paddle_w != game.paddle_w {
         Rectangle::new(
             Point::new(prev.paddle_x, PADDLE_Y),
-            Size::new(PADDLE_W as u32, PADDLE_H as u32),
+            Size::new(prev.paddle_w as u32, PADDLE_H as u32),
         )
         .into_styled(BLACK)
         .draw(display)
         .unwrap();
     }
 
-    // Black out any newly destroyed bricks
+    // Erase the old power-up, restoring any brick it passed over
+    let capsule_trail = prev.capsule.map(|c| c.area());
+    if let Some(area) = capsule_trail {
+        area.into_styled(BLACK).draw(display).unwrap();
+    }
+
+    // Redraw bricks that were hit or uncovered
     for row in 0..BRICK_ROWS {
         for col in 0..BRICK_COLS {
-            if prev.bricks[row][col] && !game.bricks[row][col] {
-                let x = BRICK_OFFSET_X + col as i32 * (BRICK_W + BRICK_GAP);
-                let y = BRICK_OFFSET_Y + row as i32 * (BRICK_H + BRICK_GAP);
-                Rectangle::new(Point::new(x, y), Size::new(BRICK_W as u32, BRICK_H as u32))
-                    .into_styled(BLACK)
-                    .draw(display)
-                    .unwrap();
+            let uncovered = game.bricks[row][col].is_some()
+                && capsule_trail
+                    .is_some_and(|area| !area.intersection(&brick_area(row, col)).is_zero_sized());
+            if prev.bricks[row][col] != game.bricks[row][col] || uncovered {
+                draw_brick(display, row, col, game.bricks[row][col]);
             }
         }
     }
 
+    // Draw the falling power-up
+    if let Some(capsule) = game.capsule {
+        capsule
+            .area()
+            .into_styled(PrimitiveStyle::with_fill(capsule.power_up.color()))
+            .draw(display)
+            .unwrap();
+    }
+
     // Draw paddle at new position
     Rectangle::new(
         Point::new(game.paddle_x, PADDLE_Y),
-        Size::new(PADDLE_W as u32, PADDLE_H as u32),
+        Size::new(game.paddle_w as u32, PADDLE_H as u32),
     )
     .into_styled(WHITE)
     .draw(display)
@@ -358,19 +606,45 @@ fn draw_frame(display: &mut Display, game: &Game, prev: &PrevState) {
     }
 }
 
+/// A full-width area from `y` to the bottom of the screen.
+fn text_row(y: i32) -> Rectangle {
+    Rectangle::new(Point::new(0, y), Size::new(W as u32, (H - y) as u32))
+}
+
 fn draw_title(display: &mut Display) {
     Rectangle::new(Point::zero(), Size::new(W as u32, H as u32))
         .into_styled(PrimitiveStyle::with_fill(Rgb565::BLACK))
         .draw(display)
         .unwrap();
 
-    let big = MonoTextStyle::new(&FONT_6X10, Rgb565::CSS_YELLOW);
+    let big = MonoTextStyle::new(&font::FONT_20X40, Rgb565::CSS_YELLOW);
     let small = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
 
-    Text::new("BREAKOUT", Point::new(W / 2 - 24, H / 2 - 10), big)
+    TextBox::new("BREAKOUT", text_row(H / 2 - 50), big)
+        .with_alignment(Alignment::Center)
         .draw(display)
         .unwrap();
-    Text::new("Press A to start", Point::new(W / 2 - 48, H / 2 + 10), small)
+    TextBox::new("Press A to start", text_row(H / 2 + 3), small)
+        .with_alignment(Alignment::Center)
+        .draw(display)
+        .unwrap();
+}
+
+fn draw_level_intro(display: &mut Display, number: usize, name: &str) {
+    Rectangle::new(Point::zero(), Size::new(W as u32, H as u32))
+        .into_styled(PrimitiveStyle::with_fill(Rgb565::BLACK))
+        .draw(display)
+        .unwrap();
+
+    let big = MonoTextStyle::new(&font::FONT_20X40, Rgb565::CSS_YELLOW);
+    let small = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+
+    TextBox::new(&format!("LEVEL {number}"), text_row(H / 2 - 50), big)
+        .with_alignment(Alignment::Center)
+        .draw(display)
+        .unwrap();
+    TextBox::new(name, text_row(H / 2 + 3), small)
+        .with_alignment(Alignment::Center)
         .draw(display)
         .unwrap();
 }
@@ -383,20 +657,23 @@ fn draw_game_over(display: &mut Display, won: bool, score: u16) {
 
     let color = if won { Rgb565::GREEN } else { Rgb565::RED };
     let msg = if won { "YOU WIN!" } else { "GAME OVER" };
-    let style = MonoTextStyle::new(&FONT_6X10, color);
+    let style = MonoTextStyle::new(&font::FONT_20X40, color);
     let small = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
 
-    Text::new(msg, Point::new(W / 2 - 30, H / 2 - 10), style)
+    TextBox::new(msg, text_row(H / 2 - 50), style)
+        .with_alignment(Alignment::Center)
         .draw(display)
         .unwrap();
 
     let mut buf = [0u8; 24];
     let score_str = format_score(score, &mut buf);
-    Text::new(score_str, Point::new(W / 2 - 30, H / 2 + 5), small)
+    TextBox::new(score_str, text_row(H / 2 - 2), small)
+        .with_alignment(Alignment::Center)
         .draw(display)
         .unwrap();
 
-    Text::new("Press A to restart", Point::new(W / 2 - 54, H / 2 + 20), small)
+    TextBox::new("Press A to restart", text_row(H / 2 + 13), small)
+        .with_alignment(Alignment::Center)
         .draw(display)
         .unwrap();
 }
@@ -433,26 +710,16 @@ fn format_score(score: u16, buf: &mut [u8; 24]) -> &str {
 }
 
 fn update_leds(leds: &mut Leds, game: &Game) {
-    if game.led_flash > 0 {
+    if game.led_flash.is_active() {
         // Flash bright white on hit
-        let brightness = (game.led_flash as u8) * 4;
+        let brightness = game.led_flash.remaining() as u8 * 14;
         let color = Srgb::new(brightness, brightness, brightness);
         leds.fill(color);
     } else {
-        // Show remaining bricks as a bar graph on LEDs
-        let remaining = game.bricks_remaining();
-        let total = (BRICK_ROWS * BRICK_COLS) as u16;
-        let lit = ((remaining as u32 * 5 + total as u32 - 1) / total as u32) as usize;
-
-        let mut left = [Srgb::new(0u8, 0, 0); BAR_COUNT];
-        let mut right = [Srgb::new(0u8, 0, 0); BAR_COUNT];
-        for i in 0..lit.min(BAR_COUNT) {
-            let color = Srgb::new(0, 4, 2);
-            left[i] = color;
-            right[i] = color;
-        }
-        leds.set_left_bar(&left);
-        leds.set_right_bar(&right);
+        // Show remaining bricks as a meter on the LEDs
+        let remaining = game.bricks_remaining() as u32;
+        let level = (remaining * 255).div_ceil(game.total.max(1) as u32) as u8;
+        leds.set_vu(level, level);
     }
 }
 
@@ -475,85 +742,120 @@ async fn game_task(
         // Wait for A press
         Buttons::debounce_press(&mut buttons.a).await;
 
-        // Game loop
-        let mut game = Game::new();
-        draw_initial(display, &game);
-        let mut prev = PrevState {
-            ball_x: game.ball_x,
-            ball_y: game.ball_y,
-            paddle_x: game.paddle_x,
-            score: game.score,
-            lives: game.lives,
-            bricks: game.bricks,
-        };
-        let tick = Duration::from_millis(TICK_MS);
-
-        loop {
-            // Poll held buttons directly each tick
-            if buttons.left.is_low() {
-                game.paddle_x = (game.paddle_x - PADDLE_SPEED).max(0);
-                if !game.launched {
-                    game.ball_x = game.paddle_x + PADDLE_W / 2;
-                }
-            }
-            if buttons.right.is_low() {
-                game.paddle_x = (game.paddle_x + PADDLE_SPEED).min(W - PADDLE_W);
-                if !game.launched {
-                    game.ball_x = game.paddle_x + PADDLE_W / 2;
+        // Play the levels in order, carrying the score and lives over
+        let (mut score, mut lives) = (0, 3);
+        for (index, data) in LEVELS.iter().enumerate() {
+            let number = index + 1;
+            let level = match Level::from_bytes(data) {
+                Ok(level) => level,
+                Err(err) => {
+                    warn!("Skipping level {}: {}", number, err);
+                    continue;
                 }
+            };
+            let name = level.get("name").unwrap_or("Untitled");
+            let mut game = Game::new(load_bricks(&level), score, lives);
+            if game.total == 0 {
+                warn!("Skipping level {}: no breakable bricks", number);
+                continue;
             }
+            info!("Level {}: {}", number, name);
+            draw_level_intro(display, number, name);
+            Timer::after(Duration::from_millis(1500)).await;
 
-            // Check A for launch
-            if !game.launched && buttons.a.is_low() {
-                game.launched = true;
-            }
+            draw_initial(display, &game);
+            let mut prev = PrevState::new(&game);
+            let mut ticker = FrameTicker::new(FPS);
+            let mut show_profile = false;
+            let mut select_was_pressed = false;
 
-            game.tick();
-
-            draw_frame(display, &game, &prev);
-            prev.ball_x = game.ball_x;
-            prev.ball_y = game.ball_y;
-            prev.paddle_x = game.paddle_x;
-            prev.score = game.score;
-            prev.lives = game.lives;
-            prev.bricks = game.bricks;
-
-            update_leds(leds, &game);
-            leds.update().await;
-
-            if game.game_over {
-                let won = game.bricks_remaining() == 0;
-                Timer::after(Duration::from_millis(500)).await;
-                draw_game_over(display, won, game.score);
-
-                // Flash LEDs for game over
-                if won {
-                    for _ in 0..3 {
-                        leds.fill(Srgb::new(0, 20, 0));
-                        leds.update().await;
-                        Timer::after(Duration::from_millis(300)).await;
-                        leds.clear();
-                        leds.update().await;
-                        Timer::after(Duration::from_millis(300)).await;
+            loop {
+                {
+                    profile!("input");
+                    // Poll held buttons directly each tick
+                    if buttons.left.is_low() {
+                        game.paddle_x = (game.paddle_x - PADDLE_SPEED).max(0);
+                        if !game.launched {
+                            game.ball_x = game.paddle_x + game.paddle_w / 2;
+                        }
                     }
-                } else {
-                    for _ in 0..3 {
-                        leds.fill(Srgb::new(20, 0, 0));
-                        leds.update().await;
-                        Timer::after(Duration::from_millis(300)).await;
-                        leds.clear();
-                        leds.update().await;
-                        Timer::after(Duration::from_millis(300)).await;
+                    if buttons.right.is_low() {
+                        game.paddle_x = (game.paddle_x + PADDLE_SPEED).min(W - game.paddle_w);
+                        if !game.launched {
+                            game.ball_x = game.paddle_x + game.paddle_w / 2;
+                        }
+                    }
+
+                    // Check A for launch
+                    if !game.launched && buttons.a.is_low() {
+                        game.launched = true;
                     }
+
+                    // Select is pulled down, so it reads high while pressed
+                    let select_pressed = buttons.select.is_high();
+                    if select_pressed && !select_was_pressed {
+                        show_profile = !show_profile;
+                        if !show_profile {
+                            draw_hud(display, game.score, game.lives);
+                        }
+                    }
+                    select_was_pressed = select_pressed;
+                }
+
+                {
+                    profile!("tick");
+                    game.tick();
+                }
+
+                {
+                    profile!("draw");
+                    draw_frame(display, &game, &prev);
+                    prev = PrevState::new(&game);
+                }
+
+                {
+                    profile!("leds");
+                    update_leds(leds, &game);
+                    leds.crossfade(LED_FADE);
+                    leds.update().await;
                 }
 
-                // Wait for restart
-                Buttons::debounce_press(&mut buttons.a).await;
-                break; // Restart outer loop
+                profiler::end_frame();
+                if show_profile {
+                    profiler::draw_bar(display, PROFILE_BAR, ticker.period()).unwrap();
+                }
+
+                if game.game_over {
+                    break;
+                }
+
+                ticker.next().await;
             }
 
-            Timer::after(tick).await;
+            score = game.score;
+            lives = game.lives;
+            if lives == 0 {
+                break;
+            }
         }
+
+        let won = lives > 0;
+        Timer::after(Duration::from_millis(500)).await;
+        draw_game_over(display, won, score);
+
+        // Flash LEDs for game over while waiting for restart
+        let color = if won { Srgb::new(0, 80, 0) } else { Srgb::new(80, 0, 0) };
+        let mut animator = LedAnimator::new();
+        animator.play(Effect::Blink {
+            color,
+            period: Duration::from_millis(600),
+            count: 3,
+        });
+        select(
+            Buttons::debounce_press(&mut buttons.a),
+            animator.run(leds),
+        )
+        .await;
     }
 }
 
@@ -1,4 +1,12 @@
-//! Prints which button was pressed via defmt logging.
+//! Prints which button was pressed via defmt logging, and watches for the
+//! Konami code and a few button combos.
+//!
+//! The Konami code is registered as a secret, so it stays unlocked after a
+//! reset until the badge is powered off.
+//!
+//! Holding A+B for a second fires the system "screenshot" combo, as it
+//! would in any app. Start+Select moves it to Down+B and back, to show
+//! remapping, and logs the bytes a settings app would save.
 
 #![no_std]
 #![no_main]
@@ -15,38 +23,78 @@ extern crate alloc;
 
 esp_bootloader_esp_idf::esp_app_desc!();
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+enum Action {
+    Pause,
+    Remap,
+}
+
+const KONAMI: usize = 0;
+
+/// The chords Start+Select moves the system "screenshot" combo between.
+const SCREENSHOT: [Chord; 2] = [
+    Chord::of(&[Button::A, Button::B]),
+    Chord::of(&[Button::Down, Button::B]),
+];
+
+#[embassy_executor::task]
+async fn secret_task() {
+    loop {
+        if buttons::wait_for_unlock().await == KONAMI {
+            info!("Konami code entered!");
+        }
+    }
+}
+
+#[embassy_executor::task]
+async fn screenshot_task(screenshot: buttons::SystemCombo) {
+    loop {
+        screenshot.wait().await;
+        info!("Screenshot combo held");
+    }
+}
+
 #[embassy_executor::task]
 async fn button_task(buttons: &'static mut Buttons) {
     info!("Button task started — press any button");
 
+    buttons::register_secret(KONAMI, Sequence::KONAMI);
+    if buttons::is_unlocked(KONAMI) {
+        info!("Konami code was entered before the last reset");
+    }
+
+    let mut combos: Combos<Action, 4> = Combos::new();
+    combos
+        .register(Action::Pause, Chord::of(&[Button::Up, Button::A]))
+        .unwrap();
+    combos
+        .register(Action::Remap, Chord::of(&[Button::Start, Button::Select]))
+        .unwrap();
+    if let Err(e) = combos.register(Action::Pause, Chord::of(&[Button::Start, Button::Select])) {
+        info!("Start+Select can't be claimed twice: {}", e);
+    }
+
     loop {
-        let pressed = embassy_futures::select::select_array([
-            Buttons::debounce_press(&mut buttons.up),
-            Buttons::debounce_press(&mut buttons.down),
-            Buttons::debounce_press(&mut buttons.left),
-            Buttons::debounce_press(&mut buttons.right),
-            Buttons::debounce_press(&mut buttons.stick),
-            Buttons::debounce_press(&mut buttons.a),
-            Buttons::debounce_press(&mut buttons.b),
-            Buttons::debounce_press(&mut buttons.start),
-            Buttons::debounce_press(&mut buttons.select),
-        ])
-        .await;
-
-        let name = match pressed.1 {
-            0 => "UP",
-            1 => "DOWN",
-            2 => "LEFT",
-            3 => "RIGHT",
-            4 => "STICK",
-            5 => "A",
-            6 => "B",
-            7 => "START",
-            8 => "SELECT",
-            _ => "???",
-        };
-
-        info!("Button pressed: {}", name);
+        let button = buttons.wait_for_press().await;
+        info!("Button pressed: {}", button);
+
+        match combos.poll(buttons) {
+            Some(Action::Remap) => {
+                let next = if buttons::combo("screenshot") == Some(SCREENSHOT[0]) {
+                    SCREENSHOT[1]
+                } else {
+                    SCREENSHOT[0]
+                };
+                buttons::remap_combo("screenshot", next).unwrap();
+                info!(
+                    "Screenshot combo is now {}, saved as {=[u8]}",
+                    next,
+                    &buttons::combos_to_bytes()[..]
+                );
+            }
+            Some(action) => info!("Combo: {}", action),
+            None => {}
+        }
     }
 }
 
@@ -61,6 +109,9 @@ async fn main(spawner: Spawner) -> ! {
     esp_rtos::start(timg0.timer0);
 
     let buttons = mk_static!(Buttons, resources.buttons.into());
+    let screenshot = buttons::claim_combo("screenshot", SCREENSHOT[0]).unwrap();
+    spawner.must_spawn(screenshot_task(screenshot));
+    spawner.must_spawn(secret_task());
     spawner.must_spawn(button_task(buttons));
 
     loop {
@@ -0,0 +1,146 @@
+//! Clock face — analog and digital clock with the LED bars sweeping seconds.
+//!
+//! The badge has no battery-backed RTC, so the `clock` module starts from the
+//! `TIME` compile-time env var (`HH:MM`, default `12:00`).
+//! Up/Down adjust the hour, Left/Right the minute.
+//!
+//! ```sh
+//! TIME="09:30" cargo run --release --example clock
+//! ```
+
+#![no_std]
+#![no_main]
+
+use defmt::info;
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        ascii::FONT_6X10,
+    },
+    pixelcolor::Rgb565,
+    prelude::*,
+    text::{
+        Alignment,
+        Text,
+    },
+};
+use esp_backtrace as _;
+use esp_hal::timer::timg::TimerGroup;
+use esp_println as _;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+const TIME: Option<&str> = option_env!("TIME");
+
+const FACE: dial::ClockFace = dial::ClockFace::new(Point::new(85, 85), 75);
+const DIGITAL_X: i32 = 245;
+
+const HINT: Rgb565 = Rgb565::new(8, 16, 8);
+
+const START: clock::Time = match TIME {
+    Some(s) => match clock::Time::parse_hm(s) {
+        Some(t) => t,
+        None => panic!("TIME must be HH:MM"),
+    },
+    None => clock::Time::from_hms(12, 0, 0),
+};
+
+// ── Drawing ─────────────────────────────────────────────────────────────────
+
+fn draw_face(fb: &mut Framebuffer, now: clock::Time) {
+    fb.clear(Rgb565::BLACK).unwrap();
+    FACE.draw(fb, now).unwrap();
+
+    let mut buf = [0u8; 8];
+    let digits = SevenSegment::new(now.format_hms(&mut buf), Point::zero(), 32, Rgb565::WHITE)
+        .with_off_color(Rgb565::new(2, 4, 2));
+    let width = digits.bounding_box().size.width as i32;
+    digits
+        .translate(Point::new(DIGITAL_X - width / 2, 69))
+        .draw(fb)
+        .unwrap();
+    Text::with_alignment(
+        "Up/Down: hour  L/R: min",
+        Point::new(DIGITAL_X, 160),
+        MonoTextStyle::new(&FONT_6X10, HINT),
+        Alignment::Center,
+    )
+    .draw(fb)
+    .unwrap();
+}
+
+// ── Tasks ───────────────────────────────────────────────────────────────────
+
+#[embassy_executor::task]
+async fn clock_task(
+    display: &'static mut Display<'static>,
+    backlight: &'static mut Backlight,
+    leds: &'static mut Leds<'static>,
+    buttons: &'static mut Buttons,
+) {
+    clock::set(START);
+    info!("Clock started at {}", START);
+    backlight.on();
+
+    let mut shown = None;
+
+    loop {
+        let step = if buttons.up.is_low() {
+            3600
+        } else if buttons.down.is_low() {
+            -3600
+        } else if buttons.right.is_low() {
+            60
+        } else if buttons.left.is_low() {
+            -60
+        } else {
+            0
+        };
+        if step != 0 {
+            clock::adjust(step);
+        }
+
+        let now = clock::now();
+        if shown != Some(now) {
+            display.frame(|fb| draw_face(fb, now)).unwrap();
+            shown = Some(now);
+        }
+
+        dial::sweep_seconds(leds, now);
+        leds.update().await;
+
+        Timer::after(Duration::from_millis(if step == 0 { 50 } else { 200 })).await;
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    // Small heap: the display framebuffer needs most of the internal RAM.
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let display = mk_static!(Display<'static>, resources.display.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    let leds = mk_static!(Leds<'static>, resources.leds.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+
+    spawner.must_spawn(clock_task(display, backlight, leds, buttons));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
@@ -58,13 +58,19 @@ async fn display_task(
         .draw(display)
         .unwrap();
 
-    info!("Display demo drawn — blinking backlight");
+    info!("Display demo drawn — blinking backlight and sleeping the panel");
 
     loop {
         Timer::after(Duration::from_secs(3)).await;
         backlight.off();
         Timer::after(Duration::from_millis(200)).await;
         backlight.on();
+
+        Timer::after(Duration::from_secs(3)).await;
+        info!("Panel asleep");
+        display.sleep(backlight).await.unwrap();
+        Timer::after(Duration::from_secs(2)).await;
+        display.wake(backlight).await.unwrap();
     }
 }
 
@@ -16,7 +16,10 @@ use embedded_graphics::{
         MonoTextStyle,
         iso_8859_1::{FONT_10X20, FONT_6X10},
     },
-    pixelcolor::Rgb565,
+    pixelcolor::{
+        Rgb565,
+        Rgb888,
+    },
     prelude::*,
     primitives::{
         Circle,
@@ -426,6 +429,19 @@ fn pattern_vertical_gradient(display: &mut Display) {
     }
 }
 
+/// Dusk sky gradient, banded on top and dithered below
+fn pattern_dithered_gradient(display: &mut Display) {
+    let (from, to) = (Rgb888::new(20, 24, 70), Rgb888::new(240, 120, 60));
+    let half = Size::new(W, H / 2);
+    gradient::Gradient::new(Rectangle::new(Point::zero(), half), from, to)
+        .with_dither(false)
+        .draw(display)
+        .unwrap();
+    gradient::Gradient::new(Rectangle::new(Point::new(0, (H / 2) as i32), half), from, to)
+        .draw(display)
+        .unwrap();
+}
+
 /// Random-looking noise pattern (deterministic PRNG, no alloc)
 fn pattern_noise(display: &mut Display) {
     let pixels = (0u32..(W * H)).map(|i| {
@@ -466,6 +482,10 @@ async fn display_task(display: &'static mut Display<'static>, backlight: &'stati
     let pause = Duration::from_millis(PAUSE_MS);
 
     loop {
+        info!("Test pattern");
+        display.test_pattern().unwrap();
+        Timer::after(pause).await;
+
         info!("Solid colors");
         pattern_solid_colors(display);
 
@@ -504,6 +524,10 @@ async fn display_task(display: &'static mut Display<'static>, backlight: &'stati
         pattern_rgb_gradient(display);
         Timer::after(pause).await;
 
+        info!("Dithered gradient");
+        pattern_dithered_gradient(display);
+        Timer::after(pause).await;
+
         info!("Checkerboard");
         pattern_checkerboard(display);
         Timer::after(pause).await;
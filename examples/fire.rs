@@ -0,0 +1,164 @@
+//! Pixel fire — the classic demoscene flame effect, rendered on an 80×42
+//! [`ScaledDisplay`] and blown up 4× to the whole screen.
+//!
+//! At a sixteenth of the pixels the simulation has time to spare, and the
+//! big blocky pixels suit the effect.
+//!
+//! - A puts the fire out or lights it again
+//! - Left / Right blow wind
+
+#![no_std]
+#![no_main]
+
+use defmt::info;
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_time::{
+    Duration,
+    Instant,
+    Timer,
+};
+use embedded_graphics::{
+    pixelcolor::Rgb565,
+    prelude::*,
+};
+use esp_backtrace as _;
+use esp_hal::timer::timg::TimerGroup;
+use esp_println as _;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+/// Each canvas pixel is a 4×4 block on screen.
+const SCALE: u32 = 4;
+const PIXELS: usize = ScaledDisplay::<SCALE>::PIXELS;
+
+/// Hottest heat value, the bottom row of a burning fire.
+const MAX_HEAT: u8 = 36;
+
+/// Frames between frame rate reports in the log.
+const REPORT_EVERY: u32 = 100;
+
+// ── Simple RNG ──────────────────────────────────────────────────────────────
+
+struct Rng(u32);
+
+impl Rng {
+    const fn new(seed: u32) -> Self {
+        Self(seed)
+    }
+
+    fn next(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0
+    }
+}
+
+// ── Fire ────────────────────────────────────────────────────────────────────
+
+/// Colour of `heat`: black through red and orange to yellow and white.
+fn color(heat: u8) -> Rgb565 {
+    let t = u32::from(heat) * 255 / u32::from(MAX_HEAT);
+    let r = (t * 3).min(255);
+    let g = (t * 3).saturating_sub(255).min(255);
+    let b = (t * 3).saturating_sub(510).min(255);
+    Rgb565::new((r >> 3) as u8, (g >> 2) as u8, (b >> 3) as u8)
+}
+
+/// Move the heat one row up, cooling and drifting it at random.
+fn spread(heat: &mut [u8], size: Size, wind: i32, rng: &mut Rng) {
+    let (w, h) = (size.width as usize, size.height as usize);
+    for y in 1..h {
+        for x in 0..w {
+            let r = rng.next();
+            let cooling = (r & 1) as u8;
+            let drift = (r >> 1) as i32 % 3 - 1 + wind;
+            let to_x = (x as i32 + drift).rem_euclid(w as i32) as usize;
+            heat[(y - 1) * w + to_x] = heat[y * w + x].saturating_sub(cooling);
+        }
+    }
+}
+
+// ── Tasks ───────────────────────────────────────────────────────────────────
+
+#[embassy_executor::task]
+async fn fire_task(
+    display: &'static mut Display<'static>,
+    backlight: &'static mut Backlight,
+    buttons: &'static mut Buttons,
+    pixels: &'static mut [Rgb565; PIXELS],
+    heat: &'static mut [u8; PIXELS],
+) {
+    backlight.on();
+    display.clear(Rgb565::BLACK).unwrap();
+
+    let mut canvas = ScaledDisplay::<SCALE>::new(display, pixels);
+    let size = canvas.size();
+    let (w, h) = (size.width as usize, size.height as usize);
+    info!("Fire on a {}×{} canvas", size.width, size.height);
+
+    let mut rng = Rng::new(0x1234_5678);
+    let mut burning = true;
+    let mut was_a = false;
+    let mut frames = 0;
+    let mut since = Instant::now();
+
+    loop {
+        let a = buttons.a.is_low();
+        if a && !was_a {
+            burning = !burning;
+        }
+        was_a = a;
+        let wind = i32::from(buttons.right.is_low()) - i32::from(buttons.left.is_low());
+
+        let source = if burning { MAX_HEAT } else { 0 };
+        heat[(h - 1) * w..h * w].fill(source);
+        spread(&mut heat[..w * h], size, wind, &mut rng);
+
+        for (px, &heat) in canvas
+            .framebuffer()
+            .pixels_mut()
+            .iter_mut()
+            .zip(&heat[..w * h])
+        {
+            *px = color(heat);
+        }
+        canvas.flush(display).unwrap();
+
+        frames += 1;
+        if frames == REPORT_EVERY {
+            let elapsed = since.elapsed().as_millis().max(1);
+            info!("{} fps", u64::from(REPORT_EVERY) * 1000 / elapsed);
+            frames = 0;
+            since = Instant::now();
+        }
+        // Let the other tasks run.
+        Timer::after(Duration::from_millis(1)).await;
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let display = mk_static!(Display<'static>, resources.display.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+    let pixels = mk_static!([Rgb565; PIXELS], [Rgb565::BLACK; PIXELS]);
+    let heat = mk_static!([u8; PIXELS], [0; PIXELS]);
+    spawner.must_spawn(fire_task(display, backlight, buttons, pixels, heat));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
@@ -0,0 +1,121 @@
+//! Flashlight — hold Start to light up all LEDs and the screen in white.
+//!
+//! The light is the badge's system flashlight: it claims Start in the
+//! shared combo registry, so holding Start for a second toggles it in any
+//! app that reads the buttons through `wait_for_press`. This app only
+//! turns the screen white to match, and uses Up/Down to set the LEDs'
+//! global brightness. That limiter, together with the battery cap, keeps
+//! ten WS2812s at full white (around 600 mA) from draining the batteries.
+
+#![no_std]
+#![no_main]
+
+use defmt::info;
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_futures::select::{
+    Either,
+    select,
+};
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        ascii::FONT_10X20,
+    },
+    pixelcolor::Rgb565,
+    prelude::*,
+    text::{
+        Alignment,
+        Text,
+    },
+};
+use esp_backtrace as _;
+use esp_hal::timer::timg::TimerGroup;
+use esp_println as _;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+/// Change in global brightness per Up/Down press.
+const BRIGHTNESS_STEP: u8 = 32;
+
+/// Keeps the LEDs updating, which is what shows the light.
+static LEDS: LedService = LedService::new();
+
+fn show_hint(display: &mut Display) {
+    display.clear(Rgb565::BLACK).unwrap();
+    Text::with_alignment(
+        "Hold START for light",
+        Point::new(160, 90),
+        MonoTextStyle::new(&FONT_10X20, Rgb565::CSS_DIM_GRAY),
+        Alignment::Center,
+    )
+    .draw(display)
+    .unwrap();
+}
+
+#[embassy_executor::task]
+async fn app_task(
+    display: &'static mut Display<'static>,
+    backlight: &'static mut Backlight,
+    buttons: &'static mut Buttons,
+) {
+    info!("Flashlight started — hold START to toggle");
+    backlight.on();
+    show_hint(display);
+
+    let leds = LEDS.handle();
+    let mut brightness = u8::MAX;
+    loop {
+        match select(buttons.wait_for_press(), flashlight::wait_for_toggle()).await {
+            Either::First(Button::Up) => brightness = brightness.saturating_add(BRIGHTNESS_STEP),
+            Either::First(Button::Down) => {
+                brightness = brightness
+                    .saturating_sub(BRIGHTNESS_STEP)
+                    .max(BRIGHTNESS_STEP);
+            }
+            Either::First(_) => continue,
+            Either::Second(true) => {
+                display.clear(Rgb565::WHITE).unwrap();
+                continue;
+            }
+            Either::Second(false) => {
+                show_hint(display);
+                continue;
+            }
+        }
+        info!("LED brightness {}", brightness);
+        leds.set_global_brightness(brightness);
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    esp_alloc::heap_allocator!(size: 64 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let display = mk_static!(Display<'static>, resources.display.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    let leds = mk_static!(Leds<'static>, resources.leds.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+
+    let combo = buttons::claim_combo(flashlight::ACTION, flashlight::CHORD).unwrap();
+    spawner.must_spawn(flashlight::flashlight_task(combo));
+    spawner.must_spawn(led_service_task(&LEDS, leds));
+    spawner.must_spawn(app_task(display, backlight, buttons));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
@@ -1,15 +1,16 @@
-//! Renders a BMP image on the display.
+//! Renders a PNG image on the display.
 //!
 //! By default the image is centered. Press UP to align it to the top,
 //! press DOWN to re-center. The image is drawn at its native resolution
 //! (no resizing).
 //!
-//! Place your BMP file at `examples/assets/image.bmp`.
+//! Place your PNG file at `examples/assets/image.png`. It is converted to
+//! raw RGB565 at compile time, so nothing is decoded on the badge.
 //! The image should be smaller than 320×170 to fit the screen.
 //!
-//! Convert a PNG to a compatible 24-bit BMP with ffmpeg:
+//! Scale a larger picture down with ffmpeg:
 //! ```sh
-//! ffmpeg -y -i examples/assets/image.png -vf "scale=320:170:force_original_aspect_ratio=decrease,format=rgb24" -pix_fmt rgb24 -update 1 examples/assets/image.bmp
+//! ffmpeg -y -i photo.png -vf "scale=320:170:force_original_aspect_ratio=decrease" -update 1 examples/assets/image.png
 //! ```
 
 #![no_std]
@@ -20,11 +21,10 @@ use defmt::info;
 use disobey2026badge::*;
 use embassy_executor::Spawner;
 use embassy_time::{Duration, Timer};
-use embedded_graphics::{pixelcolor::{Rgb565, Rgb888}, prelude::*};
+use embedded_graphics::{image::Image, pixelcolor::Rgb565, prelude::*};
 use esp_backtrace as _;
 use esp_hal::timer::timg::TimerGroup;
 use esp_println as _;
-use tinybmp::Bmp;
 
 extern crate alloc;
 
@@ -33,8 +33,8 @@ esp_bootloader_esp_idf::esp_app_desc!();
 const SCREEN_W: i32 = 320;
 const SCREEN_H: i32 = 170;
 
-/// Raw BMP data — replace with your own image.
-const BMP_DATA: &[u8] = include_bytes!("assets/image.bmp");
+/// The image as raw RGB565 — replace with your own.
+const IMAGE: image::RawRgb565Image = include_rgb565!("examples/assets/image.png");
 
 #[embassy_executor::task]
 async fn image_task(
@@ -44,8 +44,7 @@ async fn image_task(
 ) {
     backlight.on();
 
-    let bmp: Bmp<Rgb888> = Bmp::from_slice(BMP_DATA).expect("Invalid BMP");
-    let img_size = bmp.size();
+    let img_size = IMAGE.size();
     info!(
         "Image loaded: {}x{} px",
         img_size.width, img_size.height
@@ -61,7 +60,7 @@ async fn image_task(
     );
 
     let mut position = centered;
-    draw_image(display, &bmp, position);
+    draw_image(display, position);
 
     loop {
         let pressed = embassy_futures::select::select_array([
@@ -83,23 +82,14 @@ async fn image_task(
 
         if new_pos != position {
             position = new_pos;
-            draw_image(display, &bmp, position);
+            draw_image(display, position);
         }
     }
 }
 
-fn draw_image(display: &mut Display<'_>, bmp: &Bmp<Rgb888>, pos: Point) {
-    // Clear screen
+fn draw_image(display: &mut Display<'_>, pos: Point) {
     display.clear(Rgb565::BLACK).unwrap();
-    // Draw image, converting Rgb888 pixels to Rgb565
-    let h = bmp.size().height as i32;
-    let pixels = bmp.pixels().map(|Pixel(p, c)| {
-        Pixel(
-            Point::new(p.x, h - 1 - p.y) + pos,
-            Rgb565::new(c.r() >> 3, c.g() >> 2, c.b() >> 3),
-        )
-    });
-    display.draw_iter(pixels).unwrap();
+    Image::new(&IMAGE, pos).draw(display).unwrap();
 }
 
 #[esp_rtos::main]
@@ -107,7 +97,7 @@ async fn main(spawner: Spawner) -> ! {
     let peripherals = disobey2026badge::init();
     let resources = split_resources!(peripherals);
 
-    esp_alloc::heap_allocator!(size: 200 * 1024);
+    esp_alloc::heap_allocator!(size: 64 * 1024);
 
     let timg0 = TimerGroup::new(peripherals.TIMG0);
     esp_rtos::start(timg0.timer0);
@@ -3,6 +3,7 @@
 //! 1. Sets both bars to the same gradient — they should look symmetrical.
 //! 2. Sets each bar independently with different colors.
 //! 3. Scrolls a single lit LED up both bars in sync.
+//! 4. Sweeps a light round the badge by angle, like a clock hand.
 
 #![no_std]
 #![no_main]
@@ -30,11 +31,11 @@ const OFF: Srgb<u8> = Srgb::new(0, 0, 0);
 async fn led_task(leds: &'static mut Leds<'static>) {
     // A bottom-to-top green gradient used for both bars.
     let gradient: [Srgb<u8>; BAR_COUNT] = [
-        Srgb::new(0, 4, 0),
-        Srgb::new(0, 8, 0),
-        Srgb::new(0, 14, 0),
-        Srgb::new(0, 20, 0),
-        Srgb::new(0, 28, 0),
+        Srgb::new(0, 37, 0),
+        Srgb::new(0, 52, 0),
+        Srgb::new(0, 68, 0),
+        Srgb::new(0, 80, 0),
+        Srgb::new(0, 93, 0),
     ];
 
     loop {
@@ -46,8 +47,8 @@ async fn led_task(leds: &'static mut Leds<'static>) {
 
         // ── Phase 2: left red, right blue ───────────────────────────────
         info!("Phase 2: left red, right blue");
-        let red: [Srgb<u8>; BAR_COUNT] = [Srgb::new(20, 0, 0); BAR_COUNT];
-        let blue: [Srgb<u8>; BAR_COUNT] = [Srgb::new(0, 0, 20); BAR_COUNT];
+        let red: [Srgb<u8>; BAR_COUNT] = [Srgb::new(80, 0, 0); BAR_COUNT];
+        let blue: [Srgb<u8>; BAR_COUNT] = [Srgb::new(0, 0, 80); BAR_COUNT];
         leds.set_left_bar(&red);
         leds.set_right_bar(&blue);
         leds.update().await;
@@ -58,12 +59,21 @@ async fn led_task(leds: &'static mut Leds<'static>) {
         for _ in 0..5 {
             for i in 0..BAR_COUNT {
                 let mut bar = [OFF; BAR_COUNT];
-                bar[i] = Srgb::new(20, 20, 20);
+                bar[i] = Srgb::new(80, 80, 80);
                 leds.set_both_bars(&bar);
                 leds.update().await;
                 Timer::after(Duration::from_millis(150)).await;
             }
         }
+
+        // ── Phase 4: light circling the badge ───────────────────────────
+        info!("Phase 4: circling light");
+        for step in 0..3 * 36 {
+            leds.clear();
+            leds.set(leds.at_angle(step * 10), Srgb::new(0, 80, 80));
+            leds.update().await;
+            Timer::after(Duration::from_millis(40)).await;
+        }
     }
 }
 
@@ -0,0 +1,138 @@
+//! LED calibration — find the white balance that makes these particular
+//! LEDs look white, by eye.
+//!
+//! The LEDs show white (or one channel at a time), and the screen the
+//! factor for each channel:
+//!
+//! - Left / Right pick the channel
+//! - Up / Down turn it up or down
+//! - Select steps the LEDs through red, green, blue and white alone
+//! - A logs the result as a `WhiteBalance::new(..)` to paste into an app
+
+#![no_std]
+#![no_main]
+
+use defmt::info;
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        ascii::FONT_10X20,
+    },
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::Rectangle,
+    text::Text,
+};
+use esp_backtrace as _;
+use esp_hal::timer::timg::TimerGroup;
+use esp_println as _;
+use palette::Srgb;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+/// LED level to judge white at: bright enough to see a tint, not dazzling.
+const LEVEL: u8 = 160;
+const STEP: u8 = 5;
+
+const CHANNELS: [(&str, Rgb565); 3] = [
+    ("Red", Rgb565::RED),
+    ("Green", Rgb565::GREEN),
+    ("Blue", Rgb565::BLUE),
+];
+
+/// What the LEDs show: one channel alone, or white.
+const VIEWS: [Srgb<u8>; 4] = [
+    Srgb::new(LEVEL, 0, 0),
+    Srgb::new(0, LEVEL, 0),
+    Srgb::new(0, 0, LEVEL),
+    Srgb::new(LEVEL, LEVEL, LEVEL),
+];
+
+fn draw(display: &mut Display, factors: [u8; 3], selected: usize) {
+    display
+        .frame(|fb| {
+            fb.clear(Rgb565::BLACK).unwrap();
+            for (i, ((name, color), factor)) in CHANNELS.iter().zip(factors).enumerate() {
+                let y = 30 + i as i32 * 45;
+                let text = if i == selected {
+                    Rgb565::WHITE
+                } else {
+                    Rgb565::CSS_DIM_GRAY
+                };
+                let style = MonoTextStyle::new(&FONT_10X20, text);
+                let label = alloc::format!("{name:<6}{factor:>4}");
+                Text::new(&label, Point::new(10, y), style)
+                    .draw(fb)
+                    .unwrap();
+                let bar = Rectangle::new(
+                    Point::new(120, y - 14),
+                    Size::new(u32::from(factor) * 3 / 4, 16),
+                );
+                fb.fill_solid(&bar, *color).unwrap();
+            }
+        })
+        .unwrap();
+}
+
+#[embassy_executor::task]
+async fn calibration_task(
+    display: &'static mut Display<'static>,
+    backlight: &'static mut Backlight,
+    leds: &'static mut Leds<'static>,
+    buttons: &'static mut Buttons,
+) {
+    backlight.on();
+    let mut factors = WhiteBalance::NONE.to_bytes();
+    let mut selected = 0;
+    let mut view = VIEWS.len() - 1;
+
+    loop {
+        leds.set_white_balance(WhiteBalance::from_bytes(factors));
+        leds.fill(VIEWS[view]);
+        leds.update().await;
+        draw(display, factors, selected);
+
+        match buttons.wait_for_press().await {
+            Button::Left => selected = (selected + CHANNELS.len() - 1) % CHANNELS.len(),
+            Button::Right => selected = (selected + 1) % CHANNELS.len(),
+            Button::Up => factors[selected] = factors[selected].saturating_add(STEP),
+            Button::Down => factors[selected] = factors[selected].saturating_sub(STEP),
+            Button::Select => view = (view + 1) % VIEWS.len(),
+            Button::A => {
+                let [r, g, b] = factors;
+                info!("WhiteBalance::new({=u8}, {=u8}, {=u8})", r, g, b);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let display = mk_static!(Display<'static>, resources.display.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    let leds = mk_static!(Leds<'static>, resources.leds.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+    spawner.must_spawn(calibration_task(display, backlight, leds, buttons));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
@@ -0,0 +1,61 @@
+//! LED identify — lights each LED in turn with its buffer index and place on
+//! the screen, to see which physical LED an index drives.
+//!
+//! - A runs through the LEDs again
+
+#![no_std]
+#![no_main]
+
+use defmt::info;
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use esp_backtrace as _;
+use esp_hal::timer::timg::TimerGroup;
+use esp_println as _;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+#[embassy_executor::task]
+async fn identify_task(
+    display: &'static mut Display<'static>,
+    backlight: &'static mut Backlight,
+    leds: &'static mut Leds<'static>,
+    buttons: &'static mut Buttons,
+) {
+    backlight.on();
+    loop {
+        info!("Identifying LEDs");
+        if selftest::identify_leds(leds, display).await.is_err() {
+            info!("Display write failed");
+        }
+        Buttons::debounce_press(&mut buttons.a).await;
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let display = mk_static!(Display<'static>, resources.display.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    let leds = mk_static!(Leds<'static>, resources.leds.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+    spawner.must_spawn(identify_task(display, backlight, leds, buttons));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
@@ -0,0 +1,163 @@
+//! LED patterns — LED animations loaded from plain-text descriptions in
+//! `examples/assets/led_patterns`, switched with Left/Right.
+//!
+//! Copy a pattern file and edit it to make a new one; the format is
+//! described in the `led_pattern` module.
+//!
+//! New patterns load without reflashing: paste one into the serial
+//! monitor, followed by a line reading `===`, and it plays at once. It
+//! stays until the next paste or reset.
+
+#![no_std]
+#![no_main]
+
+use defmt::{
+    info,
+    warn,
+};
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_futures::select::{
+    Either3,
+    select3,
+};
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    Blocking,
+    timer::timg::TimerGroup,
+    usb_serial_jtag::{
+        UsbSerialJtag,
+        UsbSerialJtagRx,
+    },
+};
+use esp_println as _;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+const PATTERNS: [&str; 3] = [
+    include_str!("assets/led_patterns/police.txt"),
+    include_str!("assets/led_patterns/campfire.txt"),
+    include_str!("assets/led_patterns/breathe.txt"),
+];
+
+/// Longest pattern that can be pasted, in bytes.
+const PASTE_BYTES: usize = 2048;
+/// How often to check the serial port for pasted text.
+const POLL: Duration = Duration::from_millis(50);
+
+/// Text pasted over the USB serial port.
+struct Paste {
+    rx: UsbSerialJtagRx<'static, Blocking>,
+    buf: [u8; PASTE_BYTES],
+    len: usize,
+    complete: bool,
+}
+
+impl Paste {
+    /// Wait for the text up to a line reading `===`, with `\n` line breaks.
+    async fn receive(&mut self) -> &[u8] {
+        if self.complete {
+            self.len = 0;
+            self.complete = false;
+        }
+        loop {
+            let n = self.rx.drain_rx_fifo(&mut self.buf[self.len..]);
+            // Terminals send Enter as `\r`.
+            for byte in &mut self.buf[self.len..self.len + n] {
+                if *byte == b'\r' {
+                    *byte = b'\n';
+                }
+            }
+            self.len += n;
+            let mut end = None;
+            let mut start = 0;
+            for line in self.buf[..self.len].split_inclusive(|&b| b == b'\n') {
+                if line == b"===\n" {
+                    end = Some(start);
+                    break;
+                }
+                start += line.len();
+            }
+            if let Some(end) = end {
+                self.complete = true;
+                return &self.buf[..end];
+            }
+            if self.len == PASTE_BYTES {
+                warn!("Paste longer than {=usize} bytes, dropped", PASTE_BYTES);
+                self.len = 0;
+            }
+            Timer::after(POLL).await;
+        }
+    }
+}
+
+#[embassy_executor::task]
+async fn pattern_task(
+    leds: &'static mut Leds<'static>,
+    buttons: &'static mut Buttons,
+    paste: &'static mut Paste,
+) {
+    let patterns = PATTERNS.map(|text| LedPattern::parse(text).unwrap());
+    // The last pattern pasted, played after the built-in ones.
+    let mut pasted = [0u8; PASTE_BYTES];
+    let mut pasted_len = None;
+    let mut index = 0;
+
+    loop {
+        let pattern = match pasted_len {
+            Some(len) if index == patterns.len() => LedPattern::from_bytes(&pasted[..len]).unwrap(),
+            _ => patterns[index],
+        };
+        info!("Pattern {=str}", pattern.name().unwrap_or("?"));
+        let count = patterns.len() + usize::from(pasted_len.is_some());
+        match select3(buttons.wait_for_press(), pattern.run(leds), paste.receive()).await {
+            Either3::First(Button::Right) => index = (index + 1) % count,
+            Either3::First(Button::Left) => index = (index + count - 1) % count,
+            Either3::Third(text) => match LedPattern::from_bytes(text) {
+                Ok(_) => {
+                    pasted[..text.len()].copy_from_slice(text);
+                    pasted_len = Some(text.len());
+                    index = patterns.len();
+                }
+                Err(e) => warn!("Pasted pattern: {}", e),
+            },
+            _ => {}
+        }
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let leds = mk_static!(Leds<'static>, resources.leds.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+    let (rx, _) = UsbSerialJtag::new(peripherals.USB_DEVICE).split();
+    let paste = mk_static!(
+        Paste,
+        Paste {
+            rx,
+            buf: [0; PASTE_BYTES],
+            len: 0,
+            complete: false,
+        }
+    );
+    spawner.must_spawn(pattern_task(leds, buttons, paste));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
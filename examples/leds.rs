@@ -1,4 +1,5 @@
-//! Cycles a rainbow across the 10 WS2812 LEDs.
+//! Cycles a rainbow across the 10 WS2812 LEDs, then sweeps each stock
+//! palette round them.
 
 #![no_std]
 #![no_main]
@@ -14,7 +15,6 @@ use embassy_time::{
 use esp_backtrace as _;
 use esp_hal::timer::timg::TimerGroup;
 use esp_println as _;
-use palette::Srgb;
 
 extern crate alloc;
 
@@ -24,29 +24,32 @@ esp_bootloader_esp_idf::esp_app_desc!();
 async fn led_task(leds: &'static mut Leds<'static>) {
     info!("LED task started — rainbow cycle");
 
-    let colors: [Srgb<u8>; 10] = [
-        Srgb::new(20, 0, 0),
-        Srgb::new(20, 10, 0),
-        Srgb::new(20, 20, 0),
-        Srgb::new(0, 20, 0),
-        Srgb::new(0, 20, 10),
-        Srgb::new(0, 20, 20),
-        Srgb::new(0, 0, 20),
-        Srgb::new(10, 0, 20),
-        Srgb::new(20, 0, 20),
-        Srgb::new(20, 0, 10),
-    ];
-
-    let mut offset = 0usize;
-    loop {
-        for i in 0..leds.len() {
-            leds.set(i, colors[(i + offset) % colors.len()]);
-        }
+    let mut hue = 0u16;
+    for _ in 0..50 {
+        leds.fill_rainbow(hue, 36, 80);
         leds.update().await;
 
-        offset = (offset + 1) % colors.len();
+        hue = (hue + 36) % 360;
         Timer::after(Duration::from_millis(100)).await;
     }
+
+    let palettes = [
+        ("heat", Palette16::HEAT),
+        ("ocean", Palette16::OCEAN),
+        ("party", Palette16::PARTY),
+        ("disobey", Palette16::DISOBEY),
+    ];
+    for (name, palette) in palettes.iter().cycle() {
+        info!("Palette {=str}", name);
+        let mut start = 0u8;
+        for _ in 0..250 {
+            leds.fill_from_iter(palette.sweep(start, 16));
+            leds.update().await;
+
+            start = start.wrapping_add(2);
+            Timer::after(Duration::from_millis(20)).await;
+        }
+    }
 }
 
 #[esp_rtos::main]
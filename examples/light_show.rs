@@ -0,0 +1,119 @@
+//! Light show — a keyframed LED show, started in step with a button press.
+//!
+//! - A starts the show from the top, as a game event or a beat would
+//! - B switches to a rainbow whose hue and brightness follow their own
+//!   keyframes, and back
+
+#![no_std]
+#![no_main]
+
+use defmt::info;
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_time::{
+    Duration,
+    Instant,
+    Ticker,
+    Timer,
+};
+use esp_backtrace as _;
+use esp_hal::timer::timg::TimerGroup;
+use esp_println as _;
+use palette::Srgb;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+const fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+const fn bars(left: Srgb<u8>, right: Srgb<u8>) -> [Srgb<u8>; 10] {
+    [
+        right, right, right, right, right, left, left, left, left, left,
+    ]
+}
+
+const DARK: Srgb<u8> = Srgb::new(0, 0, 0);
+const PINK: Srgb<u8> = Srgb::new(255, 0, 80);
+const CYAN: Srgb<u8> = Srgb::new(0, 200, 255);
+
+/// Two hits, a swap between the bars, and a slow fade out.
+const SHOW: [Keyframe; 7] = [
+    Keyframe::fill(ms(0), PINK).with_ease(Ease::Step),
+    Keyframe::fill(ms(150), DARK).with_ease(Ease::Step),
+    Keyframe::fill(ms(400), CYAN).with_ease(Ease::Step),
+    Keyframe::new(ms(550), bars(PINK, CYAN)).with_ease(Ease::InOut),
+    Keyframe::new(ms(1300), bars(CYAN, PINK)).with_ease(Ease::InOut),
+    Keyframe::new(ms(2000), bars(PINK, CYAN)),
+    Keyframe::fill(ms(3500), DARK),
+];
+
+/// Rainbow hue, once round in four seconds.
+const HUE: [Keyframe<u16>; 2] = [Keyframe::new(ms(0), 0), Keyframe::new(ms(4000), 359)];
+
+/// Rainbow brightness, breathing.
+const VALUE: [Keyframe<u8>; 3] = [
+    Keyframe::new(ms(0), 40).with_ease(Ease::InOut),
+    Keyframe::new(ms(1000), 255).with_ease(Ease::InOut),
+    Keyframe::new(ms(2000), 40),
+];
+
+#[embassy_executor::task]
+async fn show_task(leds: &'static mut Leds<'static>, buttons: &'static mut Buttons) {
+    let mut show = Timeline::new(&SHOW);
+    let mut hue = Timeline::new(&HUE).looping();
+    let mut value = Timeline::new(&VALUE).looping();
+    let mut rainbow = false;
+    let mut ticker = Ticker::every(Duration::from_millis(20));
+    loop {
+        let now = Instant::now();
+        if buttons.a.is_low() && !show.is_playing(now) {
+            info!("Show");
+            rainbow = false;
+            show.play_from(now);
+        }
+        if buttons.b.is_low() {
+            rainbow = !rainbow;
+            info!("Rainbow: {=bool}", rainbow);
+            show.stop();
+            hue.play_from(now);
+            value.play_from(now);
+            leds.clear();
+            leds.update().await;
+            // Give the button time to come up before the next check.
+            Timer::after(Duration::from_millis(300)).await;
+        }
+
+        if rainbow {
+            if let (Some(hue), Some(value)) = (hue.value(now), value.value(now)) {
+                leds.fill_rainbow(hue, 36, value);
+                leds.update().await;
+            }
+        } else if show.render(leds, now) {
+            leds.update().await;
+        }
+        ticker.next().await;
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let leds = mk_static!(Leds<'static>, resources.leds.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+    spawner.must_spawn(show_task(leds, buttons));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
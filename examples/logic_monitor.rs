@@ -0,0 +1,273 @@
+//! Logic monitor — plots the level of a GPIO pin over time.
+//!
+//! The trace sweeps across the screen like a heart monitor: each column is
+//! one or more samples, drawn high, low, or as a vertical line when the pin
+//! changed during it. The header shows the rising edges per second and the
+//! share of time the pin was high.
+//!
+//! Pick the pin at compile time with `PIN`, one of the GPIOs the badge
+//! doesn't use itself: 9, 10, 39, 40, 41, 42, 47 or 48 (default). Which of
+//! them are reachable depends on your board. `PULL=up` or `PULL=down` adds a
+//! pull resistor; by default the input floats.
+//!
+//! ```sh
+//! PIN=9 PULL=down cargo run --release --example logic_monitor
+//! ```
+//!
+//! - Up / Down change the sample rate, from 10 Hz to 10 kHz
+//! - A pauses and resumes the trace
+
+#![no_std]
+#![no_main]
+
+use defmt::info;
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_time::{
+    Duration,
+    Instant,
+    Ticker,
+    Timer,
+};
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        ascii::FONT_6X10,
+    },
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::Rectangle,
+    text::{
+        Baseline,
+        Text,
+    },
+};
+use esp_backtrace as _;
+use esp_hal::{
+    gpio::{
+        AnyPin,
+        Input,
+        InputConfig,
+        Pull,
+    },
+    timer::timg::TimerGroup,
+};
+use esp_println as _;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+const PIN: &str = match option_env!("PIN") {
+    Some(pin) => pin,
+    None => "48",
+};
+const PULL: Option<&str> = option_env!("PULL");
+
+const W: i32 = 320;
+const H: i32 = 170;
+
+/// Sample rates to choose from, in Hz.
+const RATES: [u32; 4] = [10, 100, 1_000, 10_000];
+
+/// Most columns drawn per second; faster rates put several samples in each.
+const MAX_COLUMNS_PER_SEC: u32 = 250;
+
+const TRACE_TOP: i32 = 40;
+const TRACE_HIGH: i32 = 55;
+const TRACE_LOW: i32 = 135;
+const TRACE_BOTTOM: i32 = 150;
+
+const TRACE: Rgb565 = Rgb565::GREEN;
+const CURSOR: Rgb565 = Rgb565::new(6, 12, 6);
+
+// ── Drawing ─────────────────────────────────────────────────────────────────
+
+/// One column of the trace: the levels seen during it.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Low,
+    High,
+    Edge,
+}
+
+fn draw_column(display: &mut Display, x: i32, column: Column) {
+    let clear = Rectangle::new(
+        Point::new(x, TRACE_TOP),
+        Size::new(1, (TRACE_BOTTOM - TRACE_TOP) as u32),
+    );
+    display.fill_solid(&clear, Rgb565::BLACK).unwrap();
+    let (top, bottom) = match column {
+        Column::Low => (TRACE_LOW, TRACE_LOW),
+        Column::High => (TRACE_HIGH, TRACE_HIGH),
+        Column::Edge => (TRACE_HIGH, TRACE_LOW),
+    };
+    let line = Rectangle::new(Point::new(x, top), Size::new(1, (bottom - top + 1) as u32));
+    display.fill_solid(&line, TRACE).unwrap();
+
+    // A dim cursor just ahead of the trace shows where it is drawing.
+    let next = (x + 1) % W;
+    let cursor = Rectangle::new(
+        Point::new(next, TRACE_TOP),
+        Size::new(1, (TRACE_BOTTOM - TRACE_TOP) as u32),
+    );
+    display.fill_solid(&cursor, CURSOR).unwrap();
+}
+
+fn draw_header(display: &mut Display, rate: u32, edges: u32, duty: u32, paused: bool) {
+    let area = Rectangle::new(Point::zero(), Size::new(W as u32, 12));
+    display.fill_solid(&area, Rgb565::BLACK).unwrap();
+    let style = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+    let mut text = alloc::format!("GPIO{PIN}  {rate} Hz  {edges} edges/s  {duty}% high");
+    if paused {
+        text.push_str("  HOLD");
+    }
+    Text::with_baseline(&text, Point::new(2, 1), style, Baseline::Top)
+        .draw(display)
+        .unwrap();
+}
+
+fn draw_footer(display: &mut Display, rate: u32, samples_per_column: u32) {
+    let area = Rectangle::new(Point::new(0, H - 12), Size::new(W as u32, 12));
+    display.fill_solid(&area, Rgb565::BLACK).unwrap();
+    let style = MonoTextStyle::new(&FONT_6X10, Rgb565::CSS_GRAY);
+    let sweep_ms = u64::from(samples_per_column) * W as u64 * 1000 / u64::from(rate);
+    let text = alloc::format!("{sweep_ms} ms per sweep   Up/Down rate   A hold");
+    Text::with_baseline(&text, Point::new(2, H - 11), style, Baseline::Top)
+        .draw(display)
+        .unwrap();
+}
+
+// ── Tasks ───────────────────────────────────────────────────────────────────
+
+#[embassy_executor::task]
+async fn monitor_task(
+    display: &'static mut Display<'static>,
+    backlight: &'static mut Backlight,
+    buttons: &'static mut Buttons,
+    input: Input<'static>,
+) {
+    info!("Monitoring GPIO{=str}", PIN);
+    backlight.on();
+    display.clear(Rgb565::BLACK).unwrap();
+
+    let mut rate_index = 2;
+    let mut paused = false;
+    let mut held = [false; 3];
+    let mut x = 0;
+    // Statistics over the last second.
+    let (mut edges, mut high, mut total) = (0u32, 0u32, 0u32);
+    let mut last_report = Instant::now();
+    // Edges per second and percent high, as last shown.
+    let mut shown = (0, 0);
+    let mut level = input.is_high();
+
+    loop {
+        let rate = RATES[rate_index];
+        let samples_per_column = (rate / MAX_COLUMNS_PER_SEC).max(1);
+        draw_header(display, rate, shown.0, shown.1, paused);
+        draw_footer(display, rate, samples_per_column);
+        let mut ticker = Ticker::every(Duration::from_hz(u64::from(rate)));
+
+        loop {
+            // Buttons act on press, not while held.
+            let pressed = [
+                buttons.up.is_low(),
+                buttons.down.is_low(),
+                buttons.a.is_low(),
+            ];
+            let new = |i: usize| pressed[i] && !held[i];
+            let (up, down, hold) = (new(0), new(1), new(2));
+            held = pressed;
+            if hold {
+                paused = !paused;
+                draw_header(display, rate, shown.0, shown.1, paused);
+                (edges, high, total) = (0, 0, 0);
+                last_report = Instant::now();
+            }
+            if up && rate_index + 1 < RATES.len() {
+                rate_index += 1;
+                break;
+            }
+            if down && rate_index > 0 {
+                rate_index -= 1;
+                break;
+            }
+            if paused {
+                Timer::after(Duration::from_millis(20)).await;
+                ticker.reset();
+                continue;
+            }
+
+            // Start from the last level, so a change between columns shows.
+            let (mut saw_low, mut saw_high) = (!level, level);
+            for _ in 0..samples_per_column {
+                ticker.next().await;
+                let now = input.is_high();
+                if now && !level {
+                    edges += 1;
+                }
+                level = now;
+                saw_high |= now;
+                saw_low |= !now;
+                high += u32::from(now);
+                total += 1;
+            }
+            let column = match (saw_low, saw_high) {
+                (true, true) => Column::Edge,
+                (false, true) => Column::High,
+                _ => Column::Low,
+            };
+            draw_column(display, x, column);
+            x = (x + 1) % W;
+
+            if last_report.elapsed() >= Duration::from_secs(1) {
+                let millis = last_report.elapsed().as_millis() as u32;
+                shown = (edges * 1000 / millis.max(1), high * 100 / total.max(1));
+                draw_header(display, rate, shown.0, shown.1, paused);
+                (edges, high, total) = (0, 0, 0);
+                last_report = Instant::now();
+            }
+        }
+        info!("Sample rate {} Hz", RATES[rate_index]);
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let pin: AnyPin = match PIN {
+        "9" => peripherals.GPIO9.into(),
+        "10" => peripherals.GPIO10.into(),
+        "39" => peripherals.GPIO39.into(),
+        "40" => peripherals.GPIO40.into(),
+        "41" => peripherals.GPIO41.into(),
+        "42" => peripherals.GPIO42.into(),
+        "47" => peripherals.GPIO47.into(),
+        "48" => peripherals.GPIO48.into(),
+        _ => panic!("PIN must be one of 9, 10, 39, 40, 41, 42, 47 or 48"),
+    };
+    let pull = match PULL {
+        Some("up") => Pull::Up,
+        Some("down") => Pull::Down,
+        _ => Pull::None,
+    };
+    let input = Input::new(pin, InputConfig::default().with_pull(pull));
+
+    let display = mk_static!(Display<'static>, resources.display.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+    spawner.must_spawn(monitor_task(display, backlight, buttons, input));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
@@ -4,6 +4,7 @@
 //! - Ball bounces off walls, paddle, and bricks
 //! - LEDs flash when a brick is destroyed
 //! - Press A to launch the ball / restart after game over
+//! - Press Start to pause
 
 #![no_std]
 #![no_main]
@@ -12,15 +13,16 @@ use defmt::info;
 #[allow(clippy::wildcard_imports)]
 use disobey2026badge::*;
 use embassy_executor::Spawner;
+use embassy_futures::select::select;
 use embassy_time::{Duration, Timer};
 use embedded_graphics::{
     mono_font::{MonoTextStyle, iso_8859_1::FONT_6X10},
-    pixelcolor::{Rgb565, Rgb888},
+    image::Image,
+    pixelcolor::Rgb565,
     prelude::*,
     primitives::{PrimitiveStyle, Rectangle},
-    text::Text,
+    text::{Alignment, Text},
 };
-use tinybmp::Bmp;
 use esp_backtrace as _;
 use esp_hal::timer::timg::TimerGroup;
 use esp_println as _;
@@ -61,10 +63,13 @@ const PADDLE_SPEED: i32 = 6;
 const BALL_SIZE: i32 = 4;
 
 // LED flash duration in game ticks
-const LED_FLASH_TICKS: u8 = 6;
+const LED_FLASH_TICKS: u32 = 6;
 
-// Game tick rate
-const TICK_MS: u64 = 20;
+// Time the LEDs take to crossfade to a new state
+const LED_FADE: Duration = Duration::from_millis(120);
+
+// Game frame rate
+const FPS: u32 = 50;
 
 const BLACK: PrimitiveStyle<Rgb565> = PrimitiveStyle::with_fill(Rgb565::BLACK);
 const WHITE: PrimitiveStyle<Rgb565> = PrimitiveStyle::with_fill(Rgb565::WHITE);
@@ -90,23 +95,13 @@ fn brick_color(row: usize, col: usize) -> Rgb565 {
     PALETTE[LOGO_COLORS[row][col] as usize]
 }
 
-const LOGO_BMP_DATA: &[u8] = include_bytes!("assets/skrolli.bmp");
+/// Skrolli logo, converted at build time to palette indices with a
+/// transparent slot; a flat-coloured logo needs only a few bits per pixel.
+const LOGO: image::PalettedImage = badge_paletted_image!("examples/assets/skrolli.png");
 
-/// Draw the logo BMP on top of the grid area. Transparent (black) pixels are skipped.
-fn draw_logo(display: &mut Display) {
-    let bmp: Bmp<Rgb888> = Bmp::from_slice(LOGO_BMP_DATA).expect("Invalid BMP");
-    let img_h = bmp.size().height as i32;
-    let pixels = bmp.pixels().filter_map(|Pixel(p, c)| {
-        // Skip black pixels (were transparent in the original PNG)
-        if c.r() == 0 && c.g() == 0 && c.b() == 0 {
-            return None;
-        }
-        // BMP is bottom-up, flip Y
-        let flipped = Point::new(p.x, img_h - 1 - p.y);
-        let color = Rgb565::new(c.r() >> 3, c.g() >> 2, c.b() >> 3);
-        Some(Pixel(flipped, color))
-    });
-    display.draw_iter(pixels).unwrap();
+/// Draw the logo on top of the grid area. Transparent pixels are skipped.
+fn draw_logo(display: &mut Framebuffer) {
+    Image::new(&LOGO, Point::zero()).draw(display).unwrap();
 }
 
 struct Game {
@@ -120,7 +115,7 @@ struct Game {
     lives: u8,
     launched: bool,
     game_over: bool,
-    led_flash: u8,
+    led_flash: Cooldown,
 }
 
 impl Game {
@@ -136,7 +131,7 @@ impl Game {
             lives: 3,
             launched: false,
             game_over: false,
-            led_flash: 0,
+            led_flash: Cooldown::new(),
         }
     }
 
@@ -165,9 +160,7 @@ impl Game {
             return;
         }
 
-        if self.led_flash > 0 {
-            self.led_flash -= 1;
-        }
+        self.led_flash.tick();
 
         self.ball_x += self.ball_dx;
         self.ball_y += self.ball_dy;
@@ -232,7 +225,7 @@ impl Game {
                 {
                     self.bricks[row][col] = false;
                     self.score += (ROWS - row) as u16;
-                    self.led_flash = LED_FLASH_TICKS;
+                    self.led_flash.start(LED_FLASH_TICKS);
 
                     // Determine bounce direction
                     let ball_cx = self.ball_x + BALL_SIZE / 2;
@@ -259,16 +252,9 @@ impl Game {
     }
 }
 
-struct PrevState {
-    ball_x: i32,
-    ball_y: i32,
-    paddle_x: i32,
-    score: u16,
-    lives: u8,
-    bricks: [[bool; COLS]; ROWS],
-}
-
-fn draw_initial(display: &mut Display, game: &Game) {
+/// Draw the whole scene. The display only sends the tiles that changed since
+/// the last flush, so there's no need to track and erase old positions.
+fn draw_scene(display: &mut Framebuffer, game: &Game) {
     Rectangle::new(Point::zero(), Size::new(W as u32, H as u32))
         .into_styled(BLACK)
         .draw(display)
@@ -294,6 +280,21 @@ fn draw_initial(display: &mut Display, game: &Game) {
     // Overlay the logo image on top of the brick grid
     draw_logo(display);
 
+    // Break the logo away along with the bricks
+    for row in 0..ROWS {
+        for col in 0..COLS {
+            if !game.bricks[row][col] {
+                Rectangle::new(
+                    Point::new(ERASE_X[col], ERASE_Y[row]),
+                    Size::new(ERASE_W[col] as u32, ERASE_H[row] as u32),
+                )
+                .into_styled(BLACK)
+                .draw(display)
+                .unwrap();
+            }
+        }
+    }
+
     // Paddle
     Rectangle::new(
         Point::new(game.paddle_x, PADDLE_Y),
@@ -315,7 +316,7 @@ fn draw_initial(display: &mut Display, game: &Game) {
     draw_hud(display, game.score, game.lives);
 }
 
-fn draw_hud(display: &mut Display, score: u16, lives: u8) {
+fn draw_hud(display: &mut Framebuffer, score: u16, lives: u8) {
     // HUD at the very bottom of the screen
     let hud_y = H - 14;
     Rectangle::new(Point::new(0, hud_y), Size::new(W as u32, 14))
@@ -341,107 +342,68 @@ fn draw_hud(display: &mut Display, score: u16, lives: u8) {
     }
 }
 
-fn draw_frame(display: &mut Display, game: &Game, prev: &PrevState) {
-    // Erase old ball
-    Rectangle::new(
-        Point::new(prev.ball_x, prev.ball_y),
-        Size::new(BALL_SIZE as u32, BALL_SIZE as u32),
-    )
-    .into_styled(BLACK)
-    .draw(display)
-    .unwrap();
-
-    // Erase old paddle if moved
-    if prev.paddle_x != game.paddle_x {
-        Rectangle::new(
-            Point::new(prev.paddle_x, PADDLE_Y),
-            Size::new(PADDLE_W as u32, PADDLE_H as u32),
-        )
-        .into_styled(BLACK)
-        .draw(display)
-        .unwrap();
-    }
-
-    // Black out destroyed bricks
-    for row in 0..ROWS {
-        for col in 0..COLS {
-            if prev.bricks[row][col] && !game.bricks[row][col] {
-                let x = ERASE_X[col];
-                let y = ERASE_Y[row];
-                Rectangle::new(
-                    Point::new(x, y),
-                    Size::new(ERASE_W[col] as u32, ERASE_H[row] as u32),
-                )
-                .into_styled(BLACK)
-                .draw(display)
-                .unwrap();
-            }
-        }
-    }
-
-    // Draw paddle
-    Rectangle::new(
-        Point::new(game.paddle_x, PADDLE_Y),
-        Size::new(PADDLE_W as u32, PADDLE_H as u32),
-    )
-    .into_styled(WHITE)
-    .draw(display)
-    .unwrap();
-
-    // Draw ball
-    Rectangle::new(
-        Point::new(game.ball_x, game.ball_y),
-        Size::new(BALL_SIZE as u32, BALL_SIZE as u32),
-    )
-    .into_styled(WHITE)
-    .draw(display)
-    .unwrap();
-
-    // Redraw HUD if ball passed through or score/lives changed
-    if prev.ball_y + BALL_SIZE > H - 14 || prev.score != game.score || prev.lives != game.lives {
-        draw_hud(display, game.score, game.lives);
-    }
+/// A full-width area from `y` to the bottom of the screen.
+fn text_row(y: i32) -> Rectangle {
+    Rectangle::new(Point::new(0, y), Size::new(W as u32, (H - y) as u32))
 }
 
-fn draw_title(display: &mut Display) {
+fn draw_title(display: &mut Framebuffer) {
     Rectangle::new(Point::zero(), Size::new(W as u32, H as u32))
         .into_styled(BLACK)
         .draw(display)
         .unwrap();
 
-    let big = MonoTextStyle::new(&FONT_6X10, Rgb565::CSS_YELLOW);
+    let big = MonoTextStyle::new(&font::FONT_20X40, Rgb565::CSS_YELLOW);
     let small = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
 
-    Text::new("LOGO BREAKOUT", Point::new(W / 2 - 39, H / 2 - 10), big)
+    TextBox::new("LOGO BREAKOUT", text_row(H / 2 - 50), big)
+        .with_alignment(Alignment::Center)
         .draw(display)
         .unwrap();
-    Text::new("Press A to start", Point::new(W / 2 - 48, H / 2 + 10), small)
+    TextBox::new("Press A to start", text_row(H / 2 + 3), small)
+        .with_alignment(Alignment::Center)
         .draw(display)
         .unwrap();
 }
 
-fn draw_game_over(display: &mut Display, won: bool, score: u16) {
-    Rectangle::new(Point::zero(), Size::new(W as u32, H as u32))
-        .into_styled(BLACK)
-        .draw(display)
-        .unwrap();
+fn draw_game_over(display: &mut Framebuffer, won: bool, score: u16) {
+    // Dim the final board rather than blanking it.
+    display.draw_blended(&display.bounding_box(), Rgb565::BLACK, 200);
 
     let color = if won { Rgb565::GREEN } else { Rgb565::RED };
     let msg = if won { "YOU WIN!" } else { "TRY HARDER!" };
-    let style = MonoTextStyle::new(&FONT_6X10, color);
+    let style = MonoTextStyle::new(&font::FONT_20X40, color);
     let small = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
 
-    Text::new(msg, Point::new(W / 2 - 30, H / 2 - 10), style)
+    TextBox::new(msg, text_row(H / 2 - 50), style)
+        .with_alignment(Alignment::Center)
         .draw(display)
         .unwrap();
 
     let mut buf = [0u8; 24];
     let score_str = format_score(score, &mut buf);
-    Text::new(score_str, Point::new(W / 2 - 30, H / 2 + 5), small)
+    TextBox::new(score_str, text_row(H / 2 - 2), small)
+        .with_alignment(Alignment::Center)
+        .draw(display)
+        .unwrap();
+
+    TextBox::new("Press A to restart", text_row(H / 2 + 13), small)
+        .with_alignment(Alignment::Center)
         .draw(display)
         .unwrap();
+}
+
+fn draw_paused(display: &mut Framebuffer) {
+    display.draw_blended(&display.bounding_box(), Rgb565::BLACK, 160);
 
-    Text::new("Press A to restart", Point::new(W / 2 - 54, H / 2 + 20), small)
+    let style = MonoTextStyle::new(&font::FONT_20X40, Rgb565::WHITE);
+    let small = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+    TextBox::new("PAUSED", text_row(H / 2 - 30), style)
+        .with_alignment(Alignment::Center)
+        .draw(display)
+        .unwrap();
+    TextBox::new("Press Start to continue", text_row(H / 2 + 13), small)
+        .with_alignment(Alignment::Center)
         .draw(display)
         .unwrap();
 }
@@ -476,8 +438,8 @@ fn format_score(score: u16, buf: &mut [u8; 24]) -> &str {
 }
 
 fn update_leds(leds: &mut Leds, game: &Game) {
-    if game.led_flash > 0 {
-        let brightness = game.led_flash * 4;
+    if game.led_flash.is_active() {
+        let brightness = game.led_flash.remaining() as u8 * 14;
         let color = Srgb::new(brightness, brightness, brightness);
         leds.fill(color);
     } else {
@@ -488,7 +450,7 @@ fn update_leds(leds: &mut Leds, game: &Game) {
         let mut left = [Srgb::new(0u8, 0, 0); BAR_COUNT];
         let mut right = [Srgb::new(0u8, 0, 0); BAR_COUNT];
         for i in 0..lit.min(BAR_COUNT) {
-            let color = Srgb::new(0, 4, 2);
+            let color = Srgb::new(0, 37, 25);
             left[i] = color;
             right[i] = color;
         }
@@ -509,7 +471,7 @@ async fn game_task(
 
     loop {
         // Title screen
-        draw_title(display);
+        display.frame(draw_title).unwrap();
         leds.clear();
         leds.update().await;
 
@@ -517,16 +479,8 @@ async fn game_task(
 
         // Game loop
         let mut game = Game::new();
-        draw_initial(display, &game);
-        let mut prev = PrevState {
-            ball_x: game.ball_x,
-            ball_y: game.ball_y,
-            paddle_x: game.paddle_x,
-            score: game.score,
-            lives: game.lives,
-            bricks: game.bricks,
-        };
-        let tick = Duration::from_millis(TICK_MS);
+        display.frame(|fb| draw_scene(fb, &game)).unwrap();
+        let mut ticker = FrameTicker::new(FPS);
 
         loop {
             if buttons.left.is_low() {
@@ -546,43 +500,52 @@ async fn game_task(
                 game.launched = true;
             }
 
+            if buttons.start.is_low() {
+                display.frame(draw_paused).unwrap();
+                Buttons::debounce_release(&mut buttons.start).await;
+                Buttons::debounce_press_and_release(&mut buttons.start).await;
+                display.frame(|fb| draw_scene(fb, &game)).unwrap();
+                ticker.reset();
+            }
+
             game.tick();
 
-            draw_frame(display, &game, &prev);
-            prev.ball_x = game.ball_x;
-            prev.ball_y = game.ball_y;
-            prev.paddle_x = game.paddle_x;
-            prev.score = game.score;
-            prev.lives = game.lives;
-            prev.bricks = game.bricks;
+            // Redraw off-screen, then push the changed tiles over DMA: no
+            // flicker, and other tasks run while the transfer is in flight.
+            draw_scene(display.framebuffer(), &game);
+            display.flush_async().await.unwrap();
 
             update_leds(leds, &game);
+            leds.crossfade(LED_FADE);
             leds.update().await;
 
             if game.game_over {
                 let won = game.bricks_remaining() == 0;
                 Timer::after(Duration::from_millis(500)).await;
-                draw_game_over(display, won, game.score);
+                display
+                    .frame(|fb| draw_game_over(fb, won, game.score))
+                    .unwrap();
 
                 let flash_color = if won {
-                    Srgb::new(0, 20, 0)
+                    Srgb::new(0, 80, 0)
                 } else {
-                    Srgb::new(20, 0, 0)
+                    Srgb::new(80, 0, 0)
                 };
-                for _ in 0..3 {
-                    leds.fill(flash_color);
-                    leds.update().await;
-                    Timer::after(Duration::from_millis(300)).await;
-                    leds.clear();
-                    leds.update().await;
-                    Timer::after(Duration::from_millis(300)).await;
-                }
-
-                Buttons::debounce_press(&mut buttons.a).await;
+                let mut animator = LedAnimator::new();
+                animator.play(Effect::Blink {
+                    color: flash_color,
+                    period: Duration::from_millis(600),
+                    count: 3,
+                });
+                select(
+                    Buttons::debounce_press(&mut buttons.a),
+                    animator.run(leds),
+                )
+                .await;
                 break;
             }
 
-            Timer::after(tick).await;
+            ticker.next().await;
         }
     }
 }
@@ -592,7 +555,8 @@ async fn main(spawner: Spawner) -> ! {
     let peripherals = disobey2026badge::init();
     let resources = split_resources!(peripherals);
 
-    esp_alloc::heap_allocator!(size: 128 * 1024);
+    // Small heap: the display framebuffer needs most of the internal RAM.
+    esp_alloc::heap_allocator!(size: 32 * 1024);
 
     let timg0 = TimerGroup::new(peripherals.TIMG0);
     esp_rtos::start(timg0.timer0);
@@ -0,0 +1,155 @@
+//! Meters — progress bars and gauges: a looping download, a battery meter
+//! and a speedometer.
+//!
+//! - Up / Down change the battery level
+//! - Left / Right change the speed
+
+#![no_std]
+#![no_main]
+
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        ascii::{
+            FONT_6X10,
+            FONT_10X20,
+        },
+    },
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::Rectangle,
+    text::Text,
+};
+use esp_backtrace as _;
+use esp_hal::timer::timg::TimerGroup;
+use esp_println as _;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+const DOWNLOAD_SIZE: u32 = 4096;
+const DOWNLOAD_STEP: u32 = 37;
+const MAX_SPEED: u32 = 200;
+
+const FRAME: Duration = Duration::from_millis(40);
+
+struct State {
+    received: u32,
+    battery: u32,
+    speed: u32,
+}
+
+fn draw(fb: &mut Framebuffer, state: &State) {
+    let label = MonoTextStyle::new(&FONT_6X10, Rgb565::CSS_LIGHT_GRAY);
+    let white = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+    fb.clear(Rgb565::BLACK).unwrap();
+
+    Text::new("Download", Point::new(10, 20), label)
+        .draw(fb)
+        .unwrap();
+    let text = alloc::format!("{} / {DOWNLOAD_SIZE} bytes", state.received);
+    ProgressBar::new(
+        Rectangle::new(Point::new(10, 28), Size::new(180, 18)),
+        state.received,
+        DOWNLOAD_SIZE,
+    )
+    .with_colors(Rgb565::CSS_DODGER_BLUE, Rgb565::CSS_MIDNIGHT_BLUE)
+    .with_label(&text, white)
+    .draw(fb)
+    .unwrap();
+
+    Text::new("Battery", Point::new(10, 80), label)
+        .draw(fb)
+        .unwrap();
+    let fill = match state.battery {
+        0..=20 => Rgb565::RED,
+        21..=50 => Rgb565::YELLOW,
+        _ => Rgb565::GREEN,
+    };
+    let text = alloc::format!("{}%", state.battery);
+    ProgressBar::new(
+        Rectangle::new(Point::new(10, 88), Size::new(80, 30)),
+        state.battery,
+        100,
+    )
+    .with_border(Rgb565::WHITE, 2)
+    .with_colors(fill, Rgb565::BLACK)
+    .with_label(&text, MonoTextStyle::new(&FONT_10X20, Rgb565::CSS_GRAY))
+    .draw(fb)
+    .unwrap();
+    // The battery's terminal.
+    fb.fill_solid(
+        &Rectangle::new(Point::new(90, 97), Size::new(4, 12)),
+        Rgb565::WHITE,
+    )
+    .unwrap();
+
+    let text = alloc::format!("{}", state.speed);
+    Gauge::new(Point::new(255, 80), 110, state.speed, MAX_SPEED)
+        .with_colors(Rgb565::CSS_ORANGE, Rgb565::CSS_DARK_SLATE_GRAY)
+        .with_label(&text, MonoTextStyle::new(&FONT_10X20, Rgb565::WHITE))
+        .draw(fb)
+        .unwrap();
+    Text::new("km/h", Point::new(243, 130), label)
+        .draw(fb)
+        .unwrap();
+}
+
+#[embassy_executor::task]
+async fn meters_task(
+    display: &'static mut Display<'static>,
+    backlight: &'static mut Backlight,
+    buttons: &'static mut Buttons,
+) {
+    backlight.on();
+    let mut state = State {
+        received: 0,
+        battery: 75,
+        speed: 60,
+    };
+
+    loop {
+        state.received = (state.received + DOWNLOAD_STEP) % (DOWNLOAD_SIZE + DOWNLOAD_STEP);
+        if buttons.up.is_low() {
+            state.battery = (state.battery + 1).min(100);
+        } else if buttons.down.is_low() {
+            state.battery = state.battery.saturating_sub(1);
+        }
+        if buttons.right.is_low() {
+            state.speed = (state.speed + 2).min(MAX_SPEED);
+        } else if buttons.left.is_low() {
+            state.speed = state.speed.saturating_sub(2);
+        }
+
+        display.frame(|fb| draw(fb, &state)).unwrap();
+        Timer::after(FRAME).await;
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let display = mk_static!(Display<'static>, resources.display.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+    spawner.must_spawn(meters_task(display, backlight, buttons));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
@@ -1,5 +1,6 @@
 //! VU meter: reads the I2S microphone as fast as possible and displays
-//! peak amplitude on both LED bars (green → yellow → red).
+//! peak amplitude on both LED bars (green → yellow → red), with the
+//! loudest peak held for a moment.
 
 #![no_std]
 #![no_main]
@@ -11,41 +12,17 @@ use embassy_time::{Duration, Timer};
 use esp_backtrace as _;
 use esp_hal::{dma::DmaDescriptor, timer::timg::TimerGroup};
 use esp_println as _;
-use palette::Srgb;
 
 extern crate alloc;
 
 esp_bootloader_esp_idf::esp_app_desc!();
 
-/// VU meter colors from bottom (quiet) to top (loud).
-const VU_COLORS: [Srgb<u8>; BAR_COUNT] = [
-    Srgb::new(0, 20, 0),  // green
-    Srgb::new(0, 20, 0),  // green
-    Srgb::new(20, 20, 0), // yellow
-    Srgb::new(20, 10, 0), // orange
-    Srgb::new(20, 0, 0),  // red
-];
-
-const OFF: Srgb<u8> = Srgb::new(0, 0, 0);
-
 /// Maximum expected amplitude from the mic (tuning knob — adjust to taste).
 const MAX_AMPLITUDE: u16 = 4000;
 
-/// Map a peak amplitude (0..MAX_AMPLITUDE) to a LED bar pattern.
-fn amplitude_to_bar(peak: u16) -> [Srgb<u8>; BAR_COUNT] {
-    let level = if peak >= MAX_AMPLITUDE {
-        BAR_COUNT
-    } else {
-        (peak as usize * BAR_COUNT) / MAX_AMPLITUDE as usize
-    };
-
-    let mut bar = [OFF; BAR_COUNT];
-    for (i, led) in bar.iter_mut().enumerate() {
-        if i < level {
-            *led = VU_COLORS[i];
-        }
-    }
-    bar
+/// Map a peak amplitude (0..MAX_AMPLITUDE) to a VU meter level.
+fn amplitude_to_level(peak: u16) -> u8 {
+    (u32::from(peak.min(MAX_AMPLITUDE)) * 255 / u32::from(MAX_AMPLITUDE)) as u8
 }
 
 #[embassy_executor::task]
@@ -59,8 +36,8 @@ async fn vu_task(
         match mic.rx.read_words(&mut buf) {
             Ok(()) => {
                 let peak = buf.iter().map(|s| s.unsigned_abs()).max().unwrap_or(0);
-                let bar = amplitude_to_bar(peak);
-                leds.set_both_bars(&bar);
+                let level = amplitude_to_level(peak);
+                leds.set_vu(level, level);
                 leds.update().await;
             }
             Err(_) => {
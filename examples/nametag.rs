@@ -5,7 +5,10 @@
 //! for an animated hue-cycling background, `BG="retrofuture"` for an
 //! animated synthwave road with a setting sun, or `BG="hearts"` for
 //! floating hearts), and `LEDS=heartbeat`, `LEDS=rainbow`, or `LEDS="FF8800"`
-//! (6-char hex RGB) for LED effects.
+//! (6-char hex RGB) for LED effects. Set `MARQUEE` (any value) to scroll the
+//! name across the screen in huge letters instead, using `BG`/`FG` as plain
+//! colours. Set `STROKE` (any value) to draw the name in smooth vector
+//! letters rather than the blocky bitmap font, also on plain colours.
 //!
 //! ```sh
 //! NAME="User" BG="rainbow" FG="E0E0E0" LEDS="heartbeat" cargo run --release --example nametag
@@ -13,6 +16,8 @@
 //! NAME="Speaker" cargo run --release --example nametag
 //! NAME="Love" BG="hearts" FG="FFE0E0" LEDS="heartbeat" cargo run --release --example nametag
 //! NAME="Hacker" BG="000000" FG="00FF00" LEDS="00FF00" cargo run --release --example nametag
+//! NAME="Hello, I am Alpaca" MARQUEE=1 FG="FF8800" cargo run --release --example nametag
+//! NAME="Alpaca" STROKE=1 FG="FF8800" cargo run --release --example nametag
 //! ```
 
 #![no_std]
@@ -24,7 +29,9 @@ use disobey2026badge::*;
 use embassy_executor::Spawner;
 use embassy_time::{Duration, Timer};
 use embedded_graphics::{
-    pixelcolor::Rgb565,
+    image::ImageRaw,
+    mono_font::ascii::FONT_10X20,
+    pixelcolor::{BinaryColor, Rgb565},
     prelude::*,
     primitives::Rectangle,
 };
@@ -42,6 +49,8 @@ const DEFAULT_NAME: Option<&str> = Some("Anonymous Alpaca");
 const LEDS: Option<&str> = option_env!("LEDS");
 const BG_STR: Option<&str> = option_env!("BG");
 const FG_STR: Option<&str> = option_env!("FG");
+const MARQUEE: bool = option_env!("MARQUEE").is_some();
+const STROKE: bool = option_env!("STROKE").is_some();
 const W: u32 = 320;
 const H: u32 = 170;
 
@@ -635,6 +644,64 @@ fn draw_frame(display: &mut Display, bg: Rgb565, layout: &NameLayout, buf: &mut
     display.fill_contiguous(&area, buf.iter().copied()).unwrap();
 }
 
+// ── Marquee mode ────────────────────────────────────────────────────────────
+
+/// FONT_10X20 blown up 7× fills most of the screen height.
+const MARQUEE_SCALE: u32 = 7;
+
+/// 16×12 heart separating repetitions of the name.
+#[rustfmt::skip]
+const HEART: ImageRaw<BinaryColor> = ImageRaw::new(&[
+    0x3C, 0x3C,
+    0x7E, 0x7E,
+    0xFF, 0xFF,
+    0xFF, 0xFF,
+    0xFF, 0xFF,
+    0x7F, 0xFE,
+    0x3F, 0xFC,
+    0x1F, 0xF8,
+    0x0F, 0xF0,
+    0x07, 0xE0,
+    0x03, 0xC0,
+    0x01, 0x80,
+], 16);
+
+async fn run_marquee(display: &mut Display<'_>) -> ! {
+    let font_h = FONT_10X20.character_size.height;
+    let mut strip = alloc::vec![0u8; 8 * 1024];
+    let mut marquee = Marquee::new(&mut strip, font_h)
+        .with_scale(MARQUEE_SCALE)
+        .with_colors(FG_COLOR, BG_COLOR);
+    marquee
+        .push_text(NAME.or(DEFAULT_NAME).unwrap(), &FONT_10X20)
+        .push_space(6)
+        .push_icon(&HEART)
+        .push_space(6);
+
+    let top = (H - marquee.screen_height()) as i32 / 2;
+    display.clear(BG_COLOR).unwrap();
+    loop {
+        marquee.draw(display, Point::new(0, top), W).unwrap();
+        marquee.step(3);
+        Timer::after(Duration::from_millis(20)).await;
+    }
+}
+
+// ── Stroke mode ─────────────────────────────────────────────────────────────
+
+/// Draw the name in vector letters as big as fit, centred.
+fn draw_stroke(display: &mut Display<'_>) {
+    let name = NAME.or(DEFAULT_NAME).unwrap();
+    let fit = StrokeText::new(name, Point::zero(), H - 40, FG_COLOR);
+    let width = fit.bounding_box().size.width.max(1);
+    let height = if width > W - 16 { (H - 40) * (W - 16) / width } else { H - 40 };
+    let text = StrokeText::new(name, Point::zero(), height, FG_COLOR);
+    let size = text.bounding_box().size;
+    let top_left = Point::new((W - size.width) as i32 / 2, (H - size.height) as i32 / 2);
+    display.clear(BG_COLOR).unwrap();
+    text.translate(top_left).draw(display).unwrap();
+}
+
 #[embassy_executor::task]
 async fn display_task(
     display: &'static mut disobey2026badge::Display<'static>,
@@ -643,6 +710,16 @@ async fn display_task(
     info!("Name tag: {}", NAME);
     backlight.on();
 
+    if MARQUEE {
+        run_marquee(display).await;
+    }
+    if STROKE {
+        draw_stroke(display);
+        loop {
+            Timer::after(Duration::from_secs(600)).await;
+        }
+    }
+
     let layout = NameLayout::compute();
 
     // Allocate a shared framebuffer on the heap (320×170 pixels × 2 bytes = 108,800 bytes).
@@ -688,11 +765,11 @@ async fn heartbeat_task(leds: &'static mut Leds<'static>) {
         // Double-beat pattern like a real heartbeat: lub-dub ... pause
         for &(brightness, ms) in &[
             // First beat (lub)
-            (30u8, 80u64),
-            (10, 100),
+            (96u8, 80u64),
+            (58, 100),
             // Second beat (dub)
-            (30, 80),
-            (5, 120),
+            (96, 80),
+            (41, 120),
             // Pause
             (0, 600),
         ] {
@@ -712,21 +789,7 @@ async fn rainbow_task(leds: &'static mut Leds<'static>) {
 
     let mut offset = 0u16;
     loop {
-        for i in 0..leds.len() {
-            let hue = ((offset + i as u16 * 25) % 360) as f32;
-            // Simple HSV→RGB with S=1, V=0.08 (dim)
-            let c = 0.08_f32;
-            let x = c * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
-            let (r, g, b) = match (hue as u16) / 60 {
-                0 => (c, x, 0.0),
-                1 => (x, c, 0.0),
-                2 => (0.0, c, x),
-                3 => (0.0, x, c),
-                4 => (x, 0.0, c),
-                _ => (c, 0.0, x),
-            };
-            leds.set(i, Srgb::new((r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8));
-        }
+        leds.fill_rainbow(offset, 25, 77);
         leds.update().await;
         offset = (offset + 3) % 360;
         Timer::after(Duration::from_millis(50)).await;
@@ -0,0 +1,197 @@
+//! Overlays — a `Compositor` keeping a status bar, a toast and a debug
+//! readout over a bouncing ball, which is drawn as if nothing covered it.
+//!
+//! - A shows a toast for two seconds
+//! - Select toggles the frame counter
+
+#![no_std]
+#![no_main]
+
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_time::{
+    Duration,
+    Instant,
+    Timer,
+};
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        ascii::{
+            FONT_6X10,
+            FONT_10X20,
+        },
+    },
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::{
+        Circle,
+        PrimitiveStyle,
+        Rectangle,
+    },
+    text::{
+        Alignment,
+        Text,
+    },
+};
+use esp_backtrace as _;
+use esp_hal::timer::timg::TimerGroup;
+use esp_println as _;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+const W: i32 = 320;
+const H: i32 = 170;
+const BALL: u32 = 24;
+
+const STATUS: Size = Size::new(320, 14);
+const TOAST: Size = Size::new(200, 32);
+const DEBUG: Size = Size::new(72, 12);
+
+/// How long a toast stays up.
+const TOAST_TIME: Duration = Duration::from_secs(2);
+
+/// Key colour the debug readout is drawn on, so the game shows through.
+const SEE_THROUGH: Rgb565 = Rgb565::MAGENTA;
+
+fn draw_status(layer: &mut Layer) {
+    let mut buf = [0u8; 8];
+    let uptime = clock::format_duration(Instant::MIN.elapsed(), &mut buf);
+    let style = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+    layer.clear(Rgb565::CSS_DARK_SLATE_GRAY).unwrap();
+    Text::new("Overlays", Point::new(4, 10), style)
+        .draw(layer)
+        .unwrap();
+    Text::with_alignment(uptime, Point::new(316, 10), style, Alignment::Right)
+        .draw(layer)
+        .unwrap();
+}
+
+fn draw_toast(layer: &mut Layer) {
+    let area = Rectangle::new(Point::zero(), TOAST);
+    area.into_styled(PrimitiveStyle::with_fill(Rgb565::CSS_NAVY))
+        .draw(layer)
+        .unwrap();
+    area.into_styled(PrimitiveStyle::with_stroke(Rgb565::WHITE, 1))
+        .draw(layer)
+        .unwrap();
+    let style = MonoTextStyle::new(&FONT_10X20, Rgb565::WHITE);
+    Text::with_alignment("Hello!", Point::new(100, 22), style, Alignment::Center)
+        .draw(layer)
+        .unwrap();
+}
+
+fn draw_debug(layer: &mut Layer, frames: u32) {
+    let style = MonoTextStyle::new(&FONT_6X10, Rgb565::YELLOW);
+    layer.clear(SEE_THROUGH).unwrap();
+    let text = alloc::format!("frame {frames}");
+    Text::new(&text, Point::new(0, 9), style)
+        .draw(layer)
+        .unwrap();
+}
+
+#[embassy_executor::task]
+async fn overlays_task(
+    display: &'static mut Display<'static>,
+    backlight: &'static mut Backlight,
+    buttons: &'static mut Buttons,
+) {
+    backlight.on();
+
+    let status = Layer::new(
+        mk_static!([Rgb565; 2 * 320 * 14], [Rgb565::BLACK; 2 * 320 * 14]),
+        STATUS,
+    );
+    let mut toast = Layer::new(
+        mk_static!([Rgb565; 2 * 200 * 32], [Rgb565::BLACK; 2 * 200 * 32]),
+        TOAST,
+    )
+    .with_position(Point::new(60, 120))
+    .hidden();
+    draw_toast(&mut toast);
+    let debug = Layer::new(
+        mk_static!([Rgb565; 2 * 72 * 12], [Rgb565::BLACK; 2 * 72 * 12]),
+        DEBUG,
+    )
+    .with_position(Point::new(4, 18))
+    .with_transparent(SEE_THROUGH)
+    .hidden();
+    let mut compositor = Compositor::new([status, toast, debug]);
+
+    let (mut x, mut y, mut dx, mut dy) = (40, 60, 3, 2);
+    let mut frames = 0u32;
+    let mut toast_until = None;
+    let mut select_was_down = false;
+    let mut ticker = FrameTicker::new(30);
+    loop {
+        if buttons.a.is_low() {
+            compositor.layer_mut(1).show();
+            toast_until = Some(Instant::now() + TOAST_TIME);
+        }
+        if toast_until.is_some_and(|until| Instant::now() >= until) {
+            compositor.layer_mut(1).hide();
+            toast_until = None;
+        }
+        let select_down = buttons.select.is_low();
+        if select_down && !select_was_down {
+            let debug = compositor.layer_mut(2);
+            if debug.is_visible() {
+                debug.hide();
+            } else {
+                debug.show();
+            }
+        }
+        select_was_down = select_down;
+
+        if frames.is_multiple_of(30) {
+            draw_status(compositor.layer_mut(0));
+        }
+        if compositor.layer_mut(2).is_visible() {
+            draw_debug(compositor.layer_mut(2), frames);
+        }
+
+        x += dx;
+        y += dy;
+        if x <= 0 || x >= W - BALL as i32 {
+            dx = -dx;
+        }
+        if y <= 0 || y >= H - BALL as i32 {
+            dy = -dy;
+        }
+        compositor
+            .frame(display, |fb| {
+                fb.clear(Rgb565::BLACK).unwrap();
+                Circle::new(Point::new(x, y), BALL)
+                    .into_styled(PrimitiveStyle::with_fill(Rgb565::CSS_ORANGE))
+                    .draw(fb)
+                    .unwrap();
+            })
+            .unwrap();
+
+        frames += 1;
+        ticker.next().await;
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let display = mk_static!(Display<'static>, resources.display.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+    spawner.must_spawn(overlays_task(display, backlight, buttons));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
@@ -0,0 +1,87 @@
+//! POV — wave the badge side to side in a dark room and the LED bars spell
+//! out a message in the air.
+//!
+//! The message comes from the `POV` env var (default `DISOBEY`).
+//!
+//! - Up / Down make the letters wider or narrower, to match your wave
+//! - A flips the direction, for waving the other way
+
+#![no_std]
+#![no_main]
+
+use defmt::info;
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use esp_backtrace as _;
+use esp_hal::timer::timg::TimerGroup;
+use esp_println as _;
+use palette::Srgb;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+const MESSAGE: &str = match option_env!("POV") {
+    Some(text) => text,
+    None => "DISOBEY",
+};
+
+/// Column time range, in microseconds.
+const MIN_COLUMN: u64 = 500;
+const MAX_COLUMN: u64 = 4000;
+const COLUMN_STEP: u64 = 250;
+
+#[embassy_executor::task]
+async fn pov_task(leds: &'static mut Leds<'static>, buttons: &'static mut Buttons) {
+    let mut column = 1000;
+    let mut reversed = false;
+    loop {
+        let pov = Pov::new(MESSAGE)
+            .with_color(Srgb::new(255, 40, 0))
+            .with_column_time(Duration::from_micros(column));
+        if reversed {
+            pov.reversed().play(leds).await;
+        } else {
+            pov.play(leds).await;
+        }
+
+        if buttons.up.is_low() && column < MAX_COLUMN {
+            column += COLUMN_STEP;
+            info!("Column time {=u64} us", column);
+        }
+        if buttons.down.is_low() && column > MIN_COLUMN {
+            column -= COLUMN_STEP;
+            info!("Column time {=u64} us", column);
+        }
+        if buttons.a.is_low() {
+            reversed = !reversed;
+            info!("Reversed: {=bool}", reversed);
+            // Give the button time to come up before the next check.
+            Timer::after(Duration::from_millis(300)).await;
+        }
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let leds = mk_static!(Leds<'static>, resources.leds.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+    spawner.must_spawn(pov_task(leds, buttons));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
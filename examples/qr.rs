@@ -0,0 +1,97 @@
+//! Shows a QR code with its text beside it.
+//!
+//! Set the contents at compile time with the `QR` environment variable, and
+//! optionally a caption with `LABEL`. The code is drawn as large as fits.
+//!
+//! ```sh
+//! QR="https://example.com/me" LABEL="Scan for my contact" cargo run --release --example qr
+//! ```
+
+#![no_std]
+#![no_main]
+
+use defmt::{
+    error,
+    info,
+};
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        ascii::{
+            FONT_6X10,
+            FONT_10X20,
+        },
+    },
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::Rectangle,
+};
+use esp_backtrace as _;
+use esp_hal::timer::timg::TimerGroup;
+use esp_println as _;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+const DATA: &str = match option_env!("QR") {
+    Some(data) => data,
+    None => "https://github.com/tanelikaivola/disobey2026badge",
+};
+const LABEL: Option<&str> = option_env!("LABEL");
+const W: u32 = 320;
+const H: u32 = 170;
+
+#[embassy_executor::task]
+async fn qr_task(display: &'static mut Display<'static>, backlight: &'static mut Backlight) {
+    display.clear(Rgb565::BLACK).unwrap();
+    backlight.on();
+
+    let Some(code) = qr::QrCode::new(DATA) else {
+        error!("QR data is {} bytes, too long", DATA.len());
+        return;
+    };
+    let scale = H / (code.size() + 2 * qr::QUIET_ZONE);
+    let size = code.draw(display, Point::zero(), scale).unwrap();
+    info!("QR version {}, {} px per module", code.version(), scale);
+
+    // The label and the text itself fill the space to the right.
+    let right = Rectangle::new(
+        Point::new(size.width as i32 + 8, 8),
+        Size::new(W - size.width - 16, H - 16),
+    );
+    let mut text_area = right;
+    if let Some(label) = LABEL {
+        let style = MonoTextStyle::new(&FONT_10X20, Rgb565::WHITE);
+        let height = TextBox::new(label, right, style).draw(display).unwrap();
+        text_area.top_left.y += height as i32 + 8;
+    }
+    let style = MonoTextStyle::new(&FONT_6X10, Rgb565::CSS_LIGHT_GRAY);
+    TextBox::new(DATA, text_area, style).draw(display).unwrap();
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let display = mk_static!(Display<'static>, resources.display.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    spawner.must_spawn(qr_task(display, backlight));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
@@ -0,0 +1,147 @@
+//! Screensaver — a screen that counts button presses and gives way to a
+//! screensaver after 15 seconds without input.
+//!
+//! `NICK` sets the text of the marquee animation.
+//!
+//! - Left / Right pick the animation
+//! - Start runs it right away
+//! - any button brings the app back, with its screen and LEDs restored
+
+#![no_std]
+#![no_main]
+
+use defmt::info;
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        ascii::{
+            FONT_6X10,
+            FONT_10X20,
+        },
+    },
+    pixelcolor::Rgb565,
+    prelude::*,
+    text::{
+        Alignment,
+        Text,
+    },
+};
+use esp_backtrace as _;
+use esp_hal::timer::timg::TimerGroup;
+use esp_println as _;
+use palette::Srgb;
+use screensaver::{
+    Animation,
+    Event,
+    Screensaver,
+};
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+const NICK: &str = match option_env!("NICK") {
+    Some(nick) => nick,
+    None => "Disobey 2026",
+};
+
+const LOGO: image::Rgb565Image = badge_image!("examples/assets/skrolli.png");
+
+const ANIMATIONS: [(&str, Animation); 4] = [
+    ("Logo", Animation::Logo(LOGO)),
+    ("Starfield", Animation::Starfield),
+    ("Marquee", Animation::Marquee(NICK)),
+    ("Clock", Animation::Clock),
+];
+
+const W: i32 = 320;
+const H: i32 = 170;
+
+fn draw(fb: &mut Framebuffer, presses: u32, animation: usize) {
+    fb.clear(Rgb565::CSS_MIDNIGHT_BLUE).unwrap();
+    let big = MonoTextStyle::new(&FONT_10X20, Rgb565::WHITE);
+    let small = MonoTextStyle::new(&FONT_6X10, Rgb565::CSS_LIGHT_GRAY);
+    let text = alloc::format!("{presses} presses");
+    Text::with_alignment(&text, Point::new(W / 2, 60), big, Alignment::Center)
+        .draw(fb)
+        .unwrap();
+    let text = alloc::format!("< {} >", ANIMATIONS[animation].0);
+    Text::with_alignment(&text, Point::new(W / 2, 100), big, Alignment::Center)
+        .draw(fb)
+        .unwrap();
+    Text::with_alignment(
+        "Idle 15 s or press Start for the screensaver",
+        Point::new(W / 2, H - 10),
+        small,
+        Alignment::Center,
+    )
+    .draw(fb)
+    .unwrap();
+}
+
+#[embassy_executor::task]
+async fn app_task(
+    display: &'static mut Display<'static>,
+    backlight: &'static mut Backlight,
+    buttons: &'static mut Buttons,
+    leds: &'static mut Leds<'static>,
+) {
+    backlight.on();
+    leds.fill(Srgb::new(0, 72, 0));
+    leds.update().await;
+
+    let mut animation = 0;
+    let mut presses = 0;
+    let mut saver = Screensaver::new(ANIMATIONS[animation].1).with_timeout(Duration::from_secs(15));
+    display.frame(|fb| draw(fb, presses, animation)).unwrap();
+
+    loop {
+        match saver.wait_for_press(display, buttons, leds).await.unwrap() {
+            Event::Press(button) => {
+                presses += 1;
+                match button {
+                    Button::Left => {
+                        animation = (animation + ANIMATIONS.len() - 1) % ANIMATIONS.len()
+                    }
+                    Button::Right => animation = (animation + 1) % ANIMATIONS.len(),
+                    Button::Start => {
+                        display.frame(|fb| draw(fb, presses, animation)).unwrap();
+                        saver.run(display, buttons, leds).await.unwrap();
+                    }
+                    _ => {}
+                }
+                saver.set_animation(ANIMATIONS[animation].1);
+            }
+            Event::Resumed => info!("Back from the screensaver"),
+        }
+        display.frame(|fb| draw(fb, presses, animation)).unwrap();
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let display = mk_static!(Display<'static>, resources.display.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+    let leds = mk_static!(Leds<'static>, resources.leds.into());
+    spawner.must_spawn(app_task(display, backlight, buttons, leds));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
@@ -0,0 +1,135 @@
+//! Self-test — runs the hardware checks in `selftest` and reports the
+//! results on screen and over defmt, one machine-readable line per check:
+//!
+//! ```text
+//! SELFTEST <name> PASS
+//! SELFTEST <name> FAIL <reason>
+//! SELFTEST overall PASS|FAIL
+//! ```
+//!
+//! Flash a batch of badges and grep the logs for `FAIL`. Don't touch the
+//! buttons while it runs. To test again, press A or send a `selftest`
+//! line to the USB serial port:
+//!
+//! ```sh
+//! echo selftest > /dev/ttyACM0
+//! ```
+
+#![no_std]
+#![no_main]
+
+use defmt::warn;
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_futures::select::{
+    Either,
+    select,
+};
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        ascii::FONT_10X20,
+    },
+    pixelcolor::Rgb565,
+    prelude::*,
+    text::Text,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    dma::DmaDescriptor,
+    timer::timg::TimerGroup,
+    usb_serial_jtag::UsbSerialJtag,
+};
+use esp_println as _;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+/// How long the test pattern stays up before the results replace it.
+const PATTERN_TIME: Duration = Duration::from_secs(2);
+
+#[embassy_executor::task]
+async fn selftest_task(
+    display: &'static mut Display<'static>,
+    backlight: &'static mut Backlight,
+    leds: &'static mut Leds<'static>,
+    buttons: &'static mut Buttons,
+    mic: &'static mut Microphone<'static>,
+    lines: &'static mut serial::Lines,
+) {
+    backlight.on();
+
+    loop {
+        let checks = selftest::run(display, leds, buttons, mic).await;
+        selftest::report(&checks);
+
+        Timer::after(PATTERN_TIME).await;
+        display
+            .frame(|fb| {
+                fb.clear(Rgb565::BLACK).unwrap();
+                for (row, check) in checks.iter().enumerate() {
+                    let (verdict, color) = if check.passed() {
+                        ("PASS", Rgb565::GREEN)
+                    } else {
+                        ("FAIL", Rgb565::RED)
+                    };
+                    let y = 26 + row as i32 * 24;
+                    let style = MonoTextStyle::new(&FONT_10X20, Rgb565::WHITE);
+                    Text::new(check.name, Point::new(40, y), style)
+                        .draw(fb)
+                        .unwrap();
+                    let style = MonoTextStyle::new(&FONT_10X20, color);
+                    Text::new(verdict, Point::new(240, y), style)
+                        .draw(fb)
+                        .unwrap();
+                }
+            })
+            .unwrap();
+
+        // Both are safe to cancel, so whichever comes first wins.
+        loop {
+            let a = async {
+                Buttons::debounce_press(&mut buttons.a).await;
+                Buttons::debounce_release(&mut buttons.a).await;
+            };
+            match select(a, lines.next()).await {
+                Either::First(()) | Either::Second("selftest") => break,
+                Either::Second(line) => warn!("Unknown command {=str}", line),
+            }
+        }
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let display = mk_static!(Display<'static>, resources.display.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    let leds = mk_static!(Leds<'static>, resources.leds.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+    let descriptors = mk_static!([DmaDescriptor; 8], [DmaDescriptor::EMPTY; 8]);
+    let mic = mk_static!(
+        Microphone<'static>,
+        Microphone::new(resources.mic, microphone::DEFAULT_SAMPLE_RATE, descriptors)
+    );
+    let (rx, _) = UsbSerialJtag::new(peripherals.USB_DEVICE).split();
+    let lines = mk_static!(serial::Lines, serial::Lines::new(rx));
+    spawner.must_spawn(selftest_task(display, backlight, leds, buttons, mic, lines));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
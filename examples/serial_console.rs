@@ -0,0 +1,210 @@
+//! Serial console — shows what a device prints on its UART.
+//!
+//! Connect the badge's TX pin to the device's RX, the badge's RX to its TX,
+//! and the grounds. The UART runs at 3.3 V; a device with RS-232 levels
+//! needs a level shifter. Everything received is also logged over USB, so
+//! it shows up in `espflash monitor` too.
+//!
+//! Pick the pins at compile time with `TX` and `RX`, from the GPIOs the
+//! badge doesn't use itself: 9, 10, 39, 40, 41, 42, 47 (default TX) or 48
+//! (default RX). `BAUD` sets the starting baud rate, from 1200 to 115200
+//! (default).
+//!
+//! ```sh
+//! TX=9 RX=10 BAUD=9600 cargo run --release --example serial_console
+//! ```
+//!
+//! - A sends Enter, B sends Ctrl-C
+//! - Up / Down change the baud rate
+//! - Select clears the screen
+
+#![no_std]
+#![no_main]
+
+use defmt::{
+    info,
+    warn,
+};
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_futures::select::{
+    Either,
+    select,
+};
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        ascii::FONT_6X10,
+    },
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::Rectangle,
+    text::{
+        Baseline,
+        Text,
+    },
+};
+use esp_backtrace as _;
+use esp_hal::{
+    Async,
+    gpio::AnyPin,
+    timer::timg::TimerGroup,
+    uart::{
+        self,
+        Uart,
+    },
+};
+use esp_println as _;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+const TX: &str = match option_env!("TX") {
+    Some(pin) => pin,
+    None => "47",
+};
+const RX: &str = match option_env!("RX") {
+    Some(pin) => pin,
+    None => "48",
+};
+const BAUD: Option<&str> = option_env!("BAUD");
+
+/// Baud rates Up and Down step through.
+const BAUD_RATES: [u32; 8] = [1200, 2400, 4800, 9600, 19_200, 38_400, 57_600, 115_200];
+
+const W: i32 = 320;
+const HEADER_H: i32 = 12;
+const H: i32 = 170;
+/// Characters in the console, 53 columns of 15 rows of 6×10 text.
+const CELLS: usize = 53 * 15;
+
+fn draw_header(display: &mut Display, baud: u32) {
+    let area = Rectangle::new(Point::zero(), Size::new(W as u32, HEADER_H as u32));
+    display
+        .fill_solid(&area, Rgb565::CSS_DARK_SLATE_GRAY)
+        .unwrap();
+    let style = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+    let text = alloc::format!("TX {TX} RX {RX}  {baud} baud   A Enter  B Ctrl-C");
+    Text::with_baseline(&text, Point::new(2, 1), style, Baseline::Top)
+        .draw(display)
+        .unwrap();
+}
+
+// ── Tasks ───────────────────────────────────────────────────────────────────
+
+#[embassy_executor::task]
+async fn console_task(
+    display: &'static mut Display<'static>,
+    backlight: &'static mut Backlight,
+    buttons: &'static mut Buttons,
+    mut uart: Uart<'static, Async>,
+    mut baud_index: usize,
+) {
+    backlight.on();
+    display.clear(Rgb565::BLACK).unwrap();
+    draw_header(display, BAUD_RATES[baud_index]);
+
+    let mut cells = [0u8; CELLS];
+    let area = Rectangle::new(
+        Point::new(0, HEADER_H),
+        Size::new(W as u32, (H - HEADER_H) as u32),
+    );
+    let style = MonoTextStyle::new(&FONT_6X10, Rgb565::CSS_LIGHT_GREEN);
+    let mut console = Console::new(&mut cells, area, style);
+    let mut buf = [0u8; 256];
+    loop {
+        console.draw(display).unwrap();
+        match select(uart.read_async(&mut buf), buttons.wait_for_press()).await {
+            Either::First(Ok(n)) => {
+                info!("{=[u8]:a}", &buf[..n]);
+                console.write_bytes(&buf[..n]);
+            }
+            Either::First(Err(e)) => warn!("UART error: {}", e),
+            Either::Second(Button::A) => {
+                uart.write_async(b"\r").await.unwrap();
+            }
+            Either::Second(Button::B) => {
+                uart.write_async(b"\x03").await.unwrap();
+            }
+            Either::Second(Button::Select) => console.clear(),
+            Either::Second(button @ (Button::Up | Button::Down)) => {
+                let step = if button == Button::Up {
+                    1
+                } else {
+                    BAUD_RATES.len() - 1
+                };
+                baud_index = (baud_index + step) % BAUD_RATES.len();
+                let baud = BAUD_RATES[baud_index];
+                info!("Baud rate {}", baud);
+                uart.apply_config(&uart::Config::default().with_baudrate(baud))
+                    .unwrap();
+                draw_header(display, baud);
+            }
+            Either::Second(_) => {}
+        }
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let mut spare: [(&str, Option<AnyPin>); 8] = [
+        ("9", Some(peripherals.GPIO9.into())),
+        ("10", Some(peripherals.GPIO10.into())),
+        ("39", Some(peripherals.GPIO39.into())),
+        ("40", Some(peripherals.GPIO40.into())),
+        ("41", Some(peripherals.GPIO41.into())),
+        ("42", Some(peripherals.GPIO42.into())),
+        ("47", Some(peripherals.GPIO47.into())),
+        ("48", Some(peripherals.GPIO48.into())),
+    ];
+    let mut take = |name: &str| {
+        spare
+            .iter_mut()
+            .find(|(number, _)| *number == name)
+            .and_then(|(_, pin)| pin.take())
+            .expect("TX and RX must be two different pins of 9, 10, 39, 40, 41, 42, 47 and 48")
+    };
+    let (tx, rx) = (take(TX), take(RX));
+
+    let baud_index = BAUD.map_or(BAUD_RATES.len() - 1, |baud| {
+        BAUD_RATES
+            .iter()
+            .position(|rate| baud.parse() == Ok(*rate))
+            .expect("BAUD must be one of 1200, 2400, 4800, 9600, 19200, 38400, 57600 or 115200")
+    });
+    info!(
+        "UART on TX GPIO{=str}, RX GPIO{=str} at {} baud",
+        TX, RX, BAUD_RATES[baud_index]
+    );
+    let uart = Uart::new(
+        peripherals.UART1,
+        uart::Config::default().with_baudrate(BAUD_RATES[baud_index]),
+    )
+    .unwrap()
+    .with_tx(tx)
+    .with_rx(rx)
+    .into_async();
+
+    let display = mk_static!(Display<'static>, resources.display.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+    spawner.must_spawn(console_task(display, backlight, buttons, uart, baud_index));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
@@ -0,0 +1,213 @@
+//! Servo tester — drives a hobby servo from a spare GPIO pin.
+//!
+//! Wire the servo's signal lead to the pin, and its power to a supply that
+//! can deliver the servo's stall current; the badge's 3.3 V rail can't.
+//! Connect the grounds.
+//!
+//! Pick the pin at compile time with `PIN`, one of the GPIOs the badge
+//! doesn't use itself: 9, 10, 39, 40, 41, 42, 47 or 48 (default).
+//!
+//! ```sh
+//! PIN=9 cargo run --release --example servo
+//! ```
+//!
+//! - Left / Right turn the servo 10° at a time
+//! - A centers it, B sweeps back and forth
+//! - Start releases it
+
+#![no_std]
+#![no_main]
+
+use defmt::info;
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_futures::select::{
+    Either,
+    select,
+};
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        ascii::FONT_6X10,
+    },
+    pixelcolor::Rgb565,
+    prelude::*,
+    text::{
+        Alignment,
+        Text,
+    },
+};
+use esp_backtrace as _;
+use esp_hal::{
+    gpio::AnyPin,
+    timer::timg::TimerGroup,
+};
+use esp_println as _;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+const PIN: &str = match option_env!("PIN") {
+    Some(pin) => pin,
+    None => "48",
+};
+
+const W: i32 = 320;
+const H: i32 = 170;
+
+const STEP: u8 = 10;
+const SWEEP_STEP: u8 = 2;
+const SWEEP_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Centre of the gauge showing the angle.
+const GAUGE: Point = Point::new(W / 2, H - 30);
+const GAUGE_DIAMETER: u32 = 200;
+
+/// What the servo is doing.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Hold(u8),
+    Sweep { angle: u8, rising: bool },
+    Released,
+}
+
+fn draw(display: &mut Display, mode: Mode) {
+    display.clear(Rgb565::BLACK).unwrap();
+    let style = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+    let (angle, status) = match mode {
+        Mode::Hold(angle) => (Some(angle), alloc::format!("GPIO{PIN}  {angle} deg")),
+        Mode::Sweep { angle, .. } => (Some(angle), alloc::format!("GPIO{PIN}  sweeping")),
+        Mode::Released => (None, alloc::format!("GPIO{PIN}  released")),
+    };
+    Text::with_alignment(&status, Point::new(W / 2, 14), style, Alignment::Center)
+        .draw(display)
+        .unwrap();
+    Text::with_alignment(
+        "Left/Right turn  A center  B sweep  Start release",
+        Point::new(W / 2, H - 6),
+        MonoTextStyle::new(&FONT_6X10, Rgb565::CSS_GRAY),
+        Alignment::Center,
+    )
+    .draw(display)
+    .unwrap();
+    draw_gauge(display, angle.unwrap_or(0), angle.is_some());
+}
+
+/// A half-circle gauge filled from the left round to `angle`, so 0° is on the
+/// right and 180° on the left, like the servo horn seen from above.
+fn draw_gauge(display: &mut Display, angle: u8, active: bool) {
+    let color = if active {
+        Rgb565::YELLOW
+    } else {
+        Rgb565::CSS_DIM_GRAY
+    };
+    Gauge::new(GAUGE, GAUGE_DIAMETER, u32::from(180 - angle.min(180)), 180)
+        .with_arc(180.0.deg(), 180.0.deg())
+        .with_thickness(12)
+        .with_colors(color, Rgb565::CSS_DIM_GRAY)
+        .draw(display)
+        .unwrap();
+}
+
+#[embassy_executor::task]
+async fn servo_task(
+    display: &'static mut Display<'static>,
+    backlight: &'static mut Backlight,
+    buttons: &'static mut Buttons,
+    pin: AnyPin<'static>,
+) {
+    info!("Servo on GPIO{=str}", PIN);
+    backlight.on();
+
+    let mut pwm = backlight.take_pwm().unwrap();
+    let mut servo = pwm.servo(pin).unwrap();
+    let mut mode = Mode::Hold(90);
+    servo.set_angle(90);
+    draw(display, mode);
+
+    loop {
+        let press = match mode {
+            Mode::Sweep { .. } => {
+                match select(buttons.wait_for_press(), Timer::after(SWEEP_INTERVAL)).await {
+                    Either::First(button) => Some(button),
+                    Either::Second(()) => None,
+                }
+            }
+            _ => Some(buttons.wait_for_press().await),
+        };
+        let angle = match mode {
+            Mode::Hold(angle) | Mode::Sweep { angle, .. } => angle,
+            Mode::Released => 90,
+        };
+        mode = match (press, mode) {
+            (Some(Button::Left), _) => Mode::Hold(angle.saturating_add(STEP).min(180)),
+            (Some(Button::Right), _) => Mode::Hold(angle.saturating_sub(STEP)),
+            (Some(Button::A), _) => Mode::Hold(90),
+            (Some(Button::B), Mode::Sweep { .. }) => Mode::Hold(angle),
+            (Some(Button::B), _) => Mode::Sweep {
+                angle,
+                rising: angle < 180,
+            },
+            (Some(Button::Start), _) => Mode::Released,
+            (None, Mode::Sweep { angle, rising }) => {
+                let next = if rising {
+                    angle.saturating_add(SWEEP_STEP).min(180)
+                } else {
+                    angle.saturating_sub(SWEEP_STEP)
+                };
+                Mode::Sweep {
+                    angle: next,
+                    rising: if rising { next < 180 } else { next == 0 },
+                }
+            }
+            (_, mode) => mode,
+        };
+        match mode {
+            Mode::Hold(angle) | Mode::Sweep { angle, .. } => servo.set_angle(angle),
+            Mode::Released => servo.release(),
+        }
+        if press.is_some() {
+            draw(display, mode);
+        } else if let Mode::Sweep { angle, .. } = mode {
+            draw_gauge(display, angle, true);
+        }
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let pin: AnyPin = match PIN {
+        "9" => peripherals.GPIO9.into(),
+        "10" => peripherals.GPIO10.into(),
+        "39" => peripherals.GPIO39.into(),
+        "40" => peripherals.GPIO40.into(),
+        "41" => peripherals.GPIO41.into(),
+        "42" => peripherals.GPIO42.into(),
+        "47" => peripherals.GPIO47.into(),
+        "48" => peripherals.GPIO48.into(),
+        _ => panic!("PIN must be one of 9, 10, 39, 40, 41, 42, 47 or 48"),
+    };
+
+    let display = mk_static!(Display<'static>, resources.display.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+    spawner.must_spawn(servo_task(display, backlight, buttons, pin));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
@@ -0,0 +1,104 @@
+//! Settings — a small settings screen built with `screen!`, capping the LED
+//! brightness for the whole badge.
+//!
+//! - Up / Down move through the list and on to the buttons, Left / Right
+//!   between the buttons
+//! - A picks a brightness or presses a button
+//! - B turns the LEDs off
+//! - Select turns the accessibility settings on or off: large high-contrast
+//!   text, slower animations and a buzz on every focus change
+
+#![no_std]
+#![no_main]
+
+use defmt::info;
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use esp_backtrace as _;
+use esp_hal::timer::timg::TimerGroup;
+use esp_println as _;
+use palette::Srgb;
+use ui::{
+    Accessibility,
+    Event,
+};
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+const LEVELS: [&str; 5] = ["Full", "High", "Medium", "Low", "Night"];
+const BRIGHTNESS: [u8; 5] = [255, 160, 96, 48, 16];
+
+/// Widget indices on the screen. The row counts as one before its buttons.
+const LIST: usize = 1;
+const RAINBOW: usize = 3;
+
+#[embassy_executor::task]
+async fn settings_task(
+    display: &'static mut Display<'static>,
+    backlight: &'static mut Backlight,
+    leds: &'static mut Leds<'static>,
+    buttons: &'static mut Buttons,
+) {
+    backlight.on();
+    let mut screen = screen! {
+        column {
+            label("LED brightness"),
+            list(&LEVELS),
+            row { button("Rainbow"), button("White") },
+        }
+    };
+
+    loop {
+        display.frame(|fb| screen.draw(fb).unwrap()).unwrap();
+        let button = buttons.wait_for_press().await;
+        if button == Button::Select {
+            let on = ui::accessibility() == Accessibility::OFF;
+            info!("Accessibility: {=bool}", on);
+            ui::set_accessibility(if on { Accessibility::ALL } else { Accessibility::OFF });
+            continue;
+        }
+        match screen.handle(button) {
+            Some(Event::Chosen { item, .. }) => {
+                info!("Brightness {=str}", LEVELS[item]);
+                leds.set_global_brightness(BRIGHTNESS[item]);
+            }
+            Some(Event::Pressed(RAINBOW)) => leds.fill_rainbow(0, 36, 255),
+            Some(Event::Pressed(_)) => leds.fill(Srgb::new(255, 255, 255)),
+            Some(Event::Back) => leds.clear(),
+            None => {
+                leds.set_global_brightness(BRIGHTNESS[screen.selected(LIST)]);
+            }
+        }
+        leds.update().await;
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let display = mk_static!(Display<'static>, resources.display.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    let leds = mk_static!(Leds<'static>, resources.leds.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+    let vibration = mk_static!(Vibration, resources.vibra.into());
+    spawner.must_spawn(ui::focus_cue_task(vibration));
+    spawner.must_spawn(settings_task(display, backlight, leds, buttons));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
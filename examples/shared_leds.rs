@@ -0,0 +1,109 @@
+//! Shared LEDs — two tasks set the LEDs at the same time through
+//! `LedHandle`s while a `LedService` task sends them to the strip.
+//!
+//! - The left bar breathes blue on its own
+//! - The right bar fills up from the bottom, one LED per button press
+//! - B empties it again
+//! - Every ten seconds a notification blinks both bars white, then leaves
+//!   them as the tasks set them
+//! - After 20 seconds without a press the backlight dims and the LEDs only
+//!   flash now and then, to save power, until the next press
+
+#![no_std]
+#![no_main]
+
+use defmt::info;
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use esp_backtrace as _;
+use esp_hal::timer::timg::TimerGroup;
+use esp_println as _;
+use notification::Notification;
+use palette::Srgb;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+static LEDS: LedService = LedService::new().with_rate(60);
+
+const OFF: Srgb<u8> = Srgb::new(0, 0, 0);
+const LEVEL: Srgb<u8> = Srgb::new(160, 96, 0);
+const IDLE_AFTER: Duration = Duration::from_secs(20);
+
+#[embassy_executor::task]
+async fn breathe_task(leds: LedHandle) {
+    let mut step = 0u16;
+    loop {
+        // Triangle wave up and down over two seconds.
+        let level = if step < 50 { step } else { 100 - step } as u8 * 4;
+        leds.set_left_bar(&[Srgb::new(0, 0, level); BAR_COUNT]);
+        step = (step + 1) % 100;
+        Timer::after(Duration::from_millis(20)).await;
+    }
+}
+
+#[embassy_executor::task]
+async fn level_task(
+    leds: LedHandle,
+    buttons: &'static mut Buttons,
+    backlight: &'static mut Backlight,
+) {
+    let mut auto_dim = AutoDim::new()
+        .with_dim_after(IDLE_AFTER)
+        .with_led_power_save(leds);
+    let mut level = 0;
+    loop {
+        let button = auto_dim.wait_for_press(buttons, backlight).await;
+        level = if button == Button::B {
+            0
+        } else {
+            (level + 1).min(BAR_COUNT)
+        };
+        info!("Level {=usize}", level);
+        leds.set_right_bar(&core::array::from_fn(
+            |i| if i < level { LEVEL } else { OFF },
+        ));
+    }
+}
+
+#[embassy_executor::task]
+async fn message_task() {
+    let blink = Notification::new(Srgb::new(255, 255, 255), Duration::from_secs(2))
+        .with_blink(Duration::from_millis(250));
+    loop {
+        Timer::after(Duration::from_secs(10)).await;
+        info!("Message");
+        notification::post(blink);
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let leds = mk_static!(Leds<'static>, resources.leds.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    backlight.on();
+    spawner.must_spawn(led_service_task(&LEDS, leds));
+    spawner.must_spawn(breathe_task(LEDS.handle()));
+    spawner.must_spawn(level_task(LEDS.handle(), buttons, backlight));
+    spawner.must_spawn(message_task());
+    LEDS.handle().set_crossfade(Duration::from_millis(80));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
@@ -63,7 +63,7 @@ const MAX_VIEW_Z: i32 = (GRID_DEPTH as i32 - 4) * CELL_LENGTH;
 const FP: i32 = 256;
 const LANE_MOVE_SPEED: i32 = 20; // pixels per tick of lateral movement
 
-const TICK_MS: u64 = 25;
+const FPS: u32 = 40;
 
 /// Cell types on the grid.
 #[derive(Clone, Copy, PartialEq)]
@@ -769,7 +769,7 @@ async fn game_task(leds: &'static mut Leds<'static>) {
                 break;
             }
             let bright = if t < 32 { t } else { 64 - t };
-            leds.fill(Srgb::new(0, bright / 2, bright));
+            leds.fill(Srgb::new(0, bright * 2, bright * 3));
             leds.update().await;
             t = (t + 1) % 64;
             Timer::after(Duration::from_millis(30)).await;
@@ -781,7 +781,7 @@ async fn game_task(leds: &'static mut Leds<'static>) {
 
         // ── Game loop ───────────────────────────────────────────────────
         let mut game = Game::new();
-        let tick = Duration::from_millis(TICK_MS);
+        let mut ticker = FrameTicker::new(FPS);
 
         while game.alive {
             game.tick();
@@ -798,28 +798,28 @@ async fn game_task(leds: &'static mut Leds<'static>) {
             let speed_frac = ((game.speed / 256 - 2) * 5 / 4).clamp(0, 4) as usize;
             let mut bar = [Srgb::new(0u8, 0, 0); BAR_COUNT];
             for i in 0..=speed_frac {
-                bar[i] = Srgb::new(0, (5 + i * 4) as u8, (10 - i * 2) as u8);
+                bar[i] = Srgb::new(0, (41 + i * 10) as u8, (58 - i * 8) as u8);
             }
             if game.jump_tick > 0 {
-                bar[4] = Srgb::new(0, 0, 20);
+                bar[4] = Srgb::new(0, 0, 80);
             }
             if game.in_tunnel {
-                bar[0] = Srgb::new(10, 10, 2);
+                bar[0] = Srgb::new(58, 58, 25);
             }
             if game.fall_timer > 0 || game.crash_timer > 0 {
-                leds.fill(Srgb::new(20, 0, 0));
+                leds.fill(Srgb::new(80, 0, 0));
             } else {
                 leds.set_both_bars(&bar);
             }
             leds.update().await;
 
-            Timer::after(tick).await;
+            ticker.next().await;
         }
 
         // ── Death ───────────────────────────────────────────────────────
         for flash in 0..6 {
             if flash % 2 == 0 {
-                leds.fill(Srgb::new(25, 0, 0));
+                leds.fill(Srgb::new(88, 0, 0));
             } else {
                 leds.clear();
             }
@@ -4,6 +4,8 @@
 //! - Eat food to grow and gain points
 //! - Avoid hitting walls and yourself
 //! - Press A to start / restart after game over
+//!
+//! Screens change with the effects from [`transition`].
 
 #![no_std]
 #![no_main]
@@ -12,13 +14,14 @@ use defmt::info;
 #[allow(clippy::wildcard_imports)]
 use disobey2026badge::*;
 use embassy_executor::Spawner;
+use embassy_futures::select::select;
 use embassy_time::{Duration, Timer};
 use embedded_graphics::{
     mono_font::{MonoTextStyle, iso_8859_1::FONT_6X10},
     pixelcolor::Rgb565,
     prelude::*,
     primitives::{PrimitiveStyle, Rectangle},
-    text::Text,
+    text::{Alignment, Text},
 };
 use esp_backtrace as _;
 use esp_hal::timer::timg::TimerGroup;
@@ -40,7 +43,7 @@ const GRID_W: i32 = W / GRID_SIZE;
 const GRID_H: i32 = H / GRID_SIZE;
 
 // Game parameters
-const TICK_MS: u64 = 100;
+const FPS: u32 = 10;
 
 const SNAKE_COLOR: Rgb565 = Rgb565::GREEN;
 const FOOD_COLOR: Rgb565 = Rgb565::RED;
@@ -254,22 +257,30 @@ fn draw_frame(display: &mut Display, game: &Game) {
     draw_hud(display, game.score);
 }
 
+/// A full-width area from `y` to the bottom of the screen.
+fn text_row(y: i32) -> Rectangle {
+    Rectangle::new(Point::new(0, y), Size::new(W as u32, (H - y) as u32))
+}
+
 fn draw_title(display: &mut Display) {
     Rectangle::new(Point::zero(), Size::new(W as u32, H as u32))
         .into_styled(PrimitiveStyle::with_fill(Rgb565::BLACK))
         .draw(display)
         .unwrap();
 
-    let big = MonoTextStyle::new(&FONT_6X10, Rgb565::CSS_YELLOW);
+    let big = MonoTextStyle::new(&font::FONT_20X40, Rgb565::CSS_YELLOW);
     let small = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
 
-    Text::new("SNAKE", Point::new(W / 2 - 15, H / 2 - 20), big)
+    TextBox::new("SNAKE", text_row(H / 2 - 60), big)
+        .with_alignment(Alignment::Center)
         .draw(display)
         .unwrap();
-    Text::new("D-pad to move", Point::new(W / 2 - 42, H / 2 - 5), small)
+    TextBox::new("D-pad to move", text_row(H / 2 - 12), small)
+        .with_alignment(Alignment::Center)
         .draw(display)
         .unwrap();
-    Text::new("Press A to start", Point::new(W / 2 - 48, H / 2 + 10), small)
+    TextBox::new("Press A to start", text_row(H / 2 + 3), small)
+        .with_alignment(Alignment::Center)
         .draw(display)
         .unwrap();
 }
@@ -280,20 +291,23 @@ fn draw_game_over(display: &mut Display, score: u16) {
         .draw(display)
         .unwrap();
 
-    let style = MonoTextStyle::new(&FONT_6X10, Rgb565::RED);
+    let style = MonoTextStyle::new(&font::FONT_20X40, Rgb565::RED);
     let small = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
 
-    Text::new("GAME OVER", Point::new(W / 2 - 36, H / 2 - 15), style)
+    TextBox::new("GAME OVER", text_row(H / 2 - 55), style)
+        .with_alignment(Alignment::Center)
         .draw(display)
         .unwrap();
 
     let mut buf = [0u8; 24];
     let score_str = format_score(score, &mut buf);
-    Text::new(score_str, Point::new(W / 2 - 36, H / 2 + 0), small)
+    TextBox::new(score_str, text_row(H / 2 - 7), small)
+        .with_alignment(Alignment::Center)
         .draw(display)
         .unwrap();
 
-    Text::new("Press A to restart", Point::new(W / 2 - 54, H / 2 + 20), small)
+    TextBox::new("Press A to restart", text_row(H / 2 + 13), small)
+        .with_alignment(Alignment::Center)
         .draw(display)
         .unwrap();
 }
@@ -329,7 +343,7 @@ fn format_score(score: u16, buf: &mut [u8; 24]) -> &str {
 
 fn update_leds(leds: &mut Leds, game: &Game) {
     if game.game_over {
-        leds.fill(Srgb::new(20, 0, 0));
+        leds.fill(Srgb::new(80, 0, 0));
     } else {
         // Show score as LED bar graph
         let lit = (game.score as usize).min(BAR_COUNT);
@@ -337,7 +351,7 @@ fn update_leds(leds: &mut Leds, game: &Game) {
         let mut right = [Srgb::new(0u8, 0, 0); BAR_COUNT];
 
         for i in 0..lit {
-            let color = Srgb::new(0, 10, 0);
+            let color = Srgb::new(0, 58, 0);
             if i < BAR_COUNT / 2 {
                 left[i] = color;
             } else {
@@ -367,11 +381,14 @@ async fn game_task(
 
         // Wait for A press
         Buttons::debounce_press(&mut buttons.a).await;
+        transition::wipe_left(display, Rgb565::BLACK, Duration::from_millis(300))
+            .await
+            .unwrap();
 
         // Game loop
         let mut game = Game::new();
         draw_initial(display, &game);
-        let tick = Duration::from_millis(TICK_MS);
+        let mut ticker = FrameTicker::new(FPS);
 
         loop {
             // Poll d-pad for next direction
@@ -391,25 +408,30 @@ async fn game_task(
             leds.update().await;
 
             if game.game_over {
-                Timer::after(Duration::from_millis(500)).await;
+                transition::dissolve(display, Rgb565::BLACK, Duration::from_millis(500))
+                    .await
+                    .unwrap();
                 draw_game_over(display, game.score);
 
-                // Flash LEDs for game over
-                for _ in 0..3 {
-                    leds.fill(Srgb::new(20, 0, 0));
-                    leds.update().await;
-                    Timer::after(Duration::from_millis(300)).await;
-                    leds.clear();
-                    leds.update().await;
-                    Timer::after(Duration::from_millis(300)).await;
-                }
-
-                // Wait for restart
-                Buttons::debounce_press(&mut buttons.a).await;
+                // Flash LEDs for game over while waiting for restart
+                let mut animator = LedAnimator::new();
+                animator.play(Effect::Blink {
+                    color: Srgb::new(80, 0, 0),
+                    period: Duration::from_millis(600),
+                    count: 3,
+                });
+                select(
+                    Buttons::debounce_press(&mut buttons.a),
+                    animator.run(leds),
+                )
+                .await;
+                transition::fade_to_black(display, backlight, Duration::from_millis(300))
+                    .await
+                    .unwrap();
                 break; // Restart outer loop
             }
 
-            Timer::after(tick).await;
+            ticker.next().await;
         }
     }
 }
@@ -0,0 +1,441 @@
+//! Sokoban — push every crate onto a goal.
+//!
+//! The puzzles come from a [`level::Pack`] in the usual Sokoban notation:
+//! `#` wall, `.` goal, `$` crate, `*` crate on a goal, `@` player and `+`
+//! player on a goal. Add your own by appending levels to
+//! `examples/assets/sokoban/starter.txt`.
+//!
+//! - D-pad walks and pushes
+//! - B undoes the last move, as far back as you like
+//! - Start restarts the level
+//! - Select skips to the next level; A continues once a level is solved
+//!
+//! The backlight dims after 30 seconds without a press and turns off after
+//! two minutes; the press that turns it back on is ignored.
+
+#![no_std]
+#![no_main]
+
+use alloc::vec::Vec;
+
+use defmt::{
+    info,
+    warn,
+};
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        ascii::FONT_6X10,
+    },
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::{
+        Circle,
+        PrimitiveStyle,
+        PrimitiveStyleBuilder,
+        Rectangle,
+    },
+    text::{
+        Alignment,
+        Baseline,
+        Text,
+    },
+};
+use esp_backtrace as _;
+use esp_hal::timer::timg::TimerGroup;
+use esp_println as _;
+use level::{
+    Level,
+    Pack,
+};
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+const PACK: Pack = Pack::new(include_str!("assets/sokoban/starter.txt"));
+
+const W: i32 = 320;
+const H: i32 = 170;
+const HUD_H: i32 = 14;
+const MAX_TILE: i32 = 16;
+
+const MAX_W: usize = 24;
+const MAX_H: usize = 12;
+
+const WALL: Rgb565 = Rgb565::new(14, 20, 16);
+const FLOOR: Rgb565 = Rgb565::new(3, 6, 4);
+const GOAL: Rgb565 = Rgb565::new(31, 20, 0);
+const CRATE: Rgb565 = Rgb565::new(20, 34, 6);
+const CRATE_DONE: Rgb565 = Rgb565::new(4, 48, 8);
+const PLAYER: Rgb565 = Rgb565::CSS_YELLOW;
+
+// ── Puzzle engine ───────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Outside,
+    Floor,
+    Wall,
+}
+
+#[derive(Clone, Copy)]
+enum Dir {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Dir {
+    const fn delta(self) -> (isize, isize) {
+        match self {
+            Self::Up => (0, -1),
+            Self::Down => (0, 1),
+            Self::Left => (-1, 0),
+            Self::Right => (1, 0),
+        }
+    }
+}
+
+/// One move, enough to take it back.
+#[derive(Clone, Copy)]
+struct Step {
+    dir: Dir,
+    pushed: bool,
+}
+
+struct Puzzle {
+    width: usize,
+    height: usize,
+    tiles: [[Tile; MAX_W]; MAX_H],
+    goals: [[bool; MAX_W]; MAX_H],
+    crates: [[bool; MAX_W]; MAX_H],
+    player: (usize, usize),
+    history: Vec<Step>,
+    pushes: u16,
+}
+
+impl Puzzle {
+    /// Build a puzzle from a level, checking that it is playable.
+    fn load(level: &Level) -> Result<Self, &'static str> {
+        if level.width() > MAX_W || level.height() > MAX_H {
+            return Err("level too large");
+        }
+        let mut puzzle = Self {
+            width: level.width(),
+            height: level.height(),
+            tiles: [[Tile::Outside; MAX_W]; MAX_H],
+            goals: [[false; MAX_W]; MAX_H],
+            crates: [[false; MAX_W]; MAX_H],
+            player: (0, 0),
+            history: Vec::new(),
+            pushes: 0,
+        };
+        let (mut players, mut goals, mut crates) = (0, 0, 0);
+        for (y, row) in level.rows().enumerate() {
+            for (x, cell) in row.chars().enumerate() {
+                if cell == '#' {
+                    puzzle.tiles[y][x] = Tile::Wall;
+                }
+                if matches!(cell, '.' | '*' | '+') {
+                    puzzle.goals[y][x] = true;
+                    goals += 1;
+                }
+                if matches!(cell, '$' | '*') {
+                    puzzle.crates[y][x] = true;
+                    crates += 1;
+                }
+                if matches!(cell, '@' | '+') {
+                    puzzle.player = (x, y);
+                    players += 1;
+                }
+                if !matches!(cell, '#' | '.' | '*' | '$' | '@' | '+' | ' ' | '-' | '_') {
+                    return Err("unknown cell");
+                }
+            }
+        }
+        if players != 1 {
+            return Err("needs exactly one player");
+        }
+        if goals == 0 || goals != crates {
+            return Err("needs as many crates as goals");
+        }
+        puzzle.mark_floor();
+        Ok(puzzle)
+    }
+
+    /// Flood fill from the player so only the reachable inside is floor.
+    fn mark_floor(&mut self) {
+        let mut stack = Vec::new();
+        stack.push(self.player);
+        while let Some((x, y)) = stack.pop() {
+            if self.tiles[y][x] != Tile::Outside {
+                continue;
+            }
+            self.tiles[y][x] = Tile::Floor;
+            for dir in [Dir::Up, Dir::Down, Dir::Left, Dir::Right] {
+                if let Some(next) = self.neighbour((x, y), dir) {
+                    stack.push(next);
+                }
+            }
+        }
+    }
+
+    fn neighbour(&self, (x, y): (usize, usize), dir: Dir) -> Option<(usize, usize)> {
+        let (dx, dy) = dir.delta();
+        let x = x.checked_add_signed(dx).filter(|&x| x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < self.height)?;
+        Some((x, y))
+    }
+
+    fn is_free(&self, (x, y): (usize, usize)) -> bool {
+        self.tiles[y][x] == Tile::Floor && !self.crates[y][x]
+    }
+
+    /// Walk one tile, pushing a crate if there is one, unless blocked.
+    fn step(&mut self, dir: Dir) {
+        let Some(next) = self.neighbour(self.player, dir) else {
+            return;
+        };
+        let pushed = self.crates[next.1][next.0];
+        if pushed {
+            let Some(beyond) = self.neighbour(next, dir).filter(|&b| self.is_free(b)) else {
+                return;
+            };
+            self.crates[next.1][next.0] = false;
+            self.crates[beyond.1][beyond.0] = true;
+            self.pushes += 1;
+        } else if !self.is_free(next) {
+            return;
+        }
+        self.player = next;
+        self.history.push(Step { dir, pushed });
+    }
+
+    /// Take back the last move. Returns `false` if there is none.
+    fn undo(&mut self) -> bool {
+        let Some(Step { dir, pushed }) = self.history.pop() else {
+            return false;
+        };
+        let (dx, dy) = dir.delta();
+        let from = self.player;
+        self.player = (
+            from.0.wrapping_add_signed(-dx),
+            from.1.wrapping_add_signed(-dy),
+        );
+        if pushed {
+            let (cx, cy) = (
+                from.0.wrapping_add_signed(dx),
+                from.1.wrapping_add_signed(dy),
+            );
+            self.crates[cy][cx] = false;
+            self.crates[from.1][from.0] = true;
+            self.pushes -= 1;
+        }
+        true
+    }
+
+    fn moves(&self) -> usize {
+        self.history.len()
+    }
+
+    fn solved(&self) -> bool {
+        (0..self.height).all(|y| (0..self.width).all(|x| self.crates[y][x] == self.goals[y][x]))
+    }
+}
+
+// ── Drawing ─────────────────────────────────────────────────────────────────
+
+fn draw(fb: &mut Framebuffer, puzzle: &Puzzle, number: usize, name: &str) {
+    fb.clear(Rgb565::BLACK).unwrap();
+
+    // HUD: level on the left, counters on the right
+    let hud = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+    let title = alloc::format!("{number}/{} {name}", PACK.len());
+    Text::with_baseline(&title, Point::new(4, 2), hud, Baseline::Top)
+        .draw(fb)
+        .unwrap();
+    let counters = alloc::format!("moves {}  pushes {}", puzzle.moves(), puzzle.pushes);
+    Text::with_alignment(&counters, Point::new(W - 4, 10), hud, Alignment::Right)
+        .draw(fb)
+        .unwrap();
+
+    // Largest tile that fits, board centered below the HUD
+    let tile = (W / puzzle.width as i32)
+        .min((H - HUD_H) / puzzle.height as i32)
+        .min(MAX_TILE);
+    let origin = Point::new(
+        (W - tile * puzzle.width as i32) / 2,
+        HUD_H + (H - HUD_H - tile * puzzle.height as i32) / 2,
+    );
+    let size = Size::new(tile as u32, tile as u32);
+    let inset = (tile / 5).max(1);
+
+    for y in 0..puzzle.height {
+        for x in 0..puzzle.width {
+            let top_left = origin + Point::new(x as i32 * tile, y as i32 * tile);
+            let cell = Rectangle::new(top_left, size);
+            match puzzle.tiles[y][x] {
+                Tile::Outside => continue,
+                Tile::Wall => {
+                    cell.into_styled(PrimitiveStyle::with_fill(WALL))
+                        .draw(fb)
+                        .unwrap();
+                    continue;
+                }
+                Tile::Floor => cell
+                    .into_styled(PrimitiveStyle::with_fill(FLOOR))
+                    .draw(fb)
+                    .unwrap(),
+            }
+            if puzzle.goals[y][x] {
+                cell.offset(-inset * 2 + 1)
+                    .into_styled(PrimitiveStyle::with_fill(GOAL))
+                    .draw(fb)
+                    .unwrap();
+            }
+            if puzzle.crates[y][x] {
+                let color = if puzzle.goals[y][x] {
+                    CRATE_DONE
+                } else {
+                    CRATE
+                };
+                let style = PrimitiveStyleBuilder::new()
+                    .fill_color(color)
+                    .stroke_color(Rgb565::BLACK)
+                    .stroke_width(1)
+                    .build();
+                cell.offset(-1).into_styled(style).draw(fb).unwrap();
+            }
+            if puzzle.player == (x, y) {
+                Circle::new(
+                    top_left + Point::new(inset, inset),
+                    (tile - inset * 2) as u32,
+                )
+                .into_styled(PrimitiveStyle::with_fill(PLAYER))
+                .draw(fb)
+                .unwrap();
+            }
+        }
+    }
+
+    if puzzle.solved() {
+        Rectangle::new(Point::new(0, H / 2 - 12), Size::new(W as u32, 24))
+            .into_styled(PrimitiveStyle::with_fill(Rgb565::BLACK))
+            .draw(fb)
+            .unwrap();
+        TextBox::new(
+            "Solved! Press A",
+            Rectangle::new(Point::new(0, H / 2 - 5), Size::new(W as u32, 10)),
+            MonoTextStyle::new(&FONT_6X10, Rgb565::GREEN),
+        )
+        .with_alignment(Alignment::Center)
+        .draw(fb)
+        .unwrap();
+    }
+}
+
+fn draw_message(fb: &mut Framebuffer, message: &str) {
+    fb.clear(Rgb565::BLACK).unwrap();
+    let area = Rectangle::new(Point::new(20, H / 2 - 10), Size::new(W as u32 - 40, 40));
+    TextBox::new(message, area, MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE))
+        .with_alignment(Alignment::Center)
+        .draw(fb)
+        .unwrap();
+}
+
+// ── Tasks ───────────────────────────────────────────────────────────────────
+
+#[embassy_executor::task]
+async fn sokoban_task(
+    display: &'static mut Display<'static>,
+    backlight: &'static mut Backlight,
+    buttons: &'static mut Buttons,
+) {
+    info!("Sokoban started with {} levels", PACK.len());
+    backlight.on();
+
+    let mut auto_dim = AutoDim::new();
+    let mut index = 0;
+    loop {
+        let number = index + 1;
+        let level = match PACK.get(index) {
+            Some(Ok(level)) => level,
+            Some(Err(err)) => {
+                warn!("Skipping level {}: {}", number, err);
+                index += 1;
+                continue;
+            }
+            None => {
+                display
+                    .frame(|fb| draw_message(fb, "All levels solved!\nPress A to play again"))
+                    .unwrap();
+                while auto_dim.wait_for_press(buttons, backlight).await != Button::A {}
+                index = 0;
+                continue;
+            }
+        };
+        let mut puzzle = match Puzzle::load(&level) {
+            Ok(puzzle) => puzzle,
+            Err(err) => {
+                warn!("Skipping level {}: {=str}", number, err);
+                index += 1;
+                continue;
+            }
+        };
+        let name = level.get("name").unwrap_or("");
+        info!("Level {}: {=str}", number, name);
+
+        loop {
+            display.frame(|fb| draw(fb, &puzzle, number, name)).unwrap();
+            let solved = puzzle.solved();
+            match auto_dim.wait_for_press(buttons, backlight).await {
+                Button::Up if !solved => puzzle.step(Dir::Up),
+                Button::Down if !solved => puzzle.step(Dir::Down),
+                Button::Left if !solved => puzzle.step(Dir::Left),
+                Button::Right if !solved => puzzle.step(Dir::Right),
+                Button::B => {
+                    puzzle.undo();
+                }
+                Button::Start => while puzzle.undo() {},
+                Button::A if solved => {
+                    info!("Level {} solved in {} moves", number, puzzle.moves());
+                    break;
+                }
+                Button::Select => break,
+                _ => {}
+            }
+        }
+        index += 1;
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    // Small heap: the display framebuffer needs most of the internal RAM.
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let display = mk_static!(Display<'static>, resources.display.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+
+    spawner.must_spawn(sokoban_task(display, backlight, buttons));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
@@ -11,6 +11,10 @@
 //! - D-pad Up/Down: move ship
 //! - A: fire
 //! - Start: restart after game over
+//!
+//! Enemies spawn at times and places worked out from `SEED` (a number, set
+//! at build time), so every game on every badge built with the same seed
+//! plays out the same way for the same inputs.
 
 #![no_std]
 #![no_main]
@@ -34,6 +38,7 @@ use esp_backtrace as _;
 use esp_hal::timer::timg::TimerGroup;
 use esp_println as _;
 use palette::Srgb;
+use schedule::{Rule, Schedule};
 
 extern crate alloc;
 
@@ -63,25 +68,18 @@ const MAX_ENEMIES: usize = 8;
 const ENEMY_HP: u8 = 3;
 const FIRE_COOLDOWN: u8 = 12;
 
+// Seed for the enemy spawns; the same seed gives the same game.
+const SEED: Option<&str> = option_env!("SEED");
+const DEFAULT_SEED: u32 = 0xDEAD_BEEF;
+// Schedule rule that spawns enemies.
+const SPAWN: usize = 0;
+
 // ── Input atomics ───────────────────────────────────────────────────────────
 static INPUT_UP: AtomicBool = AtomicBool::new(false);
 static INPUT_DOWN: AtomicBool = AtomicBool::new(false);
 static INPUT_FIRE: AtomicBool = AtomicBool::new(false);
 static INPUT_START: AtomicBool = AtomicBool::new(false);
 
-// ── Simple RNG ──────────────────────────────────────────────────────────────
-struct Rng(u32);
-impl Rng {
-    const fn new(seed: u32) -> Self { Self(seed) }
-    fn next(&mut self) -> u32 {
-        self.0 ^= self.0 << 13;
-        self.0 ^= self.0 >> 17;
-        self.0 ^= self.0 << 5;
-        self.0
-    }
-    fn range(&mut self, max: u32) -> u32 { self.next() % max }
-}
-
 // ── Sine table for fire shader (fixed-point, 0..1023 → -120..120) ──────────
 const SIN_Q: [i16; 65] = [
     0, 3, 6, 9, 12, 16, 19, 22, 25, 28, 31, 34, 37, 40, 43, 46, 49, 51, 54, 57, 60, 62, 65, 67,
@@ -187,18 +185,18 @@ struct Game {
     tick: u32,
     scroll_offset: u16,
     alive: bool,
-    rng: Rng,
-    enemy_spawn_timer: u8,
+    schedule: Schedule<1>,
 }
 
 impl Game {
-    fn new() -> Self {
+    fn new(seed: u32) -> Self {
         Self {
             player: Player::new(),
             bullets: [Bullet::DEAD; MAX_BULLETS],
             enemies: [Enemy::DEAD; MAX_ENEMIES],
             score: 0, tick: 0, scroll_offset: 0,
-            alive: true, rng: Rng::new(0xDEAD_BEEF), enemy_spawn_timer: 0,
+            alive: true,
+            schedule: Schedule::new(seed, [Rule::new(60, 60).starting_at(0)]),
         }
     }
 
@@ -233,15 +231,13 @@ impl Game {
             }
         }
 
-        if self.enemy_spawn_timer == 0 {
-            let interval = 60u8.saturating_sub((self.score / 5) as u8).max(20);
-            self.enemy_spawn_timer = interval;
+        let interval = 60u32.saturating_sub(self.score / 5).max(20);
+        self.schedule.set_gaps(SPAWN, interval * 3 / 4, interval * 5 / 4);
+        for event in self.schedule.tick() {
             if let Some(slot) = self.enemies.iter_mut().find(|e| !e.alive) {
-                let y = (self.rng.range((GAME_H - Enemy::H) as u32) as i32).max(0);
+                let y = (event.roll % (GAME_H - Enemy::H) as u32) as i32;
                 *slot = Enemy { x: GAME_X + GAME_W, y, hp: ENEMY_HP, alive: true };
             }
-        } else {
-            self.enemy_spawn_timer -= 1;
         }
 
         for e in &mut self.enemies {
@@ -591,7 +587,7 @@ async fn led_task(leds: &'static mut Leds<'static>) {
             LedEvent::EnemyKill => {
                 // Bright flash fading to black over ~160ms
                 for i in (0..=8).rev() {
-                    let brightness = i * 5; // MAX flashbang: * 30
+                    let brightness = i * 14; // MAX flashbang: * 31
                     leds.fill(Srgb::new(brightness, brightness, brightness));
                     leds.update().await;
                     Timer::after(Duration::from_millis(20)).await;
@@ -601,14 +597,14 @@ async fn led_task(leds: &'static mut Leds<'static>) {
                 let lit = ((score as usize).min(BAR_COUNT * 5)) / 5;
                 let mut bar = [Srgb::new(0u8, 0, 0); BAR_COUNT];
                 for i in 0..lit.min(BAR_COUNT) {
-                    bar[i] = Srgb::new(0, 4, 2);
+                    bar[i] = Srgb::new(0, 37, 25);
                 }
                 leds.set_both_bars(&bar);
                 leds.update().await;
             }
             LedEvent::GameOver => {
                 for _ in 0..3 {
-                    leds.fill(Srgb::new(20, 0, 0));
+                    leds.fill(Srgb::new(80, 0, 0));
                     leds.update().await;
                     Timer::after(Duration::from_millis(300)).await;
                     leds.clear();
@@ -638,12 +634,13 @@ async fn game_task(
     backlight: &'static mut Backlight,
 ) {
     backlight.on();
-    info!("Space shooter started");
+    let seed = SEED.and_then(|s| s.parse().ok()).unwrap_or(DEFAULT_SEED);
+    info!("Space shooter started, seed {=u32}", seed);
 
     loop {
         display.set_vertical_scroll_region(HUD_RIGHT, HUD_LEFT).unwrap();
 
-        let mut game = Game::new();
+        let mut game = Game::new(seed);
         let mut bg_frame: i32 = 0;
         let mut bg = BgMap::new();
         let mut world_x: i32 = GAME_W as i32;
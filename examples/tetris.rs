@@ -36,7 +36,7 @@ use embedded_graphics::{
     pixelcolor::Rgb565,
     prelude::*,
     primitives::{PrimitiveStyle, Rectangle},
-    text::Text,
+    text::{Alignment, Text},
 };
 use esp_backtrace as _;
 use esp_hal::timer::timg::TimerGroup;
@@ -69,7 +69,7 @@ const SCORE_Y: i32 = BOARD_Y + 80;
 const LEVEL_X: i32 = BOARD_X + BOARD_PX_W + 10;
 const LEVEL_Y: i32 = BOARD_Y + 110;
 
-const TICK_MS: u64 = 16; // ~60fps frame tick
+const FPS: u32 = 60;
 const DAS_DELAY: u8 = 10; // frames before auto-repeat starts
 const ARR_RATE: u8 = 2; // frames between auto-repeat moves
 const LOCK_DELAY_FRAMES: u8 = 30; // 0.5s at 60fps
@@ -1084,32 +1084,32 @@ fn draw_frame(
     }
 }
 
+/// A full-width area from `y` to the bottom of the screen.
+fn text_row(y: i32) -> Rectangle {
+    Rectangle::new(Point::new(0, y), Size::new(SCREEN_W as u32, (SCREEN_H - y) as u32))
+}
+
 fn draw_title(display: &mut Display) {
     Rectangle::new(Point::zero(), Size::new(SCREEN_W as u32, SCREEN_H as u32))
         .into_styled(PrimitiveStyle::with_fill(BLACK))
         .draw(display)
         .unwrap();
 
-    let big = MonoTextStyle::new(&FONT_6X10, Rgb565::CYAN);
+    let big = MonoTextStyle::new(&font::FONT_20X40, Rgb565::CYAN);
     let small = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
 
-    Text::new("TETRIS", Point::new(SCREEN_W / 2 - 18, SCREEN_H / 2 - 20), big)
+    TextBox::new("TETRIS", text_row(SCREEN_H / 2 - 55), big)
+        .with_alignment(Alignment::Center)
+        .draw(display)
+        .unwrap();
+    TextBox::new("Championship Edition", text_row(SCREEN_H / 2 - 7), small)
+        .with_alignment(Alignment::Center)
+        .draw(display)
+        .unwrap();
+    TextBox::new("Press START", text_row(SCREEN_H / 2 + 13), small)
+        .with_alignment(Alignment::Center)
         .draw(display)
         .unwrap();
-    Text::new(
-        "Championship Edition",
-        Point::new(SCREEN_W / 2 - 60, SCREEN_H / 2),
-        small,
-    )
-    .draw(display)
-    .unwrap();
-    Text::new(
-        "Press START",
-        Point::new(SCREEN_W / 2 - 33, SCREEN_H / 2 + 20),
-        small,
-    )
-    .draw(display)
-    .unwrap();
 }
 
 fn draw_game_over(display: &mut Display, score: u32, level: u8) {
@@ -1125,10 +1125,10 @@ fn draw_game_over(display: &mut Display, score: u32, level: u8) {
     let style = MonoTextStyle::new(&FONT_6X10, Rgb565::RED);
     let white = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
 
-    Text::new("GAME", Point::new(BOARD_X + 20, BOARD_Y + 60), style)
-        .draw(display)
-        .unwrap();
-    Text::new("OVER", Point::new(BOARD_X + 20, BOARD_Y + 75), style)
+    // Narrow enough to wrap onto two lines
+    let banner = Rectangle::new(Point::new(BOARD_X + 16, BOARD_Y + 53), Size::new(48, 20));
+    TextBox::new("GAME OVER", banner, style)
+        .with_alignment(Alignment::Center)
         .draw(display)
         .unwrap();
 
@@ -1228,10 +1228,10 @@ async fn led_task(leds: &'static mut Leds<'static>) {
         match event {
             LedEvent::LineClear(lines) => {
                 let color = match lines {
-                    4 => Srgb::new(40u8, 40, 40), // Tetris = bright white
-                    3 => Srgb::new(0, 30, 30),
-                    2 => Srgb::new(0, 20, 0),
-                    _ => Srgb::new(0, 0, 15),
+                    4 => Srgb::new(110u8, 110, 110), // Tetris = bright white
+                    3 => Srgb::new(0, 96, 96),
+                    2 => Srgb::new(0, 80, 0),
+                    _ => Srgb::new(0, 0, 70),
                 };
                 for i in (0..=5).rev() {
                     let b = i as u8;
@@ -1246,7 +1246,7 @@ async fn led_task(leds: &'static mut Leds<'static>) {
             }
             LedEvent::TSpin => {
                 for _ in 0..3 {
-                    leds.fill(Srgb::new(30, 0, 30));
+                    leds.fill(Srgb::new(96, 0, 96));
                     leds.update().await;
                     Timer::after(Duration::from_millis(80)).await;
                     leds.clear();
@@ -1256,7 +1256,7 @@ async fn led_task(leds: &'static mut Leds<'static>) {
             }
             LedEvent::GameOver => {
                 for _ in 0..4 {
-                    leds.fill(Srgb::new(20, 0, 0));
+                    leds.fill(Srgb::new(80, 0, 0));
                     leds.update().await;
                     Timer::after(Duration::from_millis(250)).await;
                     leds.clear();
@@ -1267,7 +1267,7 @@ async fn led_task(leds: &'static mut Leds<'static>) {
             LedEvent::LevelUp => {
                 for i in 0..BAR_COUNT {
                     let mut bar = [Srgb::new(0u8, 0, 0); BAR_COUNT];
-                    bar[i] = Srgb::new(0, 30, 10);
+                    bar[i] = Srgb::new(0, 96, 58);
                     leds.set_both_bars(&bar);
                     leds.update().await;
                     Timer::after(Duration::from_millis(40)).await;
@@ -1338,7 +1338,7 @@ async fn game_task(
         let mut prev_hold = game.hold;
         let mut prev_next = game.bag.peek();
 
-        let tick = Duration::from_millis(TICK_MS);
+        let mut ticker = FrameTicker::new(FPS);
 
         // Game loop
         loop {
@@ -1356,7 +1356,7 @@ async fn game_task(
             }
 
             if game.paused {
-                Timer::after(tick).await;
+                ticker.next().await;
                 continue;
             }
 
@@ -1398,7 +1398,7 @@ async fn game_task(
                 break;
             }
 
-            Timer::after(tick).await;
+            ticker.next().await;
         }
     }
 }
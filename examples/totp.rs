@@ -0,0 +1,336 @@
+//! Authenticator — two-factor login codes (TOTP) for several accounts, with
+//! a ring counting down to the next code.
+//!
+//! The secrets are sealed with a key in the badge's eFuses (see
+//! `disobey2026badge::totp`), so a flash dump doesn't give them away. Burn
+//! the key into `BLOCK_KEY0` first, then:
+//!
+//! 1. Flash the example and type `seal Label:SECRET` into the serial port
+//!    for each account, with the base32 secret the service shows when
+//!    enrolling. The badge logs `SEALED Label:<hex>` in reply.
+//! 2. Flash again with the sealed accounts, comma-separated, in `TOTP`.
+//!
+//! ```sh
+//! TOTP="GitHub:3fa9…,Mail:81c0…" UNIX_TIME=$(date +%s) \
+//!     cargo run --release --example totp
+//! ```
+//!
+//! `UNIX_TIME` is the seconds since 1970 at boot; the codes are only right
+//! if the clock is, so flash with `$(date +%s)` and fine-tune with
+//! Left/Right.
+//!
+//! Up/Down pick the account, Left/Right move the clock by 5 seconds.
+
+#![no_std]
+#![no_main]
+
+use defmt::{
+    info,
+    warn,
+};
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_futures::select::{
+    Either,
+    select,
+};
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        ascii::{
+            FONT_6X10,
+            FONT_10X20,
+        },
+    },
+    pixelcolor::Rgb565,
+    prelude::*,
+    text::{
+        Alignment,
+        Baseline,
+        Text,
+    },
+};
+use esp_backtrace as _;
+use esp_hal::{
+    rng::Rng,
+    timer::timg::TimerGroup,
+    usb_serial_jtag::UsbSerialJtag,
+};
+use esp_println as _;
+use serial::Lines;
+use totp::{
+    KeyId,
+    Totp,
+    Vault,
+};
+
+extern crate alloc;
+
+use alloc::{
+    string::String,
+    vec::Vec,
+};
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+const TOTP: &str = match option_env!("TOTP") {
+    Some(accounts) => accounts,
+    None => "",
+};
+const UNIX_TIME: Option<&str> = option_env!("UNIX_TIME");
+
+const W: i32 = 320;
+const H: i32 = 170;
+const DIM: Rgb565 = Rgb565::new(12, 24, 12);
+
+/// Seconds Left/Right move the clock.
+const NUDGE: i64 = 5;
+
+/// Centre of the countdown ring.
+const RING: Point = Point::new(W - 48, 92);
+const RING_DIAMETER: u32 = 64;
+
+/// Seconds left at which the code turns red, as a hint to wait for the next.
+const HURRY: u64 = 5;
+
+/// The eFuse key block holding the sealing key.
+const KEY: KeyId = KeyId::Key0;
+
+/// How often to redraw and check the buttons.
+const POLL: Duration = Duration::from_millis(50);
+
+/// Open one `Label:<sealed hex>` entry.
+fn open_account(vault: &mut Vault, entry: &'static str) -> Option<(&'static str, Totp)> {
+    let (label, sealed) = entry.split_once(':')?;
+    let sealed = from_hex(sealed.trim())?;
+    match vault.open(&sealed) {
+        Ok(account) => Some((label.trim(), account)),
+        Err(e) => {
+            warn!("Can't open {=str}: {}", label, e);
+            None
+        }
+    }
+}
+
+/// Answer a `seal Label:SECRET` line with the sealed secret to put in
+/// `TOTP`.
+fn seal(vault: &mut Vault, line: &str) {
+    let Some((label, secret)) = line
+        .strip_prefix("seal ")
+        .and_then(|account| account.split_once(':'))
+    else {
+        warn!("Expected seal Label:SECRET");
+        return;
+    };
+    let Some(account) = Totp::from_base32(secret) else {
+        warn!("{=str}: the secret isn't base32", label);
+        return;
+    };
+    let mut nonce = [0; totp::NONCE];
+    Rng::new().read(&mut nonce);
+    let mut buf = [0; totp::MAX_SEALED];
+    match vault.seal(&account, nonce, &mut buf) {
+        Ok(sealed) => info!(
+            "SEALED {=str}:{=str}",
+            label.trim(),
+            to_hex(sealed).as_str()
+        ),
+        Err(e) => warn!("Can't seal {=str}: {}", label, e),
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| alloc::format!("{b:02x}")).collect()
+}
+
+fn from_hex(text: &str) -> Option<Vec<u8>> {
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn draw(fb: &mut Framebuffer, label: &str, account: &Totp, index: usize, count: usize) {
+    let now = clock::unix_time();
+    let remaining = account.remaining(now);
+    let color = if remaining <= HURRY {
+        Rgb565::CSS_TOMATO
+    } else {
+        Rgb565::WHITE
+    };
+
+    fb.clear(Rgb565::BLACK).unwrap();
+
+    let position = alloc::format!("{} / {count}", index + 1);
+    Text::with_baseline(
+        &position,
+        Point::new(8, 8),
+        MonoTextStyle::new(&FONT_6X10, DIM),
+        Baseline::Top,
+    )
+    .draw(fb)
+    .unwrap();
+    Text::with_baseline(
+        label,
+        Point::new(8, 28),
+        MonoTextStyle::new(&FONT_10X20, Rgb565::CSS_ORANGE),
+        Baseline::Top,
+    )
+    .draw(fb)
+    .unwrap();
+
+    let code = alloc::format!(
+        "{:0width$}",
+        account.code(now),
+        width = account.digits() as usize
+    );
+    Text::with_baseline(
+        &code,
+        Point::new(8, 72),
+        MonoTextStyle::new(&font::FONT_20X40, color),
+        Baseline::Top,
+    )
+    .draw(fb)
+    .unwrap();
+
+    // The ring empties clockwise from the top as the code runs out.
+    let seconds = alloc::format!("{remaining}");
+    Gauge::new(
+        RING,
+        RING_DIAMETER,
+        remaining as u32,
+        account.period() as u32,
+    )
+    .with_arc((-90.0).deg(), 360.0.deg())
+    .with_thickness(6)
+    .with_colors(color, DIM)
+    .with_label(&seconds, MonoTextStyle::new(&FONT_10X20, color))
+    .draw(fb)
+    .unwrap();
+
+    Text::with_alignment(
+        "Up/Down account  Left/Right clock -/+5 s",
+        Point::new(W / 2, H - 6),
+        MonoTextStyle::new(&FONT_6X10, DIM),
+        Alignment::Center,
+    )
+    .draw(fb)
+    .unwrap();
+}
+
+/// What to show until there are accounts.
+fn draw_empty(fb: &mut Framebuffer) {
+    fb.clear(Rgb565::BLACK).unwrap();
+    let style = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+    for (i, line) in [
+        "No accounts.",
+        "Type  seal Label:SECRET  into the serial port",
+        "and flash again with the reply in TOTP.",
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        Text::with_alignment(
+            line,
+            Point::new(W / 2, H / 2 - 12 + 14 * i as i32),
+            style,
+            Alignment::Center,
+        )
+        .draw(fb)
+        .unwrap();
+    }
+}
+
+// ── Tasks ───────────────────────────────────────────────────────────────────
+
+#[embassy_executor::task]
+async fn totp_task(
+    display: &'static mut Display<'static>,
+    backlight: &'static mut Backlight,
+    buttons: &'static mut Buttons,
+    vault: &'static mut Vault<'static>,
+    lines: &'static mut Lines,
+) {
+    match UNIX_TIME.map(str::parse) {
+        Some(Ok(seconds)) => clock::set_unix_time(seconds),
+        Some(Err(_)) => warn!("UNIX_TIME must be seconds since 1970"),
+        None => warn!("UNIX_TIME not set, the codes will be wrong"),
+    }
+    // Opened once, so the secrets only ever exist in RAM.
+    let accounts: Vec<_> = TOTP
+        .split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .filter_map(|entry| open_account(vault, entry))
+        .collect();
+    let count = accounts.len();
+    info!("{} accounts, clock at {}", count, clock::unix_time());
+    backlight.on();
+
+    let mut index = 0;
+    let mut shown = None;
+
+    loop {
+        let nudge = if buttons.right.is_low() {
+            NUDGE
+        } else if buttons.left.is_low() {
+            -NUDGE
+        } else {
+            0
+        };
+        if nudge != 0 {
+            clock::set_unix_time(clock::unix_time().saturating_add_signed(nudge));
+        }
+        if count > 0 && buttons.down.is_low() {
+            index = (index + 1) % count;
+        } else if count > 0 && buttons.up.is_low() {
+            index = (index + count - 1) % count;
+        }
+
+        let state = (index, clock::unix_time());
+        if shown != Some(state) {
+            display
+                .frame(|fb| match accounts.get(index) {
+                    Some((label, account)) => draw(fb, label, account, index, count),
+                    None => draw_empty(fb),
+                })
+                .unwrap();
+            shown = Some(state);
+        }
+
+        let moved = nudge != 0 || buttons.up.is_low() || buttons.down.is_low();
+        let wait = if moved { POLL * 5 } else { POLL };
+        if let Either::First(line) = select(lines.next(), Timer::after(wait)).await {
+            seal(vault, line);
+        }
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    // Small heap: the display framebuffer needs most of the internal RAM.
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let display = mk_static!(Display<'static>, resources.display.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+    let vault = mk_static!(Vault<'static>, Vault::new(peripherals.HMAC, KEY));
+    let (rx, _) = UsbSerialJtag::new(peripherals.USB_DEVICE).split();
+    let lines = mk_static!(Lines, Lines::new(rx));
+
+    spawner.must_spawn(totp_task(display, backlight, buttons, vault, lines));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
@@ -1,11 +1,10 @@
-//! Hardware vertical scrolling demo using the ST7789's built-in VSCRDEF/VSCRSADD commands.
+//! Hardware scrolling demo using the ST7789's built-in VSCRDEF/VSCRSADD commands.
 //!
 //! Draws colored stripes across the display, then uses the ST7789's hardware
-//! vertical scroll feature to smoothly scroll them without redrawing.
+//! scroll feature to smoothly scroll them without redrawing.
 //!
-//! Note: ST7789 vertical scrolling operates on the framebuffer's native
-//! vertical axis (320 px). With the badge's Deg90 rotation this appears as
-//! horizontal movement on screen.
+//! Note: ST7789 scrolling operates on the panel's long (320 px) axis, so in
+//! the default landscape orientation it appears as horizontal movement.
 
 #![no_std]
 #![no_main]
@@ -59,15 +58,14 @@ async fn scroll_task(
 
     info!("Stripes drawn, starting hardware scroll");
 
-    // Set up the scroll region: no fixed areas, entire framebuffer scrolls.
-    // The ST7789 framebuffer height (default orientation) is 320.
-    display.set_vertical_scroll_region(0, 0).unwrap();
+    // Set up the scroll area: no fixed areas, the whole 320 px axis scrolls.
+    display.set_scroll_area(0, 0).unwrap();
 
     // Scroll continuously — the offset wraps around at 320.
     let mut offset: u16 = 0;
     loop {
-        display.set_vertical_scroll_offset(offset).unwrap();
-        offset = offset.wrapping_add(1) % 320;
+        display.scroll_to(offset).unwrap();
+        offset = (offset + 1) % 320;
         Timer::after(Duration::from_millis(10)).await; // ~60 fps
     }
 }
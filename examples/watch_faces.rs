@@ -0,0 +1,205 @@
+//! Watch faces — clock faces loaded from plain-text descriptions in
+//! `examples/assets/watch_faces`, switched with Left/Right.
+//!
+//! The clock starts from `TIME` (`HH:MM`, default `12:00`), and `NICK` fills
+//! in the `{nick}` field. Copy a face file and edit it to make a new one.
+//!
+//! ```sh
+//! TIME="09:30" NICK="h4x0r" cargo run --release --example watch_faces
+//! ```
+//!
+//! New faces load without reflashing: paste one into the serial monitor,
+//! followed by a line reading `===`, and it's shown at once. It stays until
+//! the next paste or reset.
+
+#![no_std]
+#![no_main]
+
+use defmt::{
+    info,
+    warn,
+};
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_futures::select::{
+    Either3,
+    select3,
+};
+use embassy_time::{
+    Duration,
+    Instant,
+    Timer,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    Blocking,
+    timer::timg::TimerGroup,
+    usb_serial_jtag::{
+        UsbSerialJtag,
+        UsbSerialJtagRx,
+    },
+};
+use esp_println as _;
+use watch_face::WatchFace;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+const START: clock::Time = match option_env!("TIME") {
+    Some(s) => match clock::Time::parse_hm(s) {
+        Some(t) => t,
+        None => panic!("TIME must be HH:MM"),
+    },
+    None => clock::Time::from_hms(12, 0, 0),
+};
+
+const NICK: &str = match option_env!("NICK") {
+    Some(nick) => nick,
+    None => "Disobey 2026",
+};
+
+const FACES: [&str; 3] = [
+    include_str!("assets/watch_faces/classic.txt"),
+    include_str!("assets/watch_faces/digital.txt"),
+    include_str!("assets/watch_faces/retro.txt"),
+];
+
+/// Longest face that can be pasted, in bytes.
+const PASTE_BYTES: usize = 2048;
+/// How often to check the serial port for pasted text.
+const POLL: Duration = Duration::from_millis(50);
+
+/// Text pasted over the USB serial port.
+struct Paste {
+    rx: UsbSerialJtagRx<'static, Blocking>,
+    buf: [u8; PASTE_BYTES],
+    len: usize,
+    complete: bool,
+}
+
+impl Paste {
+    /// Wait for the text up to a line reading `===`, with `\n` line breaks.
+    async fn receive(&mut self) -> &[u8] {
+        if self.complete {
+            self.len = 0;
+            self.complete = false;
+        }
+        loop {
+            let n = self.rx.drain_rx_fifo(&mut self.buf[self.len..]);
+            // Terminals send Enter as `\r`.
+            for byte in &mut self.buf[self.len..self.len + n] {
+                if *byte == b'\r' {
+                    *byte = b'\n';
+                }
+            }
+            self.len += n;
+            let mut end = None;
+            let mut start = 0;
+            for line in self.buf[..self.len].split_inclusive(|&b| b == b'\n') {
+                if line == b"===\n" {
+                    end = Some(start);
+                    break;
+                }
+                start += line.len();
+            }
+            if let Some(end) = end {
+                self.complete = true;
+                return &self.buf[..end];
+            }
+            if self.len == PASTE_BYTES {
+                warn!("Paste longer than {=usize} bytes, dropped", PASTE_BYTES);
+                self.len = 0;
+            }
+            Timer::after(POLL).await;
+        }
+    }
+}
+
+#[embassy_executor::task]
+async fn watch_task(
+    display: &'static mut Display<'static>,
+    backlight: &'static mut Backlight,
+    buttons: &'static mut Buttons,
+    paste: &'static mut Paste,
+) {
+    clock::set(START);
+    backlight.on();
+
+    let faces = FACES.map(|text| WatchFace::parse(text).unwrap());
+    // The last face pasted, shown after the built-in ones.
+    let mut pasted = [0u8; PASTE_BYTES];
+    let mut pasted_len = None;
+    let mut index = 0;
+    let mut shown = None;
+
+    loop {
+        let face = match pasted_len {
+            Some(len) if index == faces.len() => WatchFace::from_bytes(&pasted[..len]).unwrap(),
+            _ => faces[index],
+        };
+        if shown != Some(index) {
+            info!("Face {=str}", face.name().unwrap_or("?"));
+            shown = Some(index);
+        }
+        let now = clock::now();
+        let mut buf = [0u8; 8];
+        let uptime = clock::format_duration(Instant::MIN.elapsed(), &mut buf);
+        display
+            .frame(|fb| {
+                face.draw(fb, now, &[("nick", NICK), ("uptime", uptime)])
+                    .unwrap();
+            })
+            .unwrap();
+
+        let count = faces.len() + usize::from(pasted_len.is_some());
+        let next_second = Timer::after(Duration::from_millis(
+            1000 - Instant::now().as_millis() % 1000,
+        ));
+        match select3(buttons.wait_for_press(), next_second, paste.receive()).await {
+            Either3::First(Button::Right) => index = (index + 1) % count,
+            Either3::First(Button::Left) => index = (index + count - 1) % count,
+            Either3::Third(text) => match WatchFace::from_bytes(text) {
+                Ok(_) => {
+                    pasted[..text.len()].copy_from_slice(text);
+                    pasted_len = Some(text.len());
+                    index = faces.len();
+                    shown = None;
+                }
+                Err(e) => warn!("Pasted face: {}", e),
+            },
+            _ => {}
+        }
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let display = mk_static!(Display<'static>, resources.display.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+    let (rx, _) = UsbSerialJtag::new(peripherals.USB_DEVICE).split();
+    let paste = mk_static!(
+        Paste,
+        Paste {
+            rx,
+            buf: [0; PASTE_BYTES],
+            len: 0,
+            complete: false,
+        }
+    );
+    spawner.must_spawn(watch_task(display, backlight, buttons, paste));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
@@ -0,0 +1,233 @@
+//! World clock and countdown — local time in several zones plus countdowns
+//! to the next events of the day.
+//!
+//! Configured with compile-time env vars:
+//! - `TIME`: local time at boot, `HH:MM` (default `12:00`)
+//! - `ZONES`: comma-separated `Name+H` / `Name-H` offsets from local time
+//! - `EVENTS`: comma-separated `Name@HH:MM` local times to count down to
+//!
+//! Up/Down adjust the hour, Left/Right the minute.
+//!
+//! ```sh
+//! TIME="10:15" ZONES="London-2,New York-7,Tokyo+7" \
+//!     EVENTS="Keynote@11:00,Badge talk@14:30,Closing@18:00" \
+//!     cargo run --release --example world_clock
+//! ```
+
+#![no_std]
+#![no_main]
+
+use defmt::{
+    info,
+    warn,
+};
+#[allow(clippy::wildcard_imports)]
+use disobey2026badge::*;
+use embassy_executor::Spawner;
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        ascii::{
+            FONT_6X10,
+            FONT_10X20,
+        },
+    },
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::{
+        Line,
+        PrimitiveStyle,
+    },
+    text::{
+        Alignment,
+        Text,
+    },
+};
+use esp_backtrace as _;
+use esp_hal::timer::timg::TimerGroup;
+use esp_println as _;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+const TIME: Option<&str> = option_env!("TIME");
+const ZONES: &str = match option_env!("ZONES") {
+    Some(z) => z,
+    None => "London-2,New York-7,San Francisco-10,Tokyo+7",
+};
+const EVENTS: &str = match option_env!("EVENTS") {
+    Some(e) => e,
+    None => "Lunch@12:00,Sauna@21:00,Closing@18:00",
+};
+
+const START: clock::Time = match TIME {
+    Some(s) => match clock::Time::parse_hm(s) {
+        Some(t) => t,
+        None => panic!("TIME must be HH:MM"),
+    },
+    None => clock::Time::from_hms(12, 0, 0),
+};
+
+const W: i32 = 320;
+const MAX_ROWS: usize = 5;
+const DIM: Rgb565 = Rgb565::new(12, 24, 12);
+
+// ── Configuration parsing ───────────────────────────────────────────────────
+
+/// Parse one `Name+H` / `Name-H` zone entry.
+fn parse_zone(entry: &str) -> Option<(&str, i64)> {
+    let split = entry.rfind(['+', '-'])?;
+    let (name, offset) = entry.split_at(split);
+    Some((name.trim(), offset.parse().ok()?))
+}
+
+/// Parse one `Name@HH:MM` event entry.
+fn parse_event(entry: &str) -> Option<(&str, clock::Time)> {
+    let (name, time) = entry.split_once('@')?;
+    Some((name.trim(), clock::Time::parse_hm(time.trim())?))
+}
+
+/// The well-formed entries of a comma-separated list.
+fn entries<T>(list: &'static str, parse: fn(&'static str) -> Option<T>) -> impl Iterator<Item = T> {
+    list.split(',').filter_map(parse)
+}
+
+/// Log the entries of `list` that `parse` rejects.
+fn check_entries<T>(list: &'static str, parse: fn(&'static str) -> Option<T>) {
+    for entry in list.split(',').filter(|e| parse(e).is_none()) {
+        warn!("Ignoring malformed entry {=str}", entry);
+    }
+}
+
+// ── Drawing ─────────────────────────────────────────────────────────────────
+
+fn draw(fb: &mut Framebuffer, now: clock::Time) {
+    let big = MonoTextStyle::new(&FONT_10X20, Rgb565::WHITE);
+    let label = MonoTextStyle::new(&FONT_6X10, DIM);
+    let text = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+    let accent = MonoTextStyle::new(&FONT_6X10, Rgb565::CSS_ORANGE);
+    let mut buf = [0u8; 8];
+
+    fb.clear(Rgb565::BLACK).unwrap();
+
+    Text::with_alignment("LOCAL", Point::new(W / 2, 12), label, Alignment::Center)
+        .draw(fb)
+        .unwrap();
+    Text::with_alignment(now.format_hms(&mut buf), Point::new(W / 2, 32), big, Alignment::Center)
+        .draw(fb)
+        .unwrap();
+    Line::new(Point::new(W / 2, 44), Point::new(W / 2, 166))
+        .into_styled(PrimitiveStyle::with_stroke(DIM, 1))
+        .draw(fb)
+        .unwrap();
+
+    // Time zones on the left
+    Text::new("ZONES", Point::new(8, 56), label).draw(fb).unwrap();
+    for (i, (name, offset)) in entries(ZONES, parse_zone).take(MAX_ROWS).enumerate() {
+        let y = 76 + i as i32 * 20;
+        Text::new(name, Point::new(8, y), text).draw(fb).unwrap();
+        let zone_time = now.with_zone_offset(offset);
+        Text::with_alignment(
+            &zone_time.format_hms(&mut buf)[..5],
+            Point::new(W / 2 - 8, y),
+            accent,
+            Alignment::Right,
+        )
+        .draw(fb)
+        .unwrap();
+    }
+
+    // Countdowns on the right, soonest first
+    Text::new("COMING UP", Point::new(W / 2 + 8, 56), label)
+        .draw(fb)
+        .unwrap();
+    let mut events = [("", Duration::MAX); MAX_ROWS];
+    for (name, at) in entries(EVENTS, parse_event) {
+        let left = now.until(at);
+        if let Some(slot) = events.iter().position(|&(_, d)| left < d) {
+            events.copy_within(slot..MAX_ROWS - 1, slot + 1);
+            events[slot] = (name, left);
+        }
+    }
+    for (i, &(name, left)) in events.iter().filter(|(name, _)| !name.is_empty()).enumerate() {
+        let y = 76 + i as i32 * 20;
+        Text::new(name, Point::new(W / 2 + 8, y), text).draw(fb).unwrap();
+        Text::with_alignment(
+            clock::format_duration(left, &mut buf),
+            Point::new(W - 8, y),
+            accent,
+            Alignment::Right,
+        )
+        .draw(fb)
+        .unwrap();
+    }
+}
+
+// ── Tasks ───────────────────────────────────────────────────────────────────
+
+#[embassy_executor::task]
+async fn world_clock_task(
+    display: &'static mut Display<'static>,
+    backlight: &'static mut Backlight,
+    buttons: &'static mut Buttons,
+) {
+    clock::set(START);
+    info!("World clock started at {}", START);
+    check_entries(ZONES, parse_zone);
+    check_entries(EVENTS, parse_event);
+    backlight.on();
+
+    let mut shown = None;
+
+    loop {
+        let step = if buttons.up.is_low() {
+            3600
+        } else if buttons.down.is_low() {
+            -3600
+        } else if buttons.right.is_low() {
+            60
+        } else if buttons.left.is_low() {
+            -60
+        } else {
+            0
+        };
+        if step != 0 {
+            clock::adjust(step);
+        }
+
+        let now = clock::now();
+        if shown != Some(now) {
+            display.frame(|fb| draw(fb, now)).unwrap();
+            shown = Some(now);
+        }
+
+        Timer::after(Duration::from_millis(if step == 0 { 50 } else { 200 })).await;
+    }
+}
+
+#[esp_rtos::main]
+async fn main(spawner: Spawner) -> ! {
+    let peripherals = disobey2026badge::init();
+    let resources = split_resources!(peripherals);
+
+    // Small heap: the display framebuffer needs most of the internal RAM.
+    esp_alloc::heap_allocator!(size: 32 * 1024);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_rtos::start(timg0.timer0);
+
+    let display = mk_static!(Display<'static>, resources.display.into());
+    let backlight = mk_static!(Backlight, resources.backlight.into());
+    let buttons = mk_static!(Buttons, resources.buttons.into());
+
+    spawner.must_spawn(world_clock_task(display, backlight, buttons));
+
+    loop {
+        Timer::after(Duration::from_secs(600)).await;
+    }
+}
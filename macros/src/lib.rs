@@ -0,0 +1,585 @@
+//! Compile-time asset macros for the `disobey2026badge` crate.
+//!
+//! Use these through the re-exports in `disobey2026badge` rather than
+//! depending on this crate directly.
+
+use std::path::PathBuf;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    Ident,
+    LitInt,
+    LitStr,
+    Token,
+    parse::{
+        Parse,
+        ParseStream,
+    },
+    parse_macro_input,
+};
+
+/// A decoded image: row-major RGBA8 pixels.
+struct Rgba {
+    width: u32,
+    height: u32,
+    pixels: Vec<[u8; 4]>,
+}
+
+impl Rgba {
+    /// Read and decode a PNG file into RGBA8, whatever its colour type.
+    fn load(path: &std::path::Path) -> Result<Self, String> {
+        let bytes =
+            std::fs::read(path).map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        let mut decoder = png::Decoder::new(std::io::Cursor::new(bytes));
+        decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+        let mut reader = decoder
+            .read_info()
+            .map_err(|e| format!("invalid PNG {}: {e}", path.display()))?;
+        let size = reader
+            .output_buffer_size()
+            .ok_or_else(|| format!("PNG {} is too large", path.display()))?;
+        let mut buf = vec![0; size];
+        let info = reader
+            .next_frame(&mut buf)
+            .map_err(|e| format!("invalid PNG {}: {e}", path.display()))?;
+
+        let pixels = buf[..info.buffer_size()]
+            .chunks_exact(info.line_size)
+            .flat_map(|line| {
+                let channels = info.color_type.samples();
+                line[..info.width as usize * channels]
+                    .chunks_exact(channels)
+                    .map(|px| match *px {
+                        [l] => [l, l, l, 0xff],
+                        [l, a] => [l, l, l, a],
+                        [r, g, b] => [r, g, b, 0xff],
+                        [r, g, b, a] => [r, g, b, a],
+                        _ => unreachable!("PNG pixels have 1-4 channels"),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        Ok(Self {
+            width: info.width,
+            height: info.height,
+            pixels,
+        })
+    }
+
+    /// `true` if any pixel is less than half opaque.
+    fn has_transparency(&self) -> bool {
+        self.pixels.iter().any(|px| px[3] < 0x80)
+    }
+
+    /// 1-bit opacity mask, MSB first, each row padded to a whole byte.
+    fn mask(&self) -> Vec<u8> {
+        let row_bytes = (self.width as usize).div_ceil(8);
+        let mut mask = vec![0u8; row_bytes * self.height as usize];
+        for (i, px) in self.pixels.iter().enumerate() {
+            if px[3] >= 0x80 {
+                let (x, y) = (i % self.width as usize, i / self.width as usize);
+                mask[y * row_bytes + x / 8] |= 0x80 >> (x % 8);
+            }
+        }
+        mask
+    }
+}
+
+/// Pack an RGB888 colour into a raw RGB565 value.
+fn rgb565([r, g, b, _]: [u8; 4]) -> u16 {
+    (u16::from(r >> 3) << 11) | (u16::from(g >> 2) << 5) | u16::from(b >> 3)
+}
+
+/// An image quantized to a palette of distinct RGB565 colours.
+struct Palettized {
+    palette: Vec<u16>,
+    /// One palette index per pixel.
+    indices: Vec<u8>,
+    /// Index reserved for transparent pixels, if any.
+    transparent: Option<u8>,
+}
+
+impl Palettized {
+    /// Fails if the image needs more than 256 entries; reduce the colours in
+    /// an image editor first.
+    fn new(image: &Rgba) -> Result<Self, String> {
+        let transparent = image.has_transparency().then_some(0);
+        // Opaque colours never share the transparent slot, even if black.
+        let skip = usize::from(transparent.is_some());
+        let mut palette = vec![0; skip];
+        let mut indices = Vec::with_capacity(image.pixels.len());
+        for &px in &image.pixels {
+            let index = if transparent.is_some() && px[3] < 0x80 {
+                0
+            } else {
+                let color = rgb565(px);
+                if let Some(i) = palette[skip..].iter().position(|&c| c == color) {
+                    i + skip
+                } else {
+                    palette.push(color);
+                    palette.len() - 1
+                }
+            };
+            indices.push(
+                u8::try_from(index)
+                    .map_err(|_| "image has more than 256 colours (after RGB565 conversion)")?,
+            );
+        }
+        Ok(Self {
+            palette,
+            indices,
+            transparent,
+        })
+    }
+}
+
+/// Pack indices two per byte, high nibble first, each row padded to a byte.
+fn pack_4bit(indices: &[u8], width: usize) -> Vec<u8> {
+    indices
+        .chunks(width)
+        .flat_map(|row| {
+            row.chunks(2)
+                .map(|pair| pair[0] << 4 | pair.get(1).unwrap_or(&0))
+        })
+        .collect()
+}
+
+/// Run-length encode pixels as `(count, colour)` pairs.
+fn rle(pixels: &[u16]) -> Vec<u16> {
+    let mut out = Vec::new();
+    for &px in pixels {
+        match out.as_mut_slice() {
+            [.., count, color] if *color == px && *count < u16::MAX => *count += 1,
+            _ => out.extend([1, px]),
+        }
+    }
+    out
+}
+
+/// Resolve `path` relative to the invoking crate's manifest directory.
+fn asset_path(path: &LitStr) -> syn::Result<PathBuf> {
+    let root = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| syn::Error::new(path.span(), "CARGO_MANIFEST_DIR is not set"))?;
+    Ok(PathBuf::from(root).join(path.value()))
+}
+
+/// Tokens that make rustc rebuild the invoking crate when `path` changes.
+fn track_file(path: &std::path::Path) -> proc_macro2::TokenStream {
+    let path = path.to_string_lossy();
+    quote! { const _: &[u8] = include_bytes!(#path); }
+}
+
+struct ImageArgs {
+    path: LitStr,
+    compress: bool,
+}
+
+impl Parse for ImageArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path: LitStr = input.parse()?;
+        let mut compress = false;
+        while input.parse::<Option<Token![,]>>()?.is_some() {
+            if input.is_empty() {
+                break;
+            }
+            let flag: Ident = input.parse()?;
+            match flag.to_string().as_str() {
+                "compress" => compress = true,
+                _ => return Err(syn::Error::new(flag.span(), "expected `compress`")),
+            }
+        }
+        Ok(Self { path, compress })
+    }
+}
+
+/// Convert a PNG into a `disobey2026badge::image::Rgb565Image` at compile time.
+///
+/// The path is relative to the invoking crate's `Cargo.toml`. Pixels that are
+/// less than half opaque become transparent; images without such pixels get
+/// no mask at all. Add `compress` to store the pixels run-length encoded.
+///
+/// ```rust,ignore
+/// const LOGO: Rgb565Image = badge_image!("examples/assets/skrolli.png");
+/// const BIG: Rgb565Image = badge_image!("assets/splash.png", compress);
+/// ```
+#[proc_macro]
+pub fn badge_image(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ImageArgs);
+    match expand_badge_image(&args) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_badge_image(args: &ImageArgs) -> syn::Result<proc_macro2::TokenStream> {
+    let path = asset_path(&args.path)?;
+    let image = Rgba::load(&path).map_err(|msg| syn::Error::new(args.path.span(), msg))?;
+
+    let (width, height) = (image.width, image.height);
+    let raw: Vec<u16> = image.pixels.iter().copied().map(rgb565).collect();
+    let data = if args.compress {
+        // Hidden pixels take the previous colour so they don't split runs.
+        let mut prev = 0;
+        let hidden_merged: Vec<u16> = raw
+            .iter()
+            .zip(&image.pixels)
+            .map(|(&px, rgba)| {
+                if rgba[3] >= 0x80 {
+                    prev = px;
+                }
+                prev
+            })
+            .collect();
+        let runs = rle(&hidden_merged);
+        quote! { ::disobey2026badge::image::PixelData::Rle(&[#(#runs),*]) }
+    } else {
+        quote! { ::disobey2026badge::image::PixelData::Raw(&[#(#raw),*]) }
+    };
+    let mask = if image.has_transparency() {
+        let mask = image.mask();
+        quote! { Some(&[#(#mask),*]) }
+    } else {
+        quote! { None }
+    };
+    let track = track_file(&path);
+
+    Ok(quote! {
+        {
+            #track
+            ::disobey2026badge::image::Rgb565Image::new(#width, #height, #data, #mask)
+        }
+    })
+}
+
+/// Convert a PNG into raw big-endian RGB565 at compile time, as a
+/// `disobey2026badge::image::RawRgb565Image` (an `embedded-graphics`
+/// `ImageRawBE<Rgb565>`).
+///
+/// The path is relative to the invoking crate's `Cargo.toml`. Alpha is
+/// ignored; use `badge_image!` for images with transparent pixels.
+///
+/// ```rust,ignore
+/// const SPLASH: RawRgb565Image = include_rgb565!("assets/splash.png");
+/// ```
+#[proc_macro]
+pub fn include_rgb565(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    match expand_include_rgb565(&path) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_include_rgb565(lit: &LitStr) -> syn::Result<proc_macro2::TokenStream> {
+    let path = asset_path(lit)?;
+    let image = Rgba::load(&path).map_err(|msg| syn::Error::new(lit.span(), msg))?;
+
+    let width = image.width;
+    let bytes = image.pixels.iter().flat_map(|&px| rgb565(px).to_be_bytes());
+    let track = track_file(&path);
+
+    Ok(quote! {
+        {
+            #track
+            ::disobey2026badge::image::RawRgb565Image::new(&[#(#bytes),*], #width)
+        }
+    })
+}
+
+/// Convert a PNG into a `disobey2026badge::image::PalettedImage` at compile
+/// time.
+///
+/// The path is relative to the invoking crate's `Cargo.toml`. The image may use
+/// at most 256 distinct colours after RGB565 conversion (one fewer if it has
+/// transparent pixels, which get a palette slot of their own). Images with up
+/// to 16 palette entries are stored at 4 bits per pixel, larger ones at 8.
+///
+/// ```rust,ignore
+/// const TILES: PalettedImage = badge_paletted_image!("assets/tiles.png");
+/// ```
+#[proc_macro]
+pub fn badge_paletted_image(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    match expand_badge_paletted_image(&path) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_badge_paletted_image(lit: &LitStr) -> syn::Result<proc_macro2::TokenStream> {
+    let path = asset_path(lit)?;
+    let image = Rgba::load(&path).map_err(|msg| syn::Error::new(lit.span(), msg))?;
+    let Palettized {
+        palette,
+        indices,
+        transparent,
+    } = Palettized::new(&image).map_err(|msg| syn::Error::new(lit.span(), msg))?;
+
+    let (width, height) = (image.width, image.height);
+    let (depth, indices) = if palette.len() <= 16 {
+        let packed = pack_4bit(&indices, width as usize);
+        (quote! { Bits4 }, packed)
+    } else {
+        (quote! { Bits8 }, indices)
+    };
+    let transparent = if let Some(index) = transparent {
+        quote! { Some(#index) }
+    } else {
+        quote! { None }
+    };
+    let track = track_file(&path);
+
+    Ok(quote! {
+        {
+            #track
+            ::disobey2026badge::image::PalettedImage::new(
+                #width,
+                #height,
+                ::disobey2026badge::image::PaletteDepth::#depth,
+                &[#(#palette),*],
+                &[#(#indices),*],
+                #transparent,
+            )
+        }
+    })
+}
+
+/// A monospaced bitmap font parsed from a BDF file.
+struct Bdf {
+    width: u32,
+    height: u32,
+    /// Rows from the top of the cell down to the baseline row.
+    baseline: u32,
+    /// Each glyph as a `width` × `height` grid of set pixels.
+    glyphs: Vec<(char, Vec<bool>)>,
+}
+
+impl Bdf {
+    /// Parse the Latin-1 glyphs (U+0020 to U+00FF) of a BDF file, placing
+    /// each on a grid of the font bounding box.
+    fn load(path: &std::path::Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| format!("can't read {}: {err}", path.display()))?;
+        let mut bounds = None;
+        let mut glyphs = Vec::new();
+        let mut lines = text.lines();
+        while let Some(line) = lines.next() {
+            match line.split_once(' ').unwrap_or((line, "")) {
+                ("FONTBOUNDINGBOX", rest) => bounds = Some(numbers(rest)?),
+                ("STARTCHAR", _) => {
+                    let bounds = bounds.ok_or("BDF glyph before FONTBOUNDINGBOX")?;
+                    glyphs.extend(parse_glyph(&mut lines, bounds)?);
+                }
+                _ => {}
+            }
+        }
+
+        let [width, height, _, y] = bounds.ok_or("BDF has no FONTBOUNDINGBOX")?;
+        if glyphs.is_empty() {
+            return Err("BDF has no glyphs between U+0020 and U+00FF".into());
+        }
+        glyphs.sort_by_key(|&(c, _)| c);
+        let size = |n: i32| u32::try_from(n).map_err(|_| "bad BDF bounding box".to_string());
+        Ok(Self {
+            width: size(width)?,
+            height: size(height)?,
+            baseline: size(height + y - 1)?,
+            glyphs,
+        })
+    }
+
+    /// The glyphs as an `ImageRaw<BinaryColor>` atlas, 16 glyphs per row,
+    /// each pixel blown up to `scale` × `scale`.
+    fn atlas(&self, scale: u32) -> Vec<u8> {
+        let (width, scale) = (self.width as usize, scale as usize);
+        let (cw, ch) = (width * scale, self.height as usize * scale);
+        let stride = (16 * cw).div_ceil(8);
+        let rows = self.glyphs.len().div_ceil(16);
+        let mut bits = vec![0u8; stride * rows * ch];
+        for (i, (_, pixels)) in self.glyphs.iter().enumerate() {
+            let (gx, gy) = (i % 16 * cw, i / 16 * ch);
+            for y in 0..ch {
+                for x in 0..cw {
+                    if pixels[y / scale * width + x / scale] {
+                        let (ax, ay) = (gx + x, gy + y);
+                        bits[ay * stride + ax / 8] |= 0x80 >> (ax % 8);
+                    }
+                }
+            }
+        }
+        bits
+    }
+
+    /// The glyph list in `StrGlyphMapping` syntax, where a run of consecutive
+    /// characters is written as `\0`, its first character and its last.
+    fn mapping(&self) -> String {
+        let chars: Vec<char> = self.glyphs.iter().map(|&(c, _)| c).collect();
+        let mut out = String::new();
+        let mut start = 0;
+        while start < chars.len() {
+            let mut end = start;
+            while end + 1 < chars.len() && chars[end + 1] as u32 == chars[end] as u32 + 1 {
+                end += 1;
+            }
+            if end > start {
+                out.push('\0');
+                out.push(chars[start]);
+                out.push(chars[end]);
+            } else {
+                out.push(chars[start]);
+            }
+            start = end + 1;
+        }
+        out
+    }
+}
+
+/// The first `N` whitespace-separated integers of `text`.
+fn numbers<const N: usize>(text: &str) -> Result<[i32; N], String> {
+    let mut out = [0; N];
+    let mut words = text.split_whitespace();
+    for n in &mut out {
+        let word = words.next().ok_or("missing number in BDF")?;
+        *n = word
+            .parse()
+            .map_err(|_| format!("bad number `{word}` in BDF"))?;
+    }
+    Ok(out)
+}
+
+/// Parse one BDF glyph up to its `ENDCHAR` onto a cell the size of the font
+/// bounding box `[width, height, x, y]`. Glyphs outside Latin-1 give `None`.
+fn parse_glyph<'a>(
+    lines: &mut impl Iterator<Item = &'a str>,
+    [fw, fh, fx, fy]: [i32; 4],
+) -> Result<Option<(char, Vec<bool>)>, String> {
+    let (width, height) = (
+        usize::try_from(fw).unwrap_or(0),
+        usize::try_from(fh).unwrap_or(0),
+    );
+    let index = |x: i32, y: i32| {
+        let (x, y) = (usize::try_from(x).ok()?, usize::try_from(y).ok()?);
+        (x < width && y < height).then_some(y * width + x)
+    };
+    let mut pixels = vec![false; width * height];
+    let (mut encoding, mut bbx) = (-1, [0; 4]);
+    while let Some(line) = lines.next() {
+        match line.split_once(' ').unwrap_or((line, "")) {
+            ("ENCODING", rest) => [encoding] = numbers(rest)?,
+            ("BBX", rest) => bbx = numbers(rest)?,
+            ("BITMAP", _) => {
+                let [w, h, x, y] = bbx;
+                // The glyph box's top row within the cell.
+                let top = fh + fy - y - h;
+                let rows = lines.by_ref().take(usize::try_from(h).unwrap_or(0));
+                for (cy, hex) in (top..).zip(rows) {
+                    let hex = hex.trim();
+                    let bits = u64::from_str_radix(hex, 16)
+                        .map_err(|_| format!("bad bitmap row `{hex}` in BDF"))?;
+                    let len = i32::try_from(hex.len() * 4).unwrap_or(0);
+                    for (cx, col) in (x - fx..).zip(0..w.min(len)) {
+                        if bits >> (len - 1 - col) & 1 != 0
+                            && let Some(i) = index(cx, cy)
+                        {
+                            pixels[i] = true;
+                        }
+                    }
+                }
+            }
+            ("ENDCHAR", _) => break,
+            _ => {}
+        }
+    }
+    let c = u32::try_from(encoding)
+        .ok()
+        .filter(|e| (0x20..=0xff).contains(e))
+        .and_then(char::from_u32);
+    Ok(c.map(|c| (c, pixels)))
+}
+
+struct FontArgs {
+    path: LitStr,
+    scale: u32,
+}
+
+impl Parse for FontArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path: LitStr = input.parse()?;
+        let mut scale = 1;
+        while input.parse::<Option<Token![,]>>()?.is_some() {
+            if input.is_empty() {
+                break;
+            }
+            let key: Ident = input.parse()?;
+            if key != "scale" {
+                return Err(syn::Error::new(key.span(), "expected `scale`"));
+            }
+            input.parse::<Token![=]>()?;
+            let value: LitInt = input.parse()?;
+            scale = value.base10_parse()?;
+            if !(1..=8).contains(&scale) {
+                return Err(syn::Error::new(value.span(), "scale must be 1 to 8"));
+            }
+        }
+        Ok(Self { path, scale })
+    }
+}
+
+/// Convert a BDF bitmap font into an `embedded-graphics` `MonoFont` at compile
+/// time.
+///
+/// The path is relative to the invoking crate's `Cargo.toml`. The Latin-1
+/// glyphs (U+0020 to U+00FF) are kept, each placed on a cell the size of the
+/// font bounding box, so proportional fonts come out monospaced. Characters
+/// the font lacks are drawn as `?`. Add `scale = N` to blow every pixel up to
+/// N × N for big, blocky lettering.
+///
+/// ```rust,ignore
+/// const TERMINUS: MonoFont = badge_font!("assets/ter-u16n.bdf");
+/// const HUGE: MonoFont = badge_font!("assets/ter-u16n.bdf", scale = 3);
+///
+/// Text::new("Hi!", Point::new(10, 40), MonoTextStyle::new(&TERMINUS, Rgb565::WHITE))
+/// ```
+#[proc_macro]
+pub fn badge_font(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as FontArgs);
+    match expand_badge_font(&args) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_badge_font(args: &FontArgs) -> syn::Result<proc_macro2::TokenStream> {
+    let path = asset_path(&args.path)?;
+    let font = Bdf::load(&path).map_err(|msg| syn::Error::new(args.path.span(), msg))?;
+
+    let scale = args.scale;
+    let atlas = font.atlas(scale);
+    let mapping = font.mapping();
+    let replacement = font.glyphs.iter().position(|&(c, _)| c == '?').unwrap_or(0);
+    let (width, height) = (font.width * scale, font.height * scale);
+    let atlas_width = 16 * width;
+    // The last of the `scale` rows the baseline row becomes.
+    let baseline = font.baseline * scale + scale - 1;
+    let (underline, strikethrough) = (baseline + 2 * scale, height / 2);
+    let track = track_file(&path);
+
+    Ok(quote! {
+        {
+            #track
+            use ::disobey2026badge::font::__macro_support as f;
+            const FONT: f::MonoFont<'static> = f::MonoFont {
+                image: f::ImageRaw::new(&[#(#atlas),*], #atlas_width),
+                glyph_mapping: &f::StrGlyphMapping::new(#mapping, #replacement),
+                character_size: f::Size::new(#width, #height),
+                character_spacing: 0,
+                baseline: #baseline,
+                underline: f::DecorationDimensions::new(#underline, #scale),
+                strikethrough: f::DecorationDimensions::new(#strikethrough, #scale),
+            };
+            FONT
+        }
+    })
+}
@@ -0,0 +1,252 @@
+//! Backlight dimming after a period without input.
+
+use embassy_futures::select::{
+    Either,
+    select,
+};
+use embassy_time::{
+    Duration,
+    Instant,
+    Timer,
+};
+use palette::Srgb;
+
+use crate::{
+    BAR_COUNT,
+    Backlight,
+    Button,
+    Buttons,
+    LedHandle,
+    PowerSave,
+    notification::{
+        self,
+        Notification,
+    },
+};
+
+/// How long a tap shows the battery gauge.
+const GAUGE_TIME: Duration = Duration::from_secs(2);
+
+/// How far the backlight has been turned down.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Awake,
+    Dimmed,
+    Off,
+}
+
+/// Dims the backlight, then turns it off, when no button has been pressed
+/// for a while. Any press brings it back to the brightness it had.
+///
+/// Event-driven apps swap [`Buttons::wait_for_press`] for
+/// [`wait_for_press`](Self::wait_for_press), which handles everything:
+///
+/// ```rust,ignore
+/// let mut auto_dim = AutoDim::new().with_off_after(Duration::from_secs(300));
+/// loop {
+///     match auto_dim.wait_for_press(buttons, backlight).await {
+///         Button::A => { /* ... */ }
+///         _ => {}
+///     }
+/// }
+/// ```
+///
+/// Apps that poll the buttons every frame call [`activity`](Self::activity)
+/// when they see input and [`update`](Self::update) once per frame.
+///
+/// With [`with_battery_gauge`](Self::with_battery_gauge), a tap while the
+/// backlight is off shows the battery level on the LED bars instead of
+/// waking it, and a second tap while the gauge shows wakes it.
+///
+/// With [`with_led_power_save`](Self::with_led_power_save), the LEDs go
+/// into [`PowerSave::IDLE`] along with the backlight.
+pub struct AutoDim {
+    dim_after: Duration,
+    off_after: Duration,
+    dim_level: u8,
+    last_activity: Instant,
+    stage: Stage,
+    /// Brightness to restore on wake.
+    saved: u8,
+    battery_level: Option<fn() -> u8>,
+    /// When the battery gauge showing goes out.
+    gauge_until: Option<Instant>,
+    leds: Option<LedHandle>,
+}
+
+impl AutoDim {
+    /// Dim to 32 after 30 seconds and turn off after 2 minutes.
+    pub fn new() -> Self {
+        Self {
+            dim_after: Duration::from_secs(30),
+            off_after: Duration::from_secs(120),
+            dim_level: 32,
+            last_activity: Instant::now(),
+            stage: Stage::Awake,
+            saved: u8::MAX,
+            battery_level: None,
+            gauge_until: None,
+            leds: None,
+        }
+    }
+
+    /// Idle time before dimming. Dimming is skipped if this isn't shorter
+    /// than the time before turning off.
+    #[must_use]
+    pub const fn with_dim_after(mut self, timeout: Duration) -> Self {
+        self.dim_after = timeout;
+        self
+    }
+
+    /// Idle time before turning the backlight off; `Duration::MAX` never does.
+    #[must_use]
+    pub const fn with_off_after(mut self, timeout: Duration) -> Self {
+        self.off_after = timeout;
+        self
+    }
+
+    /// Brightness while dimmed. A backlight already dimmer is left alone.
+    #[must_use]
+    pub const fn with_dim_level(mut self, level: u8) -> Self {
+        self.dim_level = level;
+        self
+    }
+
+    /// While the backlight is off, answer a tap with the battery level from
+    /// `level`, in percent, on the LED bars for two seconds, leaving the
+    /// screen dark.
+    ///
+    /// The gauge is a [`notification`], so it shows only while something
+    /// keeps updating the LEDs, such as a [`LedService`](crate::LedService).
+    #[must_use]
+    pub const fn with_battery_gauge(mut self, level: fn() -> u8) -> Self {
+        self.battery_level = Some(level);
+        self
+    }
+
+    /// Put `leds` into [`PowerSave::IDLE`] while the backlight is dimmed or
+    /// off, and back to full speed on wake. The battery gauge lifts it
+    /// while it shows.
+    #[must_use]
+    pub const fn with_led_power_save(mut self, leds: LedHandle) -> Self {
+        self.leds = Some(leds);
+        self
+    }
+
+    /// `true` while the backlight is dimmed or off.
+    pub fn is_idle(&self) -> bool {
+        self.stage != Stage::Awake
+    }
+
+    /// Record input and restore the backlight. Returns `true` if the
+    /// backlight was off, so the input only woke the badge up, or showed
+    /// the battery gauge, and can be ignored.
+    pub fn activity(&mut self, backlight: &mut Backlight) -> bool {
+        let now = Instant::now();
+        let gauge = self.battery_level.filter(|_| {
+            self.stage == Stage::Off && self.gauge_until.is_none_or(|until| now >= until)
+        });
+        if let Some(level) = gauge {
+            show_gauge(level());
+            self.gauge_until = Some(now + GAUGE_TIME);
+            self.set_led_power_save(None);
+            return true;
+        }
+        self.last_activity = now;
+        let was_off = self.stage == Stage::Off;
+        if self.stage != Stage::Awake {
+            backlight.set_brightness(self.saved);
+            self.stage = Stage::Awake;
+            self.gauge_until = None;
+            self.set_led_power_save(None);
+        }
+        was_off
+    }
+
+    /// Dim or turn off the backlight if the timeouts have passed.
+    pub fn update(&mut self, backlight: &mut Backlight) {
+        let idle = self.last_activity.elapsed();
+        let was_awake = self.stage == Stage::Awake;
+        if was_awake {
+            self.saved = backlight.brightness();
+        }
+        if idle >= self.off_after {
+            if self.stage != Stage::Off {
+                backlight.off();
+                self.stage = Stage::Off;
+            }
+        } else if idle >= self.dim_after && self.stage == Stage::Awake {
+            backlight.set_brightness(self.saved.min(self.dim_level));
+            self.stage = Stage::Dimmed;
+        }
+        let gauge_out = self
+            .gauge_until
+            .is_some_and(|until| Instant::now() >= until);
+        if gauge_out {
+            self.gauge_until = None;
+        }
+        if (was_awake || gauge_out) && self.stage != Stage::Awake {
+            self.set_led_power_save(Some(PowerSave::IDLE));
+        }
+    }
+
+    fn set_led_power_save(&self, power_save: Option<PowerSave>) {
+        if let Some(leds) = self.leds {
+            leds.set_power_save(power_save);
+        }
+    }
+
+    /// Wait for a button press, dimming and turning off the backlight while
+    /// waiting. A press that turns the backlight back on is not reported.
+    pub async fn wait_for_press(
+        &mut self,
+        buttons: &mut Buttons,
+        backlight: &mut Backlight,
+    ) -> Button {
+        loop {
+            let timeout = match self.stage {
+                Stage::Awake if self.dim_after < self.off_after => self.dim_after,
+                Stage::Awake | Stage::Dimmed => self.off_after,
+                Stage::Off => Duration::MAX,
+            };
+            // Also wake when the battery gauge goes out, to idle the LEDs
+            // again.
+            let deadline = self
+                .last_activity
+                .saturating_add(timeout)
+                .min(self.gauge_until.unwrap_or(Instant::MAX));
+            match select(buttons.wait_for_press(), Timer::at(deadline)).await {
+                Either::First(button) => {
+                    if !self.activity(backlight) {
+                        return button;
+                    }
+                }
+                Either::Second(()) => self.update(backlight),
+            }
+        }
+    }
+}
+
+/// Show `level` percent on both LED bars for [`GAUGE_TIME`], green when
+/// more than half full, then yellow, then red under a fifth.
+fn show_gauge(level: u8) {
+    let level = usize::from(level.min(100));
+    let color = match level {
+        51.. => Srgb::new(0, 160, 0),
+        21.. => Srgb::new(160, 140, 0),
+        _ => Srgb::new(180, 0, 0),
+    };
+    // At least one LED, so an empty battery still shows red.
+    let lit = (level * BAR_COUNT).div_ceil(100).max(1);
+    notification::post(
+        Notification::new(color, GAUGE_TIME)
+            .with_leds(notification::bars_from_bottom(lit))
+            .with_priority(notification::Priority::High),
+    );
+}
+
+impl Default for AutoDim {
+    fn default() -> Self {
+        Self::new()
+    }
+}
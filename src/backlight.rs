@@ -1,41 +1,153 @@
 //! Display backlight control.
+//!
+//! The backlight is dimmed with PWM from the LEDC peripheral. Brightness
+//! runs from 0 (off) to 255 (full) and is linear in duty cycle, so the low
+//! end changes faster to the eye than the high end.
 
-use esp_hal::gpio::{
-    Level,
-    Output,
-    OutputConfig,
+use embassy_time::{
+    Duration,
+    Instant,
+    Timer,
+};
+use embedded_hal::pwm::SetDutyCycle;
+use esp_hal::{
+    gpio::DriveMode,
+    ledc::{
+        LSGlobalClkSource,
+        Ledc,
+        LowSpeed,
+        channel::{
+            self,
+            ChannelIFace,
+        },
+        timer::{
+            self,
+            TimerIFace,
+        },
+    },
+    time::Rate,
+};
+
+use crate::{
+    BacklightResources,
+    mk_static,
+    pwm::Pwm,
 };
 
-use crate::BacklightResources;
+/// Above the audible range, so the backlight doesn't whine.
+const PWM_FREQUENCY: Rate = Rate::from_khz(20);
+
+/// Time between brightness steps while fading.
+const FADE_STEP: Duration = Duration::from_millis(10);
 
 /// Controls the display backlight LED.
 pub struct Backlight {
-    pin: Output<'static>,
+    channel: channel::Channel<'static, LowSpeed>,
+    brightness: u8,
+    /// Brightness restored by [`on`](Self::on).
+    restore: u8,
+    /// The rest of the LEDC, until taken.
+    pwm: Option<Pwm>,
 }
 
 impl From<BacklightResources<'static>> for Backlight {
     fn from(res: BacklightResources<'static>) -> Self {
+        let ledc = mk_static!(Ledc<'static>, Ledc::new(res.ledc));
+        ledc.set_global_slow_clock(LSGlobalClkSource::APBClk);
+        let ledc: &'static Ledc<'static> = ledc;
+        // Channels borrow their timer, so it has to live forever.
+        let timer = mk_static!(
+            timer::Timer<'static, LowSpeed>,
+            ledc.timer(timer::Number::Timer0)
+        );
+        timer
+            .configure(timer::config::Config {
+                duty: timer::config::Duty::Duty8Bit,
+                clock_source: timer::LSClockSource::APBClk,
+                frequency: PWM_FREQUENCY,
+            })
+            .unwrap();
+        let mut channel = ledc.channel(channel::Number::Channel0, res.led);
         // Default to backlight ON
+        channel
+            .configure(channel::config::Config {
+                timer,
+                duty_pct: 100,
+                drive_mode: DriveMode::PushPull,
+            })
+            .unwrap();
         Self {
-            pin: Output::new(res.led, Level::High, OutputConfig::default()),
+            channel,
+            brightness: u8::MAX,
+            restore: u8::MAX,
+            pwm: Some(Pwm::new(ledc)),
         }
     }
 }
 
 impl Backlight {
+    /// The LEDC timers and channels the backlight doesn't use, for
+    /// [`pwm`](crate::pwm) outputs and servos. `None` once taken.
+    pub const fn take_pwm(&mut self) -> Option<Pwm> {
+        self.pwm.take()
+    }
+
+    /// Turn on at the last brightness set.
     pub fn on(&mut self) {
-        self.pin.set_high();
+        self.apply(self.restore);
     }
 
     pub fn off(&mut self) {
-        self.pin.set_low();
+        self.apply(0);
     }
 
     pub fn toggle(&mut self) {
-        self.pin.toggle();
+        if self.is_on() {
+            self.off();
+        } else {
+            self.on();
+        }
     }
 
     pub fn is_on(&self) -> bool {
-        self.pin.is_set_high()
+        self.brightness > 0
+    }
+
+    /// Current brightness, 0 when off.
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Set the brightness, from 0 (off) to 255 (full).
+    pub fn set_brightness(&mut self, level: u8) {
+        if level > 0 {
+            self.restore = level;
+        }
+        self.apply(level);
+    }
+
+    /// Fade linearly from the current brightness to `level` over `duration`.
+    pub async fn fade_to(&mut self, level: u8, duration: Duration) {
+        let from = i64::from(self.brightness);
+        let change = i64::from(level) - from;
+        let start = Instant::now();
+        loop {
+            let elapsed = start.elapsed();
+            if elapsed >= duration {
+                break;
+            }
+            let step = change * elapsed.as_micros() as i64 / duration.as_micros() as i64;
+            self.apply((from + step) as u8);
+            Timer::after(FADE_STEP).await;
+        }
+        self.set_brightness(level);
+    }
+
+    fn apply(&mut self, level: u8) {
+        self.brightness = level;
+        let max = u32::from(self.channel.max_duty_cycle());
+        // Full brightness is a constant high, one step past 255/256.
+        let duty = u32::from(level) * max / u32::from(u8::MAX);
+        self.channel.set_duty_cycle(duty as u16).unwrap();
     }
 }
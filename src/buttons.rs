@@ -2,9 +2,31 @@
 //!
 //! The badge has a D-pad (up/down/left/right), A, B, Start, Select,
 //! and a joystick click button.
+//!
+//! [`Sequence`] watches the presses for a secret code such as the Konami code,
+//! for easter eggs, and [`register_secret`] watches every press from
+//! [`Buttons::wait_for_press`] for one, in any app, remembering the unlock
+//! across resets. [`Combos`] maps buttons held together to actions, and
+//! [`claim_combo`] lets system features such as the flashlight claim a
+//! combo held down in any app.
+
+use core::cell::RefCell;
 
+use embassy_futures::select::{
+    Either,
+    select,
+    select_array,
+};
+use embassy_sync::{
+    blocking_mutex::{
+        Mutex,
+        raw::CriticalSectionRawMutex,
+    },
+    signal::Signal,
+};
 use embassy_time::{
     Duration,
+    Instant,
     Timer,
 };
 use esp_hal::gpio::{
@@ -27,6 +49,35 @@ pub struct Buttons {
     pub select: Input<'static>,
 }
 
+/// One of the badge buttons.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum Button {
+    Up,
+    Down,
+    Left,
+    Right,
+    Stick,
+    A,
+    B,
+    Start,
+    Select,
+}
+
+impl Button {
+    /// All nine buttons.
+    pub const ALL: [Self; 9] = [
+        Self::Up,
+        Self::Down,
+        Self::Left,
+        Self::Right,
+        Self::Stick,
+        Self::A,
+        Self::B,
+        Self::Start,
+        Self::Select,
+    ];
+}
+
 const DEBOUNCE_MS: u64 = 20;
 
 impl From<ButtonResources<'static>> for Buttons {
@@ -77,4 +128,569 @@ impl Buttons {
             }
         }
     }
+
+    /// The buttons held down right now.
+    pub fn held(&self) -> Chord {
+        let pins = [
+            self.up.is_low(),
+            self.down.is_low(),
+            self.left.is_low(),
+            self.right.is_low(),
+            self.stick.is_low(),
+            self.a.is_low(),
+            self.b.is_low(),
+            self.start.is_low(),
+            // Select is pulled down and reads high while pressed.
+            self.select.is_high(),
+        ];
+        Button::ALL
+            .into_iter()
+            .zip(pins)
+            .filter(|&(_, held)| held)
+            .fold(Chord::NONE, |chord, (button, _)| chord.with(button))
+    }
+
+    /// Wait for a debounced press of any button and report which one.
+    ///
+    /// Every press also goes to the codes registered with
+    /// [`register_secret`]. A press that completes a combo claimed with
+    /// [`claim_combo`] is reported once the buttons are let go, and not at
+    /// all if they're held long enough to fire the combo.
+    pub async fn wait_for_press(&mut self) -> Button {
+        loop {
+            let button = self.next_press().await;
+            if self.hold_system_combo().await {
+                continue;
+            }
+            watch_secrets(button);
+            return button;
+        }
+    }
+
+    /// The next debounced press of any button.
+    async fn next_press(&mut self) -> Button {
+        const ACTIVE_LOW: [Button; 8] = [
+            Button::Up,
+            Button::Down,
+            Button::Left,
+            Button::Right,
+            Button::Stick,
+            Button::A,
+            Button::B,
+            Button::Start,
+        ];
+        let active_low = select_array([
+            Self::debounce_press(&mut self.up),
+            Self::debounce_press(&mut self.down),
+            Self::debounce_press(&mut self.left),
+            Self::debounce_press(&mut self.right),
+            Self::debounce_press(&mut self.stick),
+            Self::debounce_press(&mut self.a),
+            Self::debounce_press(&mut self.b),
+            Self::debounce_press(&mut self.start),
+        ]);
+        // Select is pulled down and reads high while pressed.
+        match select(active_low, Self::debounce_release(&mut self.select)).await {
+            Either::First(((), i)) => ACTIVE_LOW[i],
+            Either::Second(()) => Button::Select,
+        }
+    }
+
+    /// If the buttons held now are a system combo, wait while they stay
+    /// held. Fires the combo and returns `true` once they've been held for
+    /// [`COMBO_HOLD`], or returns `false` when they change first.
+    async fn hold_system_combo(&self) -> bool {
+        let held = self.held();
+        let Some(slot) = SYSTEM_COMBOS.lock(|combos| combos.borrow().position(held)) else {
+            return false;
+        };
+        let start = Instant::now();
+        while self.held() == held {
+            if start.elapsed() >= COMBO_HOLD {
+                COMBO_FIRED[slot].signal(());
+                return true;
+            }
+            Timer::after(Duration::from_millis(DEBOUNCE_MS)).await;
+        }
+        false
+    }
+}
+
+// ── Secret sequences ────────────────────────────────────────────────────────
+
+/// Recognises a secret sequence of button presses.
+///
+/// Feed it every press; [`push`](Self::push) returns `true` when the last
+/// presses spell out the code, wherever they started.
+///
+/// ```rust,ignore
+/// let mut konami = Sequence::new(Sequence::KONAMI);
+/// loop {
+///     let button = buttons.wait_for_press().await;
+///     if konami.push(button) {
+///         unlock_secret();
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub struct Sequence<'a> {
+    code: &'a [Button],
+    matched: usize,
+}
+
+impl<'a> Sequence<'a> {
+    /// ↑ ↑ ↓ ↓ ← → ← → B A.
+    pub const KONAMI: &'static [Button] = &[
+        Button::Up,
+        Button::Up,
+        Button::Down,
+        Button::Down,
+        Button::Left,
+        Button::Right,
+        Button::Left,
+        Button::Right,
+        Button::B,
+        Button::A,
+    ];
+
+    /// Watch for `code`.
+    pub const fn new(code: &'a [Button]) -> Self {
+        Self { code, matched: 0 }
+    }
+
+    /// Record a press. Returns `true` if it completes the code.
+    pub fn push(&mut self, button: Button) -> bool {
+        // Longest prefix of the code that the latest presses end with.
+        let done = &self.code[..self.matched];
+        self.matched = (1..=self.matched + 1)
+            .rev()
+            .find(|&n| {
+                self.code.get(n - 1) == Some(&button)
+                    && self.code[..n - 1] == done[done.len() + 1 - n..]
+            })
+            .unwrap_or(0);
+        if self.matched == self.code.len() {
+            self.matched = 0;
+            return true;
+        }
+        false
+    }
+
+    /// Forget any partial match.
+    pub const fn reset(&mut self) {
+        self.matched = 0;
+    }
+}
+
+// ── Registered secrets ──────────────────────────────────────────────────────
+
+/// Secret codes that can be registered at once.
+pub const SECRETS: usize = 8;
+
+/// Codes registered with [`register_secret`], by slot.
+static WATCHED: Mutex<CriticalSectionRawMutex, RefCell<[Option<Sequence<'static>>; SECRETS]>> =
+    Mutex::new(RefCell::new([None; SECRETS]));
+
+/// One bit per unlocked slot, in RTC RAM so that it survives resets and
+/// deep sleep. It is cleared at power-on. Only touched inside a
+/// [`WATCHED`] lock.
+#[esp_hal::ram(unstable(rtc_fast, persistent))]
+static mut UNLOCKED: u8 = 0;
+
+/// The slot unlocked last, for [`wait_for_unlock`].
+static UNLOCK: Signal<CriticalSectionRawMutex, usize> = Signal::new();
+
+/// Unlock secret `slot` when the presses spell out `code`, whichever app is
+/// reading the buttons.
+///
+/// Unlocks are remembered by slot, so register the same codes in the same
+/// slots on every boot. Returns `false` if `slot` is out of range or `code`
+/// is empty.
+///
+/// ```rust,ignore
+/// buttons::register_secret(0, Sequence::KONAMI);
+/// if buttons::is_unlocked(0) {
+///     menu.push(("Secret", secret_app));
+/// }
+/// ```
+pub fn register_secret(slot: usize, code: &'static [Button]) -> bool {
+    if slot >= SECRETS || code.is_empty() {
+        return false;
+    }
+    WATCHED.lock(|watched| watched.borrow_mut()[slot] = Some(Sequence::new(code)));
+    true
+}
+
+/// `true` once the code in `slot` has been entered.
+pub fn is_unlocked(slot: usize) -> bool {
+    slot < SECRETS && unlocked() & 1 << slot != 0
+}
+
+/// Wait for a registered code to be entered and return its slot.
+pub async fn wait_for_unlock() -> usize {
+    UNLOCK.wait().await
+}
+
+/// The unlocked slots as bits, for saving along with an app's settings.
+/// RTC RAM keeps them across resets but not power cycles.
+pub fn unlocked() -> u8 {
+    // SAFETY: `UNLOCKED` is only accessed inside the lock.
+    WATCHED.lock(|_| unsafe { UNLOCKED })
+}
+
+/// Restore the bits from [`unlocked`], or lock everything again with 0.
+pub fn restore_unlocked(bits: u8) {
+    // SAFETY: `UNLOCKED` is only accessed inside the lock.
+    WATCHED.lock(|_| unsafe { UNLOCKED = bits });
+}
+
+/// Feed `button` to every registered code.
+fn watch_secrets(button: Button) {
+    let entered = WATCHED.lock(|watched| {
+        let mut entered = None;
+        for (slot, sequence) in watched.borrow_mut().iter_mut().enumerate() {
+            if sequence.as_mut().is_some_and(|s| s.push(button)) {
+                // SAFETY: `UNLOCKED` is only accessed inside the lock.
+                unsafe { UNLOCKED |= 1 << slot };
+                entered = Some(slot);
+            }
+        }
+        entered
+    });
+    if let Some(slot) = entered {
+        UNLOCK.signal(slot);
+    }
+}
+
+// ── Combos ──────────────────────────────────────────────────────────────────
+
+/// A set of buttons pressed together.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub struct Chord(u16);
+
+impl Chord {
+    /// No buttons.
+    pub const NONE: Self = Self(0);
+
+    /// The chord of `buttons`.
+    pub const fn of(buttons: &[Button]) -> Self {
+        let mut chord = Self::NONE;
+        let mut i = 0;
+        while i < buttons.len() {
+            chord = chord.with(buttons[i]);
+            i += 1;
+        }
+        chord
+    }
+
+    /// This chord plus `button`.
+    #[must_use]
+    pub const fn with(self, button: Button) -> Self {
+        Self(self.0 | 1 << button as u16)
+    }
+
+    /// `true` if `button` is part of the chord.
+    pub const fn contains(self, button: Button) -> bool {
+        self.0 & 1 << button as u16 != 0
+    }
+
+    /// `true` for no buttons.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The buttons of the chord, e.g. to show it in a settings screen.
+    pub fn buttons(self) -> impl Iterator<Item = Button> {
+        Button::ALL.into_iter().filter(move |&b| self.contains(b))
+    }
+}
+
+/// Why a combo can't be registered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum ComboError<A> {
+    /// The chord already triggers this other action.
+    Conflict(A),
+    /// A combo needs at least one button.
+    Empty,
+    /// All slots are taken.
+    Full,
+    /// The action to remap has no combo.
+    Unknown,
+}
+
+/// Button combos claimed by actions, in one place rather than hard-coded
+/// wherever they're handled.
+///
+/// Each action claims a [`Chord`]; claiming one that is already taken
+/// fails with the action that has it. A combo fires once when its buttons
+/// are all down, and no others:
+///
+/// ```rust,ignore
+/// let mut combos: Combos<Action, 4> = Combos::new();
+/// combos.register(Action::Flashlight, Chord::of(&[Button::Start, Button::Select]))?;
+/// combos.register(Action::Screenshot, Chord::of(&[Button::A, Button::B]))?;
+/// loop {
+///     match combos.poll(buttons) {
+///         Some(Action::Flashlight) => toggle_flashlight(),
+///         Some(Action::Screenshot) => screenshot(),
+///         None => {}
+///     }
+///     ticker.next().await;
+/// }
+/// ```
+///
+/// [`remap`](Self::remap) changes a combo at runtime, such as from a
+/// settings screen, with the same conflict check.
+pub struct Combos<A, const N: usize> {
+    combos: [Option<(A, Chord)>; N],
+    last: Chord,
+}
+
+impl<A: Copy + PartialEq, const N: usize> Combos<A, N> {
+    /// An empty registry with room for `N` combos.
+    pub const fn new() -> Self {
+        Self {
+            combos: [None; N],
+            last: Chord::NONE,
+        }
+    }
+
+    /// Have `chord` trigger `action`.
+    pub fn register(&mut self, action: A, chord: Chord) -> Result<(), ComboError<A>> {
+        self.check(action, chord)?;
+        if let Some(slot) = self.slot(action) {
+            *slot = Some((action, chord));
+            return Ok(());
+        }
+        let slot = self
+            .combos
+            .iter_mut()
+            .find(|slot| slot.is_none())
+            .ok_or(ComboError::Full)?;
+        *slot = Some((action, chord));
+        Ok(())
+    }
+
+    /// Move the registered `action` to a new `chord`.
+    pub fn remap(&mut self, action: A, chord: Chord) -> Result<(), ComboError<A>> {
+        self.check(action, chord)?;
+        let slot = self.slot(action).ok_or(ComboError::Unknown)?;
+        *slot = Some((action, chord));
+        Ok(())
+    }
+
+    /// Drop the combo of `action`, if any.
+    pub fn unregister(&mut self, action: A) {
+        if let Some(slot) = self.slot(action) {
+            *slot = None;
+        }
+    }
+
+    /// The chord that triggers `action`.
+    pub fn chord(&self, action: A) -> Option<Chord> {
+        self.combos
+            .iter()
+            .flatten()
+            .find(|(a, _)| *a == action)
+            .map(|&(_, chord)| chord)
+    }
+
+    /// Look at the buttons held now, and return the action whose combo
+    /// they just completed. Call it every frame or after each press.
+    pub fn poll(&mut self, buttons: &Buttons) -> Option<A> {
+        self.update(buttons.held())
+    }
+
+    /// Like [`poll`](Self::poll), for buttons read some other way.
+    pub fn update(&mut self, held: Chord) -> Option<A> {
+        let changed = held != self.last;
+        self.last = held;
+        if !changed {
+            return None;
+        }
+        self.combos
+            .iter()
+            .flatten()
+            .find(|&&(_, chord)| chord == held)
+            .map(|&(action, _)| action)
+    }
+
+    /// Whether `action` may take `chord`.
+    fn check(&self, action: A, chord: Chord) -> Result<(), ComboError<A>> {
+        if chord.is_empty() {
+            return Err(ComboError::Empty);
+        }
+        let taken = self
+            .combos
+            .iter()
+            .flatten()
+            .find(|&&(a, c)| c == chord && a != action);
+        match taken {
+            Some(&(other, _)) => Err(ComboError::Conflict(other)),
+            None => Ok(()),
+        }
+    }
+
+    /// The slot whose combo is `chord`.
+    fn position(&self, chord: Chord) -> Option<usize> {
+        self.combos
+            .iter()
+            .position(|slot| slot.is_some_and(|(_, c)| c == chord))
+    }
+
+    fn slot(&mut self, action: A) -> Option<&mut Option<(A, Chord)>> {
+        self.combos
+            .iter_mut()
+            .find(|slot| matches!(slot, Some((a, _)) if *a == action))
+    }
+}
+
+impl<A: Copy + PartialEq, const N: usize> Default for Combos<A, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ── System combos ───────────────────────────────────────────────────────────
+
+/// System actions that can claim a combo at once.
+pub const MAX_SYSTEM_COMBOS: usize = 8;
+
+/// How long a system combo must be held down to fire, so that apps still
+/// get short presses of the same buttons.
+pub const COMBO_HOLD: Duration = Duration::from_millis(1000);
+
+/// Bytes [`combos_to_bytes`] saves: per slot, a hash of the action's name
+/// and its chord.
+pub const COMBO_BYTES: usize = MAX_SYSTEM_COMBOS * 6;
+
+/// Combos claimed with [`claim_combo`], by action name.
+static SYSTEM_COMBOS: Mutex<
+    CriticalSectionRawMutex,
+    RefCell<Combos<&'static str, MAX_SYSTEM_COMBOS>>,
+> = Mutex::new(RefCell::new(Combos::new()));
+
+/// Raised when the combo in the same slot is held.
+static COMBO_FIRED: [Signal<CriticalSectionRawMutex, ()>; MAX_SYSTEM_COMBOS] =
+    [const { Signal::new() }; MAX_SYSTEM_COMBOS];
+
+/// A system action's claim on a combo, from [`claim_combo`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub struct SystemCombo(usize);
+
+impl SystemCombo {
+    /// Wait for the combo to be held for [`COMBO_HOLD`] in any app reading
+    /// the buttons with [`Buttons::wait_for_press`].
+    pub async fn wait(self) {
+        COMBO_FIRED[self.0].wait().await;
+    }
+}
+
+/// Have `chord` held down trigger the system feature `action`, whichever
+/// app is running, rather than hard-coding the combo where it's handled.
+/// The name identifies the action in [`combos_to_bytes`] and
+/// [`remap_combo`].
+///
+/// Claim the combos at boot, before restoring the user's remaps:
+///
+/// ```rust,ignore
+/// let flashlight = buttons::claim_combo("flashlight", Chord::of(&[Button::Start]))?;
+/// buttons::combos_from_bytes(&settings.combos);
+/// loop {
+///     flashlight.wait().await;
+///     toggle_flashlight();
+/// }
+/// ```
+pub fn claim_combo(
+    action: &'static str,
+    chord: Chord,
+) -> Result<SystemCombo, ComboError<&'static str>> {
+    SYSTEM_COMBOS.lock(|combos| {
+        let mut combos = combos.borrow_mut();
+        combos.register(action, chord)?;
+        combos
+            .position(chord)
+            .map(SystemCombo)
+            .ok_or(ComboError::Unknown)
+    })
+}
+
+/// Move the system `action` to a new `chord`, such as from a settings
+/// screen.
+pub fn remap_combo(action: &'static str, chord: Chord) -> Result<(), ComboError<&'static str>> {
+    SYSTEM_COMBOS.lock(|combos| combos.borrow_mut().remap(action, chord))
+}
+
+/// The chord that triggers the system `action`.
+pub fn combo(action: &str) -> Option<Chord> {
+    SYSTEM_COMBOS.lock(|combos| {
+        combos
+            .borrow()
+            .combos
+            .iter()
+            .flatten()
+            .find(|(a, _)| *a == action)
+            .map(|&(_, chord)| chord)
+    })
+}
+
+/// The system combos, for saving the user's remaps with the settings.
+pub fn combos_to_bytes() -> [u8; COMBO_BYTES] {
+    let mut bytes = [0; COMBO_BYTES];
+    SYSTEM_COMBOS.lock(|combos| {
+        for (out, &(action, chord)) in bytes
+            .chunks_exact_mut(6)
+            .zip(combos.borrow().combos.iter().flatten())
+        {
+            out[..4].copy_from_slice(&name_hash(action).to_le_bytes());
+            out[4..].copy_from_slice(&chord.0.to_le_bytes());
+        }
+    });
+    bytes
+}
+
+/// Remap the claimed actions to the chords saved with [`combos_to_bytes`].
+/// Actions not claimed now, and chords that would conflict, are skipped.
+pub fn combos_from_bytes(bytes: &[u8; COMBO_BYTES]) {
+    SYSTEM_COMBOS.lock(|combos| {
+        let mut combos = combos.borrow_mut();
+        let saved = bytes.chunks_exact(6).filter_map(|entry| {
+            let hash = u32::from_le_bytes([entry[0], entry[1], entry[2], entry[3]]);
+            let chord = Chord::of(&Button::ALL).0 & u16::from_le_bytes([entry[4], entry[5]]);
+            let chord = Chord(chord);
+            let action = combos
+                .combos
+                .iter()
+                .flatten()
+                .find(|(a, _)| name_hash(a) == hash)?
+                .0;
+            Some((action, chord))
+        });
+        let mut pending = [None; MAX_SYSTEM_COMBOS];
+        for (slot, entry) in pending.iter_mut().zip(saved) {
+            *slot = Some(entry);
+        }
+        // A swap of two chords conflicts halfway, so go round until
+        // nothing more moves.
+        let mut moved = true;
+        while moved {
+            moved = false;
+            for slot in &mut pending {
+                let Some((action, chord)) = *slot else {
+                    continue;
+                };
+                if combos.chord(action) == Some(chord) || combos.remap(action, chord).is_ok() {
+                    *slot = None;
+                    moved = true;
+                }
+            }
+        }
+    });
+}
+
+/// FNV-1a of an action name, to save it in a few bytes.
+fn name_hash(name: &str) -> u32 {
+    name.bytes().fold(0x811c_9dc5, |hash, b| {
+        (hash ^ u32::from(b)).wrapping_mul(0x0100_0193)
+    })
 }
@@ -0,0 +1,125 @@
+//! Off-screen drawing surface that can be stamped onto any target.
+
+use core::convert::Infallible;
+
+use embedded_graphics::{
+    Pixel,
+    image::ImageDrawable,
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::Rectangle,
+};
+
+use crate::{
+    Framebuffer,
+    image::draw_opaque_runs,
+};
+
+/// An RGB565 drawing surface in RAM the caller provides, drawn elsewhere as
+/// an image.
+///
+/// Compose something once, such as a HUD panel or a sprite with text on it,
+/// and stamp it through [`Image`](embedded_graphics::image::Image) as often
+/// as needed. Each stamp is a single window write, so a small region can
+/// also be double-buffered by redrawing it in a canvas and stamping the
+/// result:
+///
+/// ```rust,ignore
+/// let mut pixels = [Rgb565::BLACK; 64 * 16];
+/// let mut hud = Canvas::new(&mut pixels, Size::new(64, 16));
+/// Text::new("HP 99", Point::new(2, 12), style).draw(&mut hud)?;
+/// Image::new(&hud, Point::new(250, 4)).draw(display)?;
+/// ```
+pub struct Canvas<'a> {
+    framebuffer: Framebuffer<'a>,
+    transparent: Option<Rgb565>,
+}
+
+impl<'a> Canvas<'a> {
+    /// Wrap `pixels` as a row-major canvas of `size`, keeping whatever
+    /// they hold.
+    ///
+    /// # Panics
+    ///
+    /// If `pixels` is smaller than `size`.
+    pub fn new(pixels: &'a mut [Rgb565], size: Size) -> Self {
+        Self {
+            framebuffer: Framebuffer::new(pixels, size),
+            transparent: None,
+        }
+    }
+
+    /// Leave pixels of `color` out when the canvas is drawn, so what is
+    /// underneath shows through. Transparent canvases are drawn a run of
+    /// pixels at a time rather than in one write.
+    #[must_use]
+    pub const fn with_transparent(mut self, color: Rgb565) -> Self {
+        self.transparent = Some(color);
+        self
+    }
+
+    /// The canvas as a plain framebuffer, e.g. for
+    /// [`draw_blended`](Framebuffer::draw_blended) or direct pixel access.
+    pub const fn framebuffer(&mut self) -> &mut Framebuffer<'a> {
+        &mut self.framebuffer
+    }
+}
+
+impl OriginDimensions for Canvas<'_> {
+    fn size(&self) -> Size {
+        self.framebuffer.size()
+    }
+}
+
+impl DrawTarget for Canvas<'_> {
+    type Color = Rgb565;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.framebuffer.draw_iter(pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.framebuffer.fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.framebuffer.fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.framebuffer.clear(color)
+    }
+}
+
+impl ImageDrawable for Canvas<'_> {
+    type Color = Rgb565;
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let pixels = self.framebuffer.pixels().iter().copied();
+        let Some(transparent) = self.transparent else {
+            return target.fill_contiguous(&self.bounding_box(), pixels);
+        };
+        let width = self.size().width as usize;
+        let all = self.framebuffer.pixels();
+        draw_opaque_runs(target, self.size(), pixels, |x, y| {
+            all[y as usize * width + x as usize] != transparent
+        })
+    }
+
+    fn draw_sub_image<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.draw(&mut target.translated(-area.top_left).clipped(area))
+    }
+}
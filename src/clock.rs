@@ -0,0 +1,163 @@
+//! Software wall clock.
+//!
+//! The badge has no battery-backed RTC, so the time of day is kept as an
+//! offset from boot. Set it once (from a compile-time default, a menu or the
+//! network) and every task reads the same time through [`now`].
+//!
+//! The offset counts from the Unix epoch, so apps that need the date as well,
+//! such as one-time passwords, can set and read [`unix_time`] instead.
+//!
+//! ```rust,ignore
+//! clock::set(Time::parse_hm("09:30").unwrap());
+//! let t = clock::now();
+//! info!("{=u64}:{=u64}", t.hour(), t.minute());
+//! ```
+
+use core::cell::Cell;
+
+use embassy_sync::blocking_mutex::{
+    Mutex,
+    raw::CriticalSectionRawMutex,
+};
+use embassy_time::{
+    Duration,
+    Instant,
+};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Seconds to add to the uptime to get the Unix time, wrapping.
+static OFFSET: Mutex<CriticalSectionRawMutex, Cell<u64>> = Mutex::new(Cell::new(0));
+
+/// A time of day with one-second resolution.
+#[derive(Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub struct Time(u64);
+
+impl Time {
+    /// 00:00:00.
+    pub const MIDNIGHT: Self = Self(0);
+
+    /// Build a time of day from hours, minutes and seconds.
+    ///
+    /// Values past the end of the day wrap around.
+    pub const fn from_hms(hour: u64, minute: u64, second: u64) -> Self {
+        Self(((hour * 60 + minute) * 60 + second) % SECONDS_PER_DAY)
+    }
+
+    /// Parse `HH:MM` (24-hour).
+    pub const fn parse_hm(s: &str) -> Option<Self> {
+        let b = s.as_bytes();
+        if b.len() != 5 || b[2] != b':' {
+            return None;
+        }
+        let (Some(h), Some(m)) = (two_digits(b[0], b[1]), two_digits(b[3], b[4])) else {
+            return None;
+        };
+        if h > 23 || m > 59 {
+            return None;
+        }
+        Some(Self::from_hms(h, m, 0))
+    }
+
+    /// Hour of the day, 0–23.
+    pub const fn hour(self) -> u64 {
+        self.0 / 3600
+    }
+
+    /// Minute of the hour, 0–59.
+    pub const fn minute(self) -> u64 {
+        self.0 / 60 % 60
+    }
+
+    /// Second of the minute, 0–59.
+    pub const fn second(self) -> u64 {
+        self.0 % 60
+    }
+
+    /// Seconds since midnight.
+    pub const fn seconds_of_day(self) -> u64 {
+        self.0
+    }
+
+    /// This time moved forward by `seconds`, or back if negative.
+    #[must_use]
+    pub const fn add_seconds(self, seconds: i64) -> Self {
+        let secs = self.seconds_of_day() as i64 + seconds;
+        Self(secs.rem_euclid(SECONDS_PER_DAY as i64) as u64)
+    }
+
+    /// The same moment in a time zone `hours` ahead, or behind if negative.
+    #[must_use]
+    pub const fn with_zone_offset(self, hours: i64) -> Self {
+        self.add_seconds(hours * 3600)
+    }
+
+    /// Time left until the next time the clock shows `target`'s time of day.
+    pub const fn until(self, target: Self) -> Duration {
+        let now = self.seconds_of_day();
+        let then = target.seconds_of_day();
+        Duration::from_secs((then + SECONDS_PER_DAY - now) % SECONDS_PER_DAY)
+    }
+
+    /// Format as `HH:MM:SS` into `buf`.
+    pub fn format_hms(self, buf: &mut [u8; 8]) -> &str {
+        format_hms(self.seconds_of_day(), buf)
+    }
+}
+
+/// Format a duration as `HH:MM:SS` into `buf`, saturating at 99 hours.
+pub fn format_duration(duration: Duration, buf: &mut [u8; 8]) -> &str {
+    format_hms(duration.as_secs().min(100 * 3600 - 1), buf)
+}
+
+fn format_hms(secs: u64, buf: &mut [u8; 8]) -> &str {
+    for (i, v) in [secs / 3600, secs / 60 % 60, secs % 60]
+        .into_iter()
+        .enumerate()
+    {
+        buf[i * 3] = b'0' + (v / 10) as u8;
+        buf[i * 3 + 1] = b'0' + (v % 10) as u8;
+        if i < 2 {
+            buf[i * 3 + 2] = b':';
+        }
+    }
+    core::str::from_utf8(buf).unwrap()
+}
+
+const fn two_digits(hi: u8, lo: u8) -> Option<u64> {
+    if hi.is_ascii_digit() && lo.is_ascii_digit() {
+        Some(((hi - b'0') * 10 + (lo - b'0')) as u64)
+    } else {
+        None
+    }
+}
+
+/// The current time of day.
+pub fn now() -> Time {
+    Time(unix_time() % SECONDS_PER_DAY)
+}
+
+/// Set the clock so that [`now`] returns `time`, keeping the date.
+pub fn set(time: Time) {
+    let today = unix_time() / SECONDS_PER_DAY * SECONDS_PER_DAY;
+    set_unix_time(today + time.seconds_of_day());
+}
+
+/// Seconds since 1970-01-01 00:00 UTC. Only the time of day means anything
+/// unless the date was set with [`set_unix_time`].
+pub fn unix_time() -> u64 {
+    OFFSET
+        .lock(Cell::get)
+        .wrapping_add(Instant::now().as_secs())
+}
+
+/// Set the date and time, as seconds since 1970-01-01 00:00 UTC.
+pub fn set_unix_time(seconds: u64) {
+    let offset = seconds.wrapping_sub(Instant::now().as_secs());
+    OFFSET.lock(|cell| cell.set(offset));
+}
+
+/// Move the clock forward by `seconds`, or back if negative.
+pub fn adjust(seconds: i64) {
+    set(now().add_seconds(seconds));
+}
@@ -0,0 +1,295 @@
+//! Layers drawn over the app's frame, such as a status bar, toasts or a
+//! debug readout, without the app redrawing around them.
+
+use core::convert::Infallible;
+
+use embedded_graphics::{
+    Pixel,
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::Rectangle,
+};
+
+use crate::{
+    Display,
+    Framebuffer,
+    display::Error,
+};
+
+/// A drawing surface shown over the app's frame by a [`Compositor`].
+///
+/// Draw into it like any target; only what changed is resent. It needs a
+/// buffer of twice its area: half for its pixels and half to keep what it
+/// covers on the screen.
+pub struct Layer<'a> {
+    surface: Framebuffer<'a>,
+    transparent: Option<Rgb565>,
+    under: &'a mut [Rgb565],
+    position: Point,
+    visible: bool,
+    /// Screen area to resend at the next flush, because the layer moved,
+    /// appeared or went away.
+    exposed: Option<Rectangle>,
+    /// Screen area saved in `under` while the layer is stamped.
+    stamped: Option<Rectangle>,
+}
+
+impl<'a> Layer<'a> {
+    /// A visible layer of `size` at the top left of the screen, cleared to
+    /// black.
+    ///
+    /// # Panics
+    ///
+    /// If `buffer` holds fewer than twice `size` pixels.
+    pub fn new(buffer: &'a mut [Rgb565], size: Size) -> Self {
+        let area = (size.width * size.height) as usize;
+        assert!(buffer.len() >= 2 * area);
+        let (pixels, under) = buffer.split_at_mut(area);
+        pixels.fill(Rgb565::BLACK);
+        Self {
+            surface: Framebuffer::new(pixels, size),
+            transparent: None,
+            under,
+            position: Point::zero(),
+            visible: true,
+            exposed: Some(Rectangle::new(Point::zero(), size)),
+            stamped: None,
+        }
+    }
+
+    /// Put the layer at `position` on the screen.
+    #[must_use]
+    pub fn with_position(mut self, position: Point) -> Self {
+        self.set_position(position);
+        self
+    }
+
+    /// Let the frame show through pixels of `color`.
+    #[must_use]
+    pub const fn with_transparent(mut self, color: Rgb565) -> Self {
+        self.transparent = Some(color);
+        self
+    }
+
+    /// Start hidden, e.g. for a toast.
+    #[must_use]
+    pub fn hidden(mut self) -> Self {
+        self.hide();
+        self
+    }
+
+    /// Where the layer's top left corner is on the screen.
+    pub const fn position(&self) -> Point {
+        self.position
+    }
+
+    /// Move the layer to `position` on the screen.
+    pub fn set_position(&mut self, position: Point) {
+        if position != self.position {
+            self.expose();
+            self.position = position;
+            self.expose();
+        }
+    }
+
+    /// `true` if the layer is shown.
+    pub const fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Show the layer.
+    pub fn show(&mut self) {
+        if !self.visible {
+            self.visible = true;
+            self.expose();
+        }
+    }
+
+    /// Hide the layer, uncovering the frame under it.
+    pub fn hide(&mut self) {
+        if self.visible {
+            self.expose();
+            self.visible = false;
+        }
+    }
+
+    /// The layer's screen area.
+    fn screen_area(&self) -> Rectangle {
+        Rectangle::new(self.position, self.surface.size())
+    }
+
+    /// Resend the layer's current screen area at the next flush.
+    fn expose(&mut self) {
+        let area = self.screen_area();
+        self.exposed = Some(match self.exposed {
+            Some(exposed) => union(&exposed, &area),
+            None => area,
+        });
+    }
+
+    /// Save what the layer covers in `frame`, draw the layer over it and
+    /// mark what needs resending.
+    fn stamp(&mut self, frame: &mut Framebuffer<'_>) {
+        let changed = self.surface.take_dirty();
+        if let Some(exposed) = self.exposed.take() {
+            frame.mark_dirty(&exposed);
+        }
+        if !self.visible {
+            return;
+        }
+        if let Some(changed) = changed {
+            frame.mark_dirty(&changed.translate(self.position));
+        }
+
+        let area = self.screen_area().intersection(&frame.bounding_box());
+        let (w, frame_width) = (area.size.width as usize, frame.size().width as usize);
+        let layer_width = self.surface.size().width as usize;
+        let offset = area.top_left - self.position;
+        let pixels = self.surface.pixels();
+        let screen = frame.pixels_untracked();
+        for (row, y) in area.rows().enumerate() {
+            let start = y as usize * frame_width + area.top_left.x as usize;
+            let on_screen = &mut screen[start..start + w];
+            self.under[row * w..(row + 1) * w].copy_from_slice(on_screen);
+
+            let from = (offset.y as usize + row) * layer_width + offset.x as usize;
+            for (px, &color) in on_screen.iter_mut().zip(&pixels[from..from + w]) {
+                if Some(color) != self.transparent {
+                    *px = color;
+                }
+            }
+        }
+        self.stamped = Some(area);
+    }
+
+    /// Put back what [`stamp`](Self::stamp) covered, leaving the frame as
+    /// the app drew it.
+    fn restore(&mut self, frame: &mut Framebuffer<'_>) {
+        let Some(area) = self.stamped.take() else {
+            return;
+        };
+        let (w, frame_width) = (area.size.width as usize, frame.size().width as usize);
+        let screen = frame.pixels_untracked();
+        for (row, y) in area.rows().enumerate() {
+            let start = y as usize * frame_width + area.top_left.x as usize;
+            screen[start..start + w].copy_from_slice(&self.under[row * w..(row + 1) * w]);
+        }
+    }
+}
+
+impl OriginDimensions for Layer<'_> {
+    fn size(&self) -> Size {
+        self.surface.size()
+    }
+}
+
+impl DrawTarget for Layer<'_> {
+    type Color = Rgb565;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.surface.draw_iter(pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.surface.fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.surface.fill_solid(area, color)
+    }
+}
+
+/// The smallest rectangle holding both `a` and `b`.
+fn union(a: &Rectangle, b: &Rectangle) -> Rectangle {
+    match (a.bottom_right(), b.bottom_right()) {
+        (Some(a_br), Some(b_br)) => Rectangle::with_corners(
+            a.top_left.component_min(b.top_left),
+            a_br.component_max(b_br),
+        ),
+        (Some(_), None) => *a,
+        _ => *b,
+    }
+}
+
+/// Shares the display between the app and `N` [`Layer`]s over it, the
+/// first at the bottom: say a status bar and toasts, with a debug readout
+/// on top.
+///
+/// The app keeps drawing its frame into the display's framebuffer as if
+/// nothing covered it, and flushes through the compositor instead of the
+/// display. Each flush lays the visible layers over the frame, sends only
+/// the tiles that changed in the frame or in a layer, then takes the layers
+/// off again:
+///
+/// ```rust,ignore
+/// let mut compositor = Compositor::new([status_bar, toast.hidden()]);
+/// loop {
+///     compositor.layer_mut(0).clear(Rgb565::BLACK)?;
+///     Text::new(&clock, Point::new(4, 12), style).draw(compositor.layer_mut(0))?;
+///     compositor.frame(display, |fb| game.draw(fb))?;
+/// }
+/// ```
+pub struct Compositor<'a, const N: usize> {
+    layers: [Layer<'a>; N],
+}
+
+impl<'a, const N: usize> Compositor<'a, N> {
+    /// Composite `layers`, bottom first.
+    pub const fn new(layers: [Layer<'a>; N]) -> Self {
+        Self { layers }
+    }
+
+    /// Layer `index`, counted from the bottom.
+    pub const fn layer_mut(&mut self, index: usize) -> &mut Layer<'a> {
+        &mut self.layers[index]
+    }
+
+    /// Draw the app's frame with `draw`, like [`Display::frame`], then
+    /// flush it with the layers over it.
+    pub fn frame<R>(
+        &mut self,
+        display: &mut Display<'_>,
+        draw: impl FnOnce(&mut Framebuffer<'static>) -> R,
+    ) -> Result<R, Error> {
+        let result = draw(display.framebuffer());
+        self.flush(display)?;
+        Ok(result)
+    }
+
+    /// Push the frame and layers to the panel, blocking until the transfer
+    /// is done.
+    pub fn flush(&mut self, display: &mut Display<'_>) -> Result<(), Error> {
+        self.stamp(display.framebuffer());
+        let result = display.flush();
+        self.restore(display.framebuffer());
+        result
+    }
+
+    /// Push the frame and layers to the panel over DMA, yielding to other
+    /// tasks while it's in flight.
+    pub async fn flush_async(&mut self, display: &mut Display<'_>) -> Result<(), Error> {
+        self.stamp(display.framebuffer());
+        let result = display.flush_async().await;
+        self.restore(display.framebuffer());
+        result
+    }
+
+    fn stamp(&mut self, frame: &mut Framebuffer<'_>) {
+        for layer in &mut self.layers {
+            layer.stamp(frame);
+        }
+    }
+
+    fn restore(&mut self, frame: &mut Framebuffer<'_>) {
+        for layer in self.layers.iter_mut().rev() {
+            layer.restore(frame);
+        }
+    }
+}
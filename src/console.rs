@@ -0,0 +1,213 @@
+//! Scrolling text console.
+
+use core::fmt;
+
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::Rectangle,
+    text::{
+        Baseline,
+        Text,
+    },
+};
+
+/// Where an ANSI escape sequence is being skipped.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Escape {
+    None,
+    /// After ESC.
+    Start,
+    /// Inside `ESC [`, up to the final byte.
+    Csi,
+}
+
+/// A screen area of monospaced text that wraps at its right edge and
+/// scrolls up when full, like a terminal.
+///
+/// Text goes in through [`fmt::Write`] or [`write_bytes`](Self::write_bytes)
+/// and only appears on [`draw`](Self::draw), which redraws just the rows
+/// that changed:
+///
+/// ```rust,ignore
+/// let mut cells = [0u8; 53 * 17];
+/// let style = MonoTextStyle::new(&FONT_6X10, Rgb565::CSS_LIGHT_GREEN);
+/// let mut console = Console::new(&mut cells, display.bounding_box(), style);
+/// writeln!(console, "flag part 1: {part}")?;
+/// console.draw(display)?;
+/// ```
+///
+/// Newline, carriage return, backspace and tab move the cursor, ANSI escape
+/// sequences such as colours are skipped, and anything else outside
+/// printable ASCII shows as `?`.
+pub struct Console<'a> {
+    /// One byte per character cell, row after row.
+    cells: &'a mut [u8],
+    area: Rectangle,
+    style: MonoTextStyle<'a, Rgb565>,
+    background: Rgb565,
+    columns: usize,
+    rows: usize,
+    line_height: u32,
+    /// Row of `cells` shown at the top of the area.
+    top: usize,
+    /// Screen row and column of the cursor.
+    row: usize,
+    column: usize,
+    /// Screen rows from `.0` up to `.1` need redrawing.
+    dirty: (usize, usize),
+    escape: Escape,
+}
+
+impl<'a> Console<'a> {
+    /// An empty console filling `area` with text in `style`, on black.
+    ///
+    /// `buffer` holds one byte per character. Rows of `area` that don't fit
+    /// in it are left unused.
+    pub fn new(buffer: &'a mut [u8], area: Rectangle, style: MonoTextStyle<'a, Rgb565>) -> Self {
+        let character = style.font.character_size;
+        let spacing = style.font.character_spacing;
+        let columns = ((area.size.width + spacing) / (character.width + spacing).max(1)) as usize;
+        let rows = (area.size.height / character.height.max(1)) as usize;
+        let rows = rows.min(buffer.len().checked_div(columns).unwrap_or(0));
+        let mut console = Self {
+            cells: buffer,
+            area,
+            style,
+            background: Rgb565::BLACK,
+            columns,
+            rows,
+            line_height: character.height,
+            top: 0,
+            row: 0,
+            column: 0,
+            dirty: (0, 0),
+            escape: Escape::None,
+        };
+        console.clear();
+        console
+    }
+
+    /// Colour behind the text.
+    #[must_use]
+    pub const fn with_background(mut self, background: Rgb565) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Characters per row.
+    pub const fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Rows of text.
+    pub const fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Erase all text and put the cursor at the top left.
+    pub fn clear(&mut self) {
+        self.cells.fill(b' ');
+        self.top = 0;
+        self.row = 0;
+        self.column = 0;
+        self.dirty = (0, self.rows);
+        self.escape = Escape::None;
+    }
+
+    /// Add raw bytes, such as output read from a UART.
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.push(byte);
+        }
+    }
+
+    /// Redraw the rows that changed since the last call.
+    pub fn draw<D>(&mut self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let (first, last) = core::mem::replace(&mut self.dirty, (self.rows, 0));
+        for row in first..last.min(self.rows) {
+            let top_left =
+                self.area.top_left + Point::new(0, (row as u32 * self.line_height) as i32);
+            let strip = Rectangle::new(top_left, Size::new(self.area.size.width, self.line_height));
+            target.fill_solid(&strip, self.background)?;
+            let line = self.line(row);
+            let text = core::str::from_utf8(line).unwrap_or("");
+            Text::with_baseline(text.trim_end(), top_left, self.style, Baseline::Top)
+                .draw(target)?;
+        }
+        Ok(())
+    }
+
+    /// Cells of screen row `row`.
+    fn line(&self, row: usize) -> &[u8] {
+        let start = (self.top + row) % self.rows * self.columns;
+        &self.cells[start..start + self.columns]
+    }
+
+    fn mark_dirty(&mut self, first: usize, last: usize) {
+        self.dirty = (self.dirty.0.min(first), self.dirty.1.max(last));
+    }
+
+    /// Feed one byte through the escape sequence filter.
+    fn push(&mut self, byte: u8) {
+        self.escape = match (self.escape, byte) {
+            (Escape::None, 0x1b) => Escape::Start,
+            (Escape::Start, b'[') => Escape::Csi,
+            (Escape::Csi, 0x40..=0x7e) | (Escape::Start, _) => Escape::None,
+            (Escape::Csi, _) => Escape::Csi,
+            (Escape::None, _) => {
+                self.put(byte);
+                Escape::None
+            }
+        };
+    }
+
+    fn put(&mut self, byte: u8) {
+        if self.rows == 0 || self.columns == 0 {
+            return;
+        }
+        match byte {
+            b'\n' => self.new_line(),
+            b'\r' => self.column = 0,
+            0x08 => self.column = self.column.saturating_sub(1),
+            b'\t' => self.column = ((self.column / 8 + 1) * 8).min(self.columns - 1),
+            0x00..=0x1f | 0x7f => {}
+            _ => {
+                if self.column == self.columns {
+                    self.new_line();
+                }
+                let byte = if byte.is_ascii() { byte } else { b'?' };
+                let start = (self.top + self.row) % self.rows * self.columns;
+                self.cells[start + self.column] = byte;
+                self.column += 1;
+                self.mark_dirty(self.row, self.row + 1);
+            }
+        }
+    }
+
+    fn new_line(&mut self) {
+        self.column = 0;
+        if self.row + 1 < self.rows {
+            self.row += 1;
+            return;
+        }
+        // Reuse the top row as the new bottom one.
+        let start = self.top * self.columns;
+        self.cells[start..start + self.columns].fill(b' ');
+        self.top = (self.top + 1) % self.rows;
+        self.mark_dirty(0, self.rows);
+    }
+}
+
+impl fmt::Write for Console<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            self.push(if c.is_ascii() { c as u8 } else { b'?' });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+//! Integer trig for dials: clock hands, and directions round the badge.
+//!
+//! [`ClockFace`] draws an analog clock with it, and [`sweep_seconds`]
+//! counts the minute round the LED bars, for clock apps and the
+//! [clock screensaver](crate::screensaver::Animation::Clock).
+
+use embedded_graphics::{
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::{
+        Circle,
+        Line,
+        PrimitiveStyle,
+    },
+};
+use palette::Srgb;
+
+use crate::{
+    BAR_COUNT,
+    Leds,
+    clock::Time,
+};
+
+const DIAL: Rgb565 = Rgb565::new(8, 16, 8);
+const HOUR_HAND: Rgb565 = Rgb565::WHITE;
+const MINUTE_HAND: Rgb565 = Rgb565::CSS_LIGHT_GRAY;
+const SECOND_HAND: Rgb565 = Rgb565::CSS_ORANGE_RED;
+
+/// Brightest an LED of the seconds sweep gets.
+const SWEEP_PEAK: u64 = 87;
+
+/// Sine over a quarter turn, scaled to 1024.
+const QUARTER_SINE: [i32; 65] = [
+    0, 25, 50, 75, 100, 125, 150, 175, 200, 224, 249, 273, 297, 321, 345, 369, 392, 415, 438, 460,
+    483, 505, 526, 548, 569, 590, 610, 630, 650, 669, 688, 706, 724, 742, 759, 775, 792, 807, 822,
+    837, 851, 865, 878, 891, 903, 915, 926, 936, 946, 955, 964, 972, 980, 987, 993, 999, 1004,
+    1009, 1013, 1016, 1019, 1021, 1023, 1024, 1024,
+];
+
+/// Sine of `angle` (1024 per turn), scaled to ±1024.
+fn sine(angle: i32) -> i32 {
+    let angle = angle.rem_euclid(1024);
+    let i = (angle % 256 / 4) as usize;
+    match angle / 256 {
+        0 => QUARTER_SINE[i],
+        1 => QUARTER_SINE[64 - i],
+        2 => -QUARTER_SINE[i],
+        _ => -QUARTER_SINE[64 - i],
+    }
+}
+
+/// The point `length` pixels from `center` at `angle`, 1024 per turn
+/// clockwise from 12.
+pub fn on_dial(center: Point, angle: i32, length: i32) -> Point {
+    center
+        + Point::new(
+            sine(angle) * length / 1024,
+            -sine(angle + 256) * length / 1024,
+        )
+}
+
+/// An analog clock face: a dial with twelve marks and three hands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClockFace {
+    center: Point,
+    radius: i32,
+}
+
+impl ClockFace {
+    /// A face of `radius` pixels around `center`.
+    pub const fn new(center: Point, radius: i32) -> Self {
+        Self { center, radius }
+    }
+
+    /// Draw the dial and the hands at `time` over whatever is there.
+    pub fn draw<D>(&self, target: &mut D, time: Time) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let dial = PrimitiveStyle::with_stroke(DIAL, 2);
+        Circle::with_center(self.center, self.radius as u32 * 2)
+            .into_styled(dial)
+            .draw(target)?;
+        for tick in 0..12 {
+            let angle = tick * 1024 / 12;
+            let inner = self.radius - if tick % 3 == 0 { 12 } else { 6 };
+            Line::new(
+                on_dial(self.center, angle, inner),
+                on_dial(self.center, angle, self.radius - 2),
+            )
+            .into_styled(dial)
+            .draw(target)?;
+        }
+        self.draw_hands(target, time, None)
+    }
+
+    /// Paint over the hands drawn for `time` in `background`, so a face
+    /// drawn straight to the panel can move them without clearing it.
+    pub fn erase_hands<D>(
+        &self,
+        target: &mut D,
+        time: Time,
+        background: Rgb565,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        self.draw_hands(target, time, Some(background))
+    }
+
+    /// The hands at `time`, in their own colours or all in `color`.
+    fn draw_hands<D>(
+        &self,
+        target: &mut D,
+        time: Time,
+        color: Option<Rgb565>,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let (h, m, s) = (time.hour() % 12, time.minute(), time.second());
+        let hour_angle = ((h * 60 + m) * 1024 / (12 * 60)) as i32;
+        let minute_angle = ((m * 60 + s) * 1024 / 3600) as i32;
+        let second_angle = (s * 1024 / 60) as i32;
+        for (angle, length, width, hand) in [
+            (hour_angle, self.radius * 5 / 10, 5, HOUR_HAND),
+            (minute_angle, self.radius * 8 / 10, 3, MINUTE_HAND),
+            (second_angle, self.radius * 9 / 10, 1, SECOND_HAND),
+        ] {
+            Line::new(self.center, on_dial(self.center, angle, length))
+                .into_styled(PrimitiveStyle::with_stroke(color.unwrap_or(hand), width))
+                .draw(target)?;
+        }
+        Circle::with_center(self.center, 7)
+            .into_styled(PrimitiveStyle::with_fill(color.unwrap_or(SECOND_HAND)))
+            .draw(target)
+    }
+}
+
+/// Light the LEDs up the left bar, then down the right one, as the minute
+/// of `time` goes by. The LED currently filling fades in.
+pub fn sweep_seconds(leds: &mut Leds<'_>, time: Time) {
+    let progress = time.second() * 1000 / 60; // 0..1000 over the minute
+    let per_led = 1000 / (2 * BAR_COUNT as u64);
+    let level = |i: usize| {
+        let start = i as u64 * per_led;
+        let lit = progress.saturating_sub(start).min(per_led);
+        lit * SWEEP_PEAK / per_led
+    };
+    let amber = |level: u64| Srgb::new(level as u8, (level * 3 / 5) as u8, 0);
+
+    let mut left = [Srgb::new(0, 0, 0); BAR_COUNT];
+    let mut right = [Srgb::new(0, 0, 0); BAR_COUNT];
+    for i in 0..BAR_COUNT {
+        left[i] = amber(level(i));
+        right[BAR_COUNT - 1 - i] = amber(level(BAR_COUNT + i));
+    }
+    leds.set_left_bar(&left);
+    leds.set_right_bar(&right);
+}
@@ -1,6 +1,37 @@
 //! ST7789 display driver — 320×170 LCD over SPI with DMA.
 
-use embedded_hal_bus::spi::ExclusiveDevice;
+#[cfg(feature = "psram")]
+use alloc::alloc::Layout;
+use core::{
+    convert::Infallible,
+    ops::{
+        Deref,
+        DerefMut,
+    },
+};
+
+use embassy_time::{
+    Duration,
+    Instant,
+    Timer,
+};
+use embedded_graphics::{
+    Pixel,
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::{
+        PrimitiveStyleBuilder,
+        Rectangle,
+        StrokeAlignment,
+    },
+};
+use embedded_hal::spi::SpiDevice as _;
+use embedded_hal_bus::spi::{
+    DeviceError,
+    ExclusiveDevice,
+};
+#[cfg(feature = "psram")]
+use esp_alloc::MemoryCapability;
 use esp_hal::{
     Async,
     dma::{
@@ -13,20 +44,774 @@ use esp_hal::{
         Output,
         OutputConfig,
     },
-    spi::master::Spi,
+    spi::master::{
+        Spi,
+        SpiDmaBus,
+    },
     time::Rate,
 };
+use mipidsi::interface::Interface;
+pub use mipidsi::options::{
+    Orientation,
+    Rotation,
+};
+#[cfg(not(feature = "psram"))]
+use static_cell::ConstStaticCell;
+
+use crate::{
+    Backlight,
+    DisplayResources,
+    Framebuffer,
+};
+
+pub(crate) const WIDTH: u32 = 320;
+pub(crate) const HEIGHT: u32 = 170;
+
+/// How long the panel needs after entering or leaving sleep before it takes
+/// the opposite command, and to settle after waking.
+const SLEEP_SETTLE: Duration = Duration::from_millis(120);
+
+type SpiDevice<'a> = ExclusiveDevice<SpiDmaBus<'a, Async>, Output<'a>, embassy_time::Delay>;
+
+type Panel<'a> = mipidsi::Display<DisplayInterface<'a>, mipidsi::models::ST7789, Output<'a>>;
+
+/// Error returned by display operations.
+pub type Error = DeviceError<esp_hal::spi::Error, Infallible>;
 
-use crate::DisplayResources;
+/// SPI link to the panel.
+///
+/// Works like `mipidsi::interface::SpiInterface`, plus an async pixel path
+/// that lets the executor run other tasks while DMA pushes a frame.
+pub struct DisplayInterface<'a> {
+    spi: SpiDevice<'a>,
+    dc: Output<'a>,
+    buffer: &'static mut [u8],
+}
+
+impl DisplayInterface<'_> {
+    /// Stream pixels after a memory write command, awaiting each DMA chunk.
+    async fn send_pixels_async(
+        &mut self,
+        pixels: impl IntoIterator<Item = Rgb565>,
+    ) -> Result<(), Error> {
+        let mut pixels = pixels.into_iter();
+        loop {
+            let mut len = 0;
+            for (chunk, pixel) in self.buffer.chunks_exact_mut(2).zip(&mut pixels) {
+                chunk.copy_from_slice(&pixel.to_be_bytes());
+                len += 2;
+            }
+            if len == 0 {
+                return Ok(());
+            }
+            embedded_hal_async::spi::SpiDevice::write(&mut self.spi, &self.buffer[..len]).await?;
+        }
+    }
+}
+
+impl Interface for DisplayInterface<'_> {
+    type Word = u8;
+    type Error = Error;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.dc.set_low();
+        self.spi.write(&[command])?;
+        self.dc.set_high();
+        self.spi.write(args)
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let mut pixels = pixels.into_iter();
+        loop {
+            let mut len = 0;
+            for (chunk, pixel) in self.buffer.chunks_exact_mut(N).zip(&mut pixels) {
+                chunk.copy_from_slice(&pixel);
+                len += N;
+            }
+            if len == 0 {
+                return Ok(());
+            }
+            self.spi.write(&self.buffer[..len])?;
+        }
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        let per_chunk = (self.buffer.len() / N).min(count as usize);
+        for chunk in self.buffer[..per_chunk * N].chunks_exact_mut(N) {
+            chunk.copy_from_slice(&pixel);
+        }
+        let mut left = count as usize;
+        while left > 0 {
+            let n = left.min(per_chunk);
+            self.spi.write(&self.buffer[..n * N])?;
+            left -= n;
+        }
+        Ok(())
+    }
+}
 
-type SpiInterface<'a> = mipidsi::interface::SpiInterface<
-    'a,
-    ExclusiveDevice<esp_hal::spi::master::SpiDmaBus<'a, Async>, Output<'a>, esp_hal::delay::Delay>,
-    Output<'a>,
->;
+/// Whole-screen buffer for [`Display::frame`], only linked in when used.
+#[cfg(not(feature = "psram"))]
+static FRAMEBUFFER: ConstStaticCell<[Rgb565; (WIDTH * HEIGHT) as usize]> =
+    ConstStaticCell::new([Rgb565::BLACK; (WIDTH * HEIGHT) as usize]);
+
+/// Pixels for the whole-screen framebuffer, in internal RAM.
+#[cfg(not(feature = "psram"))]
+fn framebuffer_pixels() -> &'static mut [Rgb565] {
+    FRAMEBUFFER.take()
+}
+
+/// Pixels for the whole-screen framebuffer, allocated once from the PSRAM
+/// heap region and never freed.
+#[cfg(feature = "psram")]
+fn framebuffer_pixels() -> &'static mut [Rgb565] {
+    let len = (WIDTH * HEIGHT) as usize;
+    let layout = Layout::array::<Rgb565>(len).unwrap();
+    // SAFETY: the layout is not zero-sized.
+    let pixels = unsafe { esp_alloc::HEAP.alloc_caps(MemoryCapability::External.into(), layout) }
+        .cast::<Rgb565>();
+    assert!(
+        !pixels.is_null(),
+        "no PSRAM for the framebuffer, add it to the heap with esp_alloc::psram_allocator!"
+    );
+    // SAFETY: `pixels` is a fresh, suitably aligned allocation for `len`
+    // pixels, each written before the slice is made.
+    unsafe {
+        for i in 0..len {
+            pixels.add(i).write(Rgb565::BLACK);
+        }
+        core::slice::from_raw_parts_mut(pixels, len)
+    }
+}
+
+// ── Dirty tiles ─────────────────────────────────────────────────────────────
+
+const TILE: u32 = 16;
+/// Tiles along the long side of the screen, which is vertical in portrait.
+const TILES_LONG: usize = WIDTH.div_ceil(TILE) as usize;
+const TILES_SHORT: usize = HEIGHT.div_ceil(TILE) as usize;
+
+/// Per tile row, a bitmask of tiles.
+type TileMasks = [u32; TILES_LONG];
+
+/// Hashes of what was last pushed to each 16×16 tile of the panel, row by
+/// row in the current orientation.
+///
+/// Zero means "unknown"; real tile hashes are practically never zero.
+struct TileHashes([u32; TILES_LONG * TILES_SHORT]);
+
+impl TileHashes {
+    const UNKNOWN: Self = Self([0; TILES_LONG * TILES_SHORT]);
+
+    /// Re-hash the tiles overlapping `area` and return, per tile row, a
+    /// bitmask of the tiles whose content differs from the panel.
+    fn update(&mut self, framebuffer: &Framebuffer<'_>, area: &Rectangle) -> TileMasks {
+        let mut changed = [0; TILES_LONG];
+        let Some(end) = area.bottom_right() else {
+            return changed;
+        };
+        let size = framebuffer.size();
+        let cols = size.width.div_ceil(TILE) as usize;
+        let (start, end) = (area.top_left / TILE as i32, end / TILE as i32);
+        let rows = self.0.chunks_mut(cols).zip(&mut changed).enumerate();
+        for (ty, (hashes, mask)) in rows.take(end.y as usize + 1).skip(start.y as usize) {
+            let cols = hashes.iter_mut().enumerate();
+            for (tx, stored) in cols.take(end.x as usize + 1).skip(start.x as usize) {
+                let hash = tile_hash(framebuffer.pixels(), size, tx, ty);
+                if *stored != hash {
+                    *stored = hash;
+                    *mask |= 1 << tx;
+                }
+            }
+        }
+        changed
+    }
+}
+
+/// FNV-1a over the raw pixels of one tile of a `size` buffer.
+fn tile_hash(pixels: &[Rgb565], size: Size, tx: usize, ty: usize) -> u32 {
+    let (width, height) = (size.width as usize, size.height as usize);
+    let (x, y) = (tx * TILE as usize, ty * TILE as usize);
+    let w = (TILE as usize).min(width - x);
+    let rows = (TILE as usize).min(height - y);
+    let mut hash = 0x811c_9dc5_u32;
+    for row in pixels[y * width..].chunks(width).take(rows) {
+        for px in &row[x..x + w] {
+            hash = (hash ^ u32::from(px.into_storage())).wrapping_mul(0x0100_0193);
+        }
+    }
+    hash
+}
+
+/// Areas of a `size` screen covering each horizontal run of changed tiles.
+fn changed_areas(changed: TileMasks, size: Size) -> impl Iterator<Item = Rectangle> {
+    let screen = Rectangle::new(Point::zero(), size);
+    changed
+        .into_iter()
+        .enumerate()
+        .flat_map(move |(ty, mut mask)| {
+            core::iter::from_fn(move || {
+                if mask == 0 {
+                    return None;
+                }
+                let start = mask.trailing_zeros();
+                let len = (mask >> start).trailing_ones();
+                mask &= !(((1 << len) - 1) << start);
+                let top_left = Point::new((start * TILE) as i32, ty as i32 * TILE as i32);
+                Some(Rectangle::new(top_left, Size::new(len * TILE, TILE)).intersection(&screen))
+            })
+        })
+}
+
+// ── Orientation ─────────────────────────────────────────────────────────────
+
+/// ST7789 controller RAM size, in the panel's native portrait layout.
+const PANEL_RAM: (u16, u16) = (240, 320);
+/// Where the visible 170×320 area sits in controller RAM.
+const PANEL_OFFSET: (u16, u16) = (35, 0);
+
+/// Controller orientation for `orientation` relative to the badge's
+/// landscape default.
+///
+/// The panel is mounted in portrait, so landscape is a 90° rotation in
+/// controller terms. Mirroring is re-expressed so that `flip_horizontal`
+/// still flips the screen the way the user sees it.
+const fn panel_orientation(orientation: Orientation) -> Orientation {
+    let rotation = Rotation::Deg90.rotate(orientation.rotation);
+    Orientation {
+        rotation: if orientation.mirrored {
+            rotation.rotate(Rotation::Deg180)
+        } else {
+            rotation
+        },
+        mirrored: orientation.mirrored,
+    }
+}
+
+/// Whether a panel orientation reverses the controller's rows and columns,
+/// the same way `mipidsi` works it out.
+const fn reversed(panel: Orientation) -> (bool, bool) {
+    let (rows, columns) = match panel.rotation {
+        Rotation::Deg0 => (false, false),
+        Rotation::Deg90 => (false, true),
+        Rotation::Deg180 => (true, true),
+        Rotation::Deg270 => (true, false),
+    };
+    (rows, columns ^ panel.mirrored)
+}
+
+/// Offset of the visible area in controller RAM for a panel orientation.
+const fn window_offset(panel: Orientation) -> (u16, u16) {
+    let (reverse_rows, reverse_columns) = reversed(panel);
+    let (mut x, mut y) = PANEL_OFFSET;
+    if reverse_columns {
+        x = PANEL_RAM.0 - (HEIGHT as u16 + x);
+    }
+    if reverse_rows {
+        y = PANEL_RAM.1 - (WIDTH as u16 + y);
+    }
+    if panel.rotation.is_vertical() {
+        (y, x)
+    } else {
+        (x, y)
+    }
+}
+
+/// `[start, end]` as the big-endian argument of a column or row address
+/// command.
+const fn address_range(start: u16, end: u16) -> [u8; 4] {
+    let ([s0, s1], [e0, e1]) = (start.to_be_bytes(), end.to_be_bytes());
+    [s0, s1, e0, e1]
+}
+
+// ── Display ─────────────────────────────────────────────────────────────────
 
 /// The badge's ST7789 display, ready to draw on with `embedded-graphics`.
-pub type Display<'a> = mipidsi::Display<SpiInterface<'a>, mipidsi::models::ST7789, Output<'a>>;
+///
+/// Drawing goes straight to the panel. For flicker-free updates, draw inside
+/// [`frame`](Self::frame) instead. The underlying `mipidsi` driver is
+/// reachable through `Deref` for panel-specific commands; its own drawing
+/// methods assume the default landscape orientation.
+pub struct Display<'a> {
+    panel: Panel<'a>,
+    orientation: Orientation,
+    offset: (u16, u16),
+    /// Fixed controller rows above the scroll area, and its height.
+    scroll_area: (u16, u16),
+    framebuffer: Option<Framebuffer<'static>>,
+    tiles: TileHashes,
+    asleep: bool,
+    /// When the panel last entered or left sleep, which it needs time to
+    /// settle from.
+    sleep_changed: Instant,
+}
+
+impl Display<'_> {
+    /// Rotate or mirror everything drawn from now on.
+    ///
+    /// `orientation` is relative to the default 320×170 landscape, with
+    /// rotations clockwise: `Rotation::Deg90` and `Rotation::Deg270` give a
+    /// 170×320 portrait screen. The panel does the rotation itself, so
+    /// drawing is just as fast in every orientation.
+    ///
+    /// The screen and the framebuffer are cleared to black, and scrolling is
+    /// reset.
+    ///
+    /// ```rust,ignore
+    /// display.set_orientation(Orientation::new().rotate(Rotation::Deg90))?;
+    /// assert_eq!(display.size(), Size::new(170, 320));
+    /// ```
+    pub fn set_orientation(&mut self, orientation: Orientation) -> Result<(), Error> {
+        let panel = panel_orientation(orientation);
+        self.panel.set_orientation(panel)?;
+        self.orientation = orientation;
+        self.offset = window_offset(panel);
+
+        let size = self.size();
+        if let Some(framebuffer) = self.framebuffer.take() {
+            let pixels = framebuffer.into_pixels();
+            pixels.fill(Rgb565::BLACK);
+            self.framebuffer = Some(Framebuffer::new(pixels, size));
+        }
+        self.tiles = TileHashes::UNKNOWN;
+        self.set_scroll_area(0, 0)?;
+        self.clear(Rgb565::BLACK)
+    }
+
+    /// The current orientation, see [`set_orientation`](Self::set_orientation).
+    pub const fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Select a region of the screen to stream pixels into with
+    /// [`write_pixels`](Self::write_pixels).
+    ///
+    /// This is the fastest way to put a sprite, an image row or a HUD panel
+    /// on screen: the window is set once and then every pixel is two bytes
+    /// of pixel data, with no per-pixel addressing. Like other direct
+    /// drawing, it bypasses the framebuffer.
+    ///
+    /// ```rust,ignore
+    /// display.set_window(&Rectangle::new(Point::new(10, 10), Size::new(16, 16)))?;
+    /// display.write_pixels(sprite.iter().copied())?;
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `area` is empty or not entirely on screen.
+    pub fn set_window(&mut self, area: &Rectangle) -> Result<(), Error> {
+        assert!(
+            !area.is_zero_sized() && area.intersection(&self.bounding_box()) == *area,
+            "window must be on screen"
+        );
+        start_write(&mut self.panel, self.offset, area).map(drop)
+    }
+
+    /// Stream pixels into the window chosen with
+    /// [`set_window`](Self::set_window), row by row from its top left.
+    ///
+    /// Several calls continue where the previous one stopped, so a window
+    /// can be filled piecewise. Past the end of the window, the panel wraps
+    /// around to its top left.
+    pub fn write_pixels(&mut self, pixels: impl IntoIterator<Item = Rgb565>) -> Result<(), Error> {
+        // SAFETY: only pixel data for the memory write started by
+        // `set_window` (or an earlier draw) is sent.
+        let interface = unsafe { self.panel.dcs() };
+        interface.send_pixels(pixels.into_iter().map(|c| c.to_be_bytes()))
+    }
+
+    /// Set up hardware scrolling along the screen's long axis: horizontal in
+    /// landscape, vertical in portrait.
+    ///
+    /// Everything but `fixed_start` pixels at the left or top and `fixed_end`
+    /// pixels at the right or bottom can then be moved with
+    /// [`scroll_to`](Self::scroll_to), without redrawing anything. The
+    /// scroll position is reset to 0.
+    ///
+    /// # Panics
+    ///
+    /// If the fixed areas leave nothing to scroll.
+    pub fn set_scroll_area(&mut self, fixed_start: u16, fixed_end: u16) -> Result<(), Error> {
+        let scrolled = (WIDTH as u16)
+            .checked_sub(fixed_start.saturating_add(fixed_end))
+            .filter(|&height| height > 0)
+            .expect("scroll area must not be empty");
+        let (top, bottom) = if self.rows_reversed() {
+            (fixed_end, fixed_start)
+        } else {
+            (fixed_start, fixed_end)
+        };
+        self.panel.set_vertical_scroll_region(top, bottom)?;
+        self.scroll_area = (top, scrolled);
+        self.scroll_to(0)
+    }
+
+    /// Move the content of the scroll area `offset` pixels towards its start.
+    ///
+    /// What scrolls out at the start comes back in at the end. Drawing is
+    /// not affected: coordinates still address the unscrolled content, so a
+    /// terminal scrolling up by one line draws the new line into the rows
+    /// that just wrapped around to the bottom.
+    ///
+    /// ```rust,ignore
+    /// display.set_orientation(Orientation::new().rotate(Rotation::Deg90))?;
+    /// display.set_scroll_area(20, 0)?; // keep a 20 px title bar
+    /// for offset in (0..300).cycle() {
+    ///     display.scroll_to(offset)?;
+    ///     Timer::after_millis(20).await;
+    /// }
+    /// ```
+    pub fn scroll_to(&mut self, offset: u16) -> Result<(), Error> {
+        let (top, height) = self.scroll_area;
+        let offset = offset % height;
+        let offset = if self.rows_reversed() {
+            (height - offset) % height
+        } else {
+            offset
+        };
+        self.panel.set_vertical_scroll_offset(top + offset)
+    }
+
+    /// Turn the backlight off and put the panel to sleep.
+    ///
+    /// A sleeping panel draws almost no power but keeps its memory, so
+    /// [`wake`](Self::wake) brings back what was on screen. Drawing while
+    /// asleep still updates the memory and shows on waking.
+    pub async fn sleep(&mut self, backlight: &mut Backlight) -> Result<(), Error> {
+        if self.asleep {
+            return Ok(());
+        }
+        Timer::at(self.sleep_changed + SLEEP_SETTLE).await;
+        backlight.off();
+        // SAFETY: display off and sleep in leave the address window,
+        // orientation and scrolling alone.
+        let interface = unsafe { self.panel.dcs() };
+        interface.send_command(0x28, &[])?; // DISPOFF
+        interface.send_command(0x10, &[])?; // SLPIN
+        self.asleep = true;
+        self.sleep_changed = Instant::now();
+        Ok(())
+    }
+
+    /// Wake the panel from [`sleep`](Self::sleep) and turn the backlight
+    /// back on at its last brightness.
+    ///
+    /// Takes about 120 ms, while the panel's supply settles, so that the
+    /// backlight only comes on once the picture is stable.
+    pub async fn wake(&mut self, backlight: &mut Backlight) -> Result<(), Error> {
+        if !self.asleep {
+            return Ok(());
+        }
+        Timer::at(self.sleep_changed + SLEEP_SETTLE).await;
+        // SAFETY: as in `sleep`.
+        let interface = unsafe { self.panel.dcs() };
+        interface.send_command(0x11, &[])?; // SLPOUT
+        self.asleep = false;
+        self.sleep_changed = Instant::now();
+        Timer::after(SLEEP_SETTLE).await;
+        interface.send_command(0x29, &[])?; // DISPON
+        backlight.on();
+        Ok(())
+    }
+
+    /// Whether the panel is asleep, see [`sleep`](Self::sleep).
+    pub const fn is_asleep(&self) -> bool {
+        self.asleep
+    }
+
+    /// Send a horizontal run of pixels starting at `start`.
+    fn write_run(&mut self, start: Point, run: &[[u8; 2]]) -> Result<(), Error> {
+        if run.is_empty() {
+            return Ok(());
+        }
+        let area = Rectangle::new(start, Size::new(run.len() as u32, 1));
+        start_write(&mut self.panel, self.offset, &area)?.send_pixels(run.iter().copied())
+    }
+
+    /// Whether the screen's long axis runs against the controller's rows.
+    const fn rows_reversed(&self) -> bool {
+        reversed(panel_orientation(self.orientation)).0
+    }
+
+    /// Draw a frame off-screen, then push only what changed to the panel.
+    ///
+    /// Uses the same framebuffer as [`framebuffer`](Self::framebuffer), so
+    /// see there for how changes are found.
+    ///
+    /// ```rust,ignore
+    /// display.frame(|fb| {
+    ///     fb.clear(Rgb565::BLACK).unwrap();
+    ///     ball.draw(fb).unwrap();
+    /// })?;
+    /// ```
+    pub fn frame<R>(
+        &mut self,
+        draw: impl FnOnce(&mut Framebuffer<'static>) -> R,
+    ) -> Result<R, Error> {
+        let result = draw(self.framebuffer());
+        self.flush()?;
+        Ok(result)
+    }
+
+    /// The off-screen framebuffer, for use with [`flush`](Self::flush) and
+    /// [`flush_async`](Self::flush_async).
+    ///
+    /// The first call allocates a screen-sized framebuffer (about 106 KiB of
+    /// RAM). With the `psram` feature it lives in PSRAM instead, which must
+    /// first be added to the heap with `esp_alloc::psram_allocator!`.
+    /// It keeps its contents between frames and must mirror the panel, so
+    /// once you use it, do all of your drawing through it.
+    ///
+    /// There is no need to erase and redraw moving objects by hand: clear and
+    /// redraw the whole scene every frame. On flush, the display hashes each
+    /// 16×16 tile that was drawn to and only sends the tiles whose content
+    /// actually differs from what the panel shows.
+    pub fn framebuffer(&mut self) -> &mut Framebuffer<'static> {
+        let size = self.size();
+        self.framebuffer
+            .get_or_insert_with(|| Framebuffer::new(framebuffer_pixels(), size))
+    }
+
+    /// Forget what the panel shows, so the next flush sends the whole
+    /// framebuffer. Call this after drawing around the framebuffer, e.g.
+    /// directly to the panel, to put the last frame back.
+    pub fn invalidate(&mut self) {
+        self.tiles = TileHashes::UNKNOWN;
+        if let Some(framebuffer) = &mut self.framebuffer {
+            framebuffer.mark_dirty(&framebuffer.bounding_box());
+        }
+    }
+
+    /// Draw a test card for checking a freshly assembled panel: colour bars
+    /// across the top, red, green, blue and grey ramps below them, and a
+    /// 10-pixel grid at the bottom, all inside a red outline on the
+    /// outermost pixels.
+    ///
+    /// Dead rows or columns break the grid lines, a wrong offset cuts off
+    /// part of the outline, and a missing colour bit shows up as steps or
+    /// gaps in the ramps. Draws straight to the panel in the current
+    /// orientation.
+    pub fn test_pattern(&mut self) -> Result<(), Error> {
+        const BARS: [Rgb565; 8] = [
+            Rgb565::WHITE,
+            Rgb565::YELLOW,
+            Rgb565::CYAN,
+            Rgb565::GREEN,
+            Rgb565::MAGENTA,
+            Rgb565::RED,
+            Rgb565::BLUE,
+            Rgb565::BLACK,
+        ];
+        const GRID: u32 = 10;
+
+        let Size { width, height } = self.size();
+        let bars_height = height * 2 / 5;
+        let ramp_height = height / 10;
+        for (i, color) in BARS.into_iter().enumerate() {
+            let (left, right) = (width * i as u32 / 8, width * (i as u32 + 1) / 8);
+            let bar = Rectangle::new(
+                Point::new(left as i32, 0),
+                Size::new(right - left, bars_height),
+            );
+            self.fill_solid(&bar, color)?;
+        }
+
+        let ramps: [fn(u8) -> Rgb565; 4] = [
+            |level| Rgb565::new(level >> 3, 0, 0),
+            |level| Rgb565::new(0, level >> 2, 0),
+            |level| Rgb565::new(0, 0, level >> 3),
+            |level| Rgb565::new(level >> 3, level >> 2, level >> 3),
+        ];
+        for (i, ramp) in ramps.into_iter().enumerate() {
+            let area = Rectangle::new(
+                Point::new(0, (bars_height + ramp_height * i as u32) as i32),
+                Size::new(width, ramp_height),
+            );
+            let row = (0..width).map(|x| ramp((x * 255 / (width - 1)) as u8));
+            self.fill_contiguous(&area, (0..ramp_height).flat_map(|_| row.clone()))?;
+        }
+
+        let top = bars_height + ramp_height * 4;
+        let grid = Rectangle::new(Point::new(0, top as i32), Size::new(width, height - top));
+        let pixels = grid.points().map(|p| {
+            if (p.x as u32).is_multiple_of(GRID) || (p.y as u32).is_multiple_of(GRID) {
+                Rgb565::WHITE
+            } else {
+                Rgb565::BLACK
+            }
+        });
+        self.fill_contiguous(&grid, pixels)?;
+
+        let outline = PrimitiveStyleBuilder::new()
+            .stroke_color(Rgb565::RED)
+            .stroke_width(1)
+            .stroke_alignment(StrokeAlignment::Inside)
+            .build();
+        self.bounding_box().into_styled(outline).draw(self)?;
+        self.invalidate();
+        Ok(())
+    }
+
+    /// Push the changed tiles of the framebuffer to the panel, blocking until
+    /// the transfer is done.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        let Some(framebuffer) = &mut self.framebuffer else {
+            return Ok(());
+        };
+        let Some(dirty) = framebuffer.take_dirty() else {
+            return Ok(());
+        };
+        let changed = self.tiles.update(framebuffer, &dirty);
+        for area in changed_areas(changed, framebuffer.size()) {
+            let interface = start_write(&mut self.panel, self.offset, &area)?;
+            interface.send_pixels(framebuffer.pixels_in(&area).map(|c| c.to_be_bytes()))?;
+        }
+        Ok(())
+    }
+
+    /// Push the changed tiles of the framebuffer to the panel over DMA,
+    /// yielding to other tasks while each chunk is in flight.
+    ///
+    /// ```rust,ignore
+    /// loop {
+    ///     draw_game(display.framebuffer());
+    ///     display.flush_async().await?;
+    /// }
+    /// ```
+    pub async fn flush_async(&mut self) -> Result<(), Error> {
+        let Some(framebuffer) = &mut self.framebuffer else {
+            return Ok(());
+        };
+        let Some(dirty) = framebuffer.take_dirty() else {
+            return Ok(());
+        };
+        let changed = self.tiles.update(framebuffer, &dirty);
+        for area in changed_areas(changed, framebuffer.size()) {
+            let interface = start_write(&mut self.panel, self.offset, &area)?;
+            interface
+                .send_pixels_async(framebuffer.pixels_in(&area))
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Set the panel's memory window to `area` (in screen coordinates, on
+/// screen) and start a memory write, returning the interface to send the
+/// pixels through.
+fn start_write<'p, 'a>(
+    panel: &'p mut Panel<'a>,
+    offset: (u16, u16),
+    area: &Rectangle,
+) -> Result<&'p mut DisplayInterface<'a>, Error> {
+    let end = area.bottom_right().unwrap_or(area.top_left);
+    let (sx, sy) = (
+        area.top_left.x as u16 + offset.0,
+        area.top_left.y as u16 + offset.1,
+    );
+    let (ex, ey) = (end.x as u16 + offset.0, end.y as u16 + offset.1);
+    // SAFETY: the window and memory write commands leave no state behind
+    // that `mipidsi` relies on; it sets its own window before every write.
+    let interface = unsafe { panel.dcs() };
+    interface.send_command(0x2a, &address_range(sx, ex))?;
+    interface.send_command(0x2b, &address_range(sy, ey))?;
+    interface.send_command(0x2c, &[])?;
+    Ok(interface)
+}
+
+impl<'a> Deref for Display<'a> {
+    type Target = Panel<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.panel
+    }
+}
+
+impl DerefMut for Display<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.panel
+    }
+}
+
+impl OriginDimensions for Display<'_> {
+    fn size(&self) -> Size {
+        if self.orientation.rotation.is_horizontal() {
+            Size::new(WIDTH, HEIGHT)
+        } else {
+            Size::new(HEIGHT, WIDTH)
+        }
+    }
+}
+
+impl DrawTarget for Display<'_> {
+    type Color = Rgb565;
+    type Error = Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounds = self.bounding_box();
+        // Pixels that follow each other left to right along a row, as from
+        // images and text, go out as one window write.
+        let mut run = [[0; 2]; WIDTH as usize];
+        let mut start = Point::zero();
+        let mut len = 0;
+        for Pixel(point, color) in pixels {
+            if !bounds.contains(point) {
+                continue;
+            }
+            if point != start + Point::new(len as i32, 0) || len == run.len() {
+                self.write_run(start, &run[..len])?;
+                start = point;
+                len = 0;
+            }
+            run[len] = color.to_be_bytes();
+            len += 1;
+        }
+        self.write_run(start, &run[..len])
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let clipped = area.intersection(&self.bounding_box());
+        if clipped.is_zero_sized() {
+            return Ok(());
+        }
+        let interface = start_write(&mut self.panel, self.offset, &clipped)?;
+        if clipped == *area {
+            interface.send_pixels(colors.into_iter().map(|c| c.to_be_bytes()))
+        } else {
+            let colors = area
+                .points()
+                .zip(colors)
+                .filter(|(p, _)| clipped.contains(*p));
+            interface.send_pixels(colors.map(|(_, c)| c.to_be_bytes()))
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+        start_write(&mut self.panel, self.offset, &area)?
+            .send_repeated_pixel(color.to_be_bytes(), area.size.width * area.size.height)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill_solid(&self.bounding_box(), color)
+    }
+}
 
 impl<'a> From<DisplayResources<'a>> for Display<'a> {
     fn from(res: DisplayResources<'a>) -> Self {
@@ -53,20 +838,34 @@ impl<'a> From<DisplayResources<'a>> for Display<'a> {
         .into_async();
 
         let cs = Output::new(res.cs, Level::High, OutputConfig::default());
-        let spi_device = ExclusiveDevice::new(spi, cs, delay).unwrap();
+        // The device only needs a delay for `DelayNs` operations, which we never issue.
+        let spi_device = ExclusiveDevice::new(spi, cs, embassy_time::Delay).unwrap();
 
         let buffer = crate::mk_static!([u8; 32000], [0_u8; 32000]);
-        let di = mipidsi::interface::SpiInterface::new(spi_device, dc, buffer);
+        let di = DisplayInterface {
+            spi: spi_device,
+            dc,
+            buffer,
+        };
 
-        mipidsi::Builder::new(mipidsi::models::ST7789, di)
+        let panel = mipidsi::Builder::new(mipidsi::models::ST7789, di)
             .reset_pin(rst)
             .display_size(170, 320)
             .invert_colors(mipidsi::options::ColorInversion::Inverted)
-            .orientation(
-                mipidsi::options::Orientation::new().rotate(mipidsi::options::Rotation::Deg90),
-            )
+            .orientation(panel_orientation(Orientation::new()))
             .display_offset(35, 0)
             .init(&mut delay)
-            .unwrap()
+            .unwrap();
+
+        Self {
+            panel,
+            orientation: Orientation::new(),
+            offset: window_offset(panel_orientation(Orientation::new())),
+            scroll_area: (0, WIDTH as u16),
+            framebuffer: None,
+            tiles: TileHashes::UNKNOWN,
+            asleep: false,
+            sleep_changed: Instant::now(),
+        }
     }
 }
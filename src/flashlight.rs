@@ -0,0 +1,94 @@
+//! The flashlight, a system action that lights every LED white.
+//!
+//! Claim its combo in the shared registry at boot and spawn
+//! [`flashlight_task`], so holding Start for
+//! [`COMBO_HOLD`](crate::buttons::COMBO_HOLD) toggles the light in any app
+//! that reads input through
+//! [`Buttons::wait_for_press`](crate::Buttons::wait_for_press):
+//!
+//! ```rust,ignore
+//! let combo = buttons::claim_combo(flashlight::ACTION, flashlight::CHORD)?;
+//! spawner.must_spawn(flashlight::flashlight_task(combo));
+//! ```
+//!
+//! The light is a [`Notification`] over every LED, so it shows over the
+//! app's colours whenever the LEDs update. It goes through the same
+//! limiter as anything else on the LEDs: the global brightness and the
+//! battery cap of [`Leds`](crate::Leds) keep ten WS2812s at full white
+//! from pulling more than the batteries should give.
+//!
+//! A system task can't reach the app's backlight, so apps that want the
+//! screen lit too check [`is_lit`] or wait with [`wait_for_toggle`].
+
+use core::sync::atomic::{
+    AtomicBool,
+    Ordering,
+};
+
+use defmt::info;
+use embassy_sync::{
+    blocking_mutex::raw::CriticalSectionRawMutex,
+    signal::Signal,
+};
+use embassy_time::Duration;
+use palette::Srgb;
+
+use crate::{
+    Button,
+    Chord,
+    buttons::SystemCombo,
+    notification::{
+        self,
+        Notification,
+        Priority,
+    },
+};
+
+/// Name of the flashlight in the combo registry.
+pub const ACTION: &str = "flashlight";
+
+/// The chord the flashlight claims by default.
+pub const CHORD: Chord = Chord::of(&[Button::Start]);
+
+/// Full white on every LED until turned off, over anything else.
+const LIGHT: Notification =
+    Notification::new(Srgb::new(255, 255, 255), Duration::MAX).with_priority(Priority::Critical);
+
+static LIT: AtomicBool = AtomicBool::new(false);
+
+/// Raised with the new state when the light is turned on or off.
+static TOGGLED: Signal<CriticalSectionRawMutex, bool> = Signal::new();
+
+/// `true` while the flashlight is on.
+pub fn is_lit() -> bool {
+    LIT.load(Ordering::Relaxed)
+}
+
+/// Wait for the flashlight to be turned on or off, and return whether
+/// it's on. For one task, such as the app that lights the screen to match.
+pub async fn wait_for_toggle() -> bool {
+    TOGGLED.wait().await
+}
+
+/// Turn the flashlight on or off.
+pub fn set_lit(lit: bool) {
+    if LIT.swap(lit, Ordering::Relaxed) == lit {
+        return;
+    }
+    if lit {
+        notification::post(LIGHT);
+    } else {
+        notification::dismiss(LIGHT);
+    }
+    TOGGLED.signal(lit);
+    info!("Flashlight {}", if lit { "on" } else { "off" });
+}
+
+/// Toggle the flashlight whenever `combo` is held.
+#[embassy_executor::task]
+pub async fn flashlight_task(combo: SystemCombo) -> ! {
+    loop {
+        combo.wait().await;
+        set_lit(!is_lit());
+    }
+}
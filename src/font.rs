@@ -0,0 +1,37 @@
+//! Extra bitmap fonts.
+//!
+//! The `embedded-graphics` mono fonts top out at 10×20, which is hard to read
+//! on the 320×170 panel at arm's length. [`FONT_20X40`] is that same face at
+//! twice the size, for scores and titles.
+//!
+//! Convert your own BDF fonts with [`badge_font!`](crate::badge_font). The
+//! result is an ordinary `MonoFont`, stored in flash, so it works with
+//! `MonoTextStyle`, `Text` and [`TextBox`](crate::TextBox):
+//!
+//! ```rust,ignore
+//! const TERMINUS: MonoFont = badge_font!("assets/ter-u16b.bdf");
+//!
+//! let style = MonoTextStyle::new(&font::FONT_20X40, Rgb565::WHITE);
+//! Text::new("1234", Point::new(10, 50), style).draw(display)?;
+//! ```
+
+use embedded_graphics::mono_font::MonoFont;
+
+use crate::badge_font;
+
+/// The public domain X11 `10x20` font, doubled to 20×40. Latin-1.
+pub const FONT_20X40: MonoFont = badge_font!("assets/fonts/10x20.bdf", scale = 2);
+
+/// Paths used by the code [`badge_font!`](crate::badge_font) expands to.
+#[doc(hidden)]
+pub mod __macro_support {
+    pub use embedded_graphics::{
+        geometry::Size,
+        image::ImageRaw,
+        mono_font::{
+            DecorationDimensions,
+            MonoFont,
+            mapping::StrGlyphMapping,
+        },
+    };
+}
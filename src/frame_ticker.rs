@@ -0,0 +1,73 @@
+//! Fixed-rate frame pacing for game loops.
+
+use embassy_time::{
+    Duration,
+    Instant,
+    Timer,
+};
+
+/// Paces a game loop at a steady frame rate.
+///
+/// `Timer::after(tick)` at the end of a frame makes every frame last the
+/// tick plus however long the frame took, so the game slows down when
+/// there is a lot to draw. [`next`](Self::next) instead waits until the
+/// next frame is due, counting from when the previous one was due:
+///
+/// ```rust,ignore
+/// let mut ticker = FrameTicker::new(50);
+/// loop {
+///     game.tick();
+///     draw(display, &game);
+///     ticker.next().await;
+/// }
+/// ```
+///
+/// A frame that runs a little long is made up by starting the next one
+/// early. After falling a whole frame or more behind, for example after a
+/// pause, the ticker drops the frames it missed rather than rushing through
+/// them.
+pub struct FrameTicker {
+    period: Duration,
+    /// When the next frame is due.
+    deadline: Instant,
+    missed: u32,
+}
+
+impl FrameTicker {
+    /// A ticker for `fps` frames per second, starting now.
+    pub fn new(fps: u32) -> Self {
+        let period = Duration::from_hz(u64::from(fps.max(1)));
+        Self {
+            period,
+            deadline: Instant::now() + period,
+            missed: 0,
+        }
+    }
+
+    /// Time between frames, the budget for each one.
+    pub const fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Number of frames dropped so far because the loop fell behind.
+    pub const fn missed(&self) -> u32 {
+        self.missed
+    }
+
+    /// Start counting frames from now, such as after a pause.
+    pub fn reset(&mut self) {
+        self.deadline = Instant::now() + self.period;
+    }
+
+    /// Wait until the next frame is due.
+    pub async fn next(&mut self) {
+        let now = Instant::now();
+        if now >= self.deadline + self.period {
+            let behind = (now - self.deadline).as_ticks() / self.period.as_ticks();
+            self.missed = self.missed.saturating_add(behind as u32);
+            self.deadline = now;
+        }
+        Timer::at(self.deadline).await;
+        self.deadline += self.period;
+    }
+}
@@ -0,0 +1,222 @@
+//! In-RAM RGB565 framebuffer with dirty-region tracking.
+
+use core::convert::Infallible;
+
+use embedded_graphics::{
+    Pixel,
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::Rectangle,
+};
+
+/// An RGB565 framebuffer that remembers which part of it was drawn to.
+///
+/// Draw into it with `embedded-graphics` like any other target, then call
+/// [`flush`](Self::flush) to copy the changed region to the real display in a
+/// single window write. The buffer keeps its contents between flushes, so it
+/// always mirrors what is on the panel.
+///
+/// [`Display::frame`](crate::Display::frame) manages one of these for the
+/// whole screen.
+pub struct Framebuffer<'a> {
+    pixels: &'a mut [Rgb565],
+    size: Size,
+    dirty: Option<Rectangle>,
+}
+
+impl<'a> Framebuffer<'a> {
+    /// Wrap `pixels` as a row-major buffer of `size`.
+    ///
+    /// # Panics
+    ///
+    /// If `pixels` is smaller than `size`.
+    pub fn new(pixels: &'a mut [Rgb565], size: Size) -> Self {
+        assert!(pixels.len() >= (size.width * size.height) as usize);
+        Self {
+            pixels,
+            size,
+            dirty: None,
+        }
+    }
+
+    /// The raw pixels, row-major.
+    pub fn pixels(&self) -> &[Rgb565] {
+        self.pixels
+    }
+
+    /// Mutable access to the raw pixels. Marks the whole buffer dirty.
+    pub fn pixels_mut(&mut self) -> &mut [Rgb565] {
+        self.dirty = Some(self.bounding_box());
+        self.pixels
+    }
+
+    /// The region changed since the last flush, if any.
+    pub const fn dirty(&self) -> Option<Rectangle> {
+        self.dirty
+    }
+
+    /// Mark `area` as changed, e.g. after editing [`pixels_mut`](Self::pixels_mut)
+    /// rows by hand or to force a full redraw.
+    pub fn mark_dirty(&mut self, area: &Rectangle) {
+        let area = area.intersection(&self.bounding_box());
+        let Some(bottom_right) = area.bottom_right() else {
+            return;
+        };
+        self.dirty = Some(
+            match self.dirty.and_then(|d| Some((d, d.bottom_right()?))) {
+                Some((dirty, dirty_br)) => Rectangle::with_corners(
+                    dirty.top_left.component_min(area.top_left),
+                    dirty_br.component_max(bottom_right),
+                ),
+                None => area,
+            },
+        );
+    }
+
+    /// Lay `color` over `area` with opacity `alpha`, from 0 (invisible) to
+    /// 255 (opaque), blending with what is already drawn there.
+    ///
+    /// Dims a game behind a pause menu or dialog instead of blanking it:
+    ///
+    /// ```rust,ignore
+    /// fb.draw_blended(&fb.bounding_box(), Rgb565::BLACK, 160);
+    /// ```
+    pub fn draw_blended(&mut self, area: &Rectangle, color: Rgb565, alpha: u8) {
+        let area = area.intersection(&self.bounding_box());
+        let w = area.size.width as usize;
+        for y in area.rows() {
+            let start = self.index(Point::new(area.top_left.x, y));
+            for px in &mut self.pixels[start..start + w] {
+                *px = blend(*px, color, alpha);
+            }
+        }
+        self.mark_dirty(&area);
+    }
+
+    /// Copy the dirty region to `target` and mark the buffer clean.
+    pub fn flush<D>(&mut self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let Some(area) = self.take_dirty() else {
+            return Ok(());
+        };
+        target.fill_contiguous(&area, self.pixels_in(&area))
+    }
+
+    /// Give back the pixel storage, e.g. to reuse it at a different size.
+    pub(crate) fn into_pixels(self) -> &'a mut [Rgb565] {
+        self.pixels
+    }
+
+    /// The raw pixels, for changes that shouldn't be sent to the panel.
+    pub(crate) const fn pixels_untracked(&mut self) -> &mut [Rgb565] {
+        self.pixels
+    }
+
+    /// Return the dirty region and mark the buffer clean.
+    pub(crate) const fn take_dirty(&mut self) -> Option<Rectangle> {
+        self.dirty.take()
+    }
+
+    /// The pixels of `area` (which must lie inside the buffer), row by row.
+    pub(crate) fn pixels_in(&self, area: &Rectangle) -> impl Iterator<Item = Rgb565> + '_ {
+        let width = self.size.width as usize;
+        let (x, w) = (area.top_left.x as usize, area.size.width as usize);
+        area.rows().flat_map(move |y| {
+            let start = y as usize * width + x;
+            self.pixels[start..start + w].iter().copied()
+        })
+    }
+
+    fn index(&self, point: Point) -> usize {
+        point.y as usize * self.size.width as usize + point.x as usize
+    }
+}
+
+/// `over` laid on `under` with opacity `alpha`.
+fn blend(under: Rgb565, over: Rgb565, alpha: u8) -> Rgb565 {
+    let alpha = u16::from(alpha);
+    let mix = |under: u8, over: u8| {
+        ((u16::from(under) * (255 - alpha) + u16::from(over) * alpha + 127) / 255) as u8
+    };
+    Rgb565::new(
+        mix(under.r(), over.r()),
+        mix(under.g(), over.g()),
+        mix(under.b(), over.b()),
+    )
+}
+
+impl OriginDimensions for Framebuffer<'_> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl DrawTarget for Framebuffer<'_> {
+    type Color = Rgb565;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounds = self.bounding_box();
+        let (mut min, mut max) = (
+            Point::new(i32::MAX, i32::MAX),
+            Point::new(i32::MIN, i32::MIN),
+        );
+        for Pixel(point, color) in pixels {
+            if bounds.contains(point) {
+                let i = self.index(point);
+                self.pixels[i] = color;
+                min = min.component_min(point);
+                max = max.component_max(point);
+            }
+        }
+        if min.x <= max.x {
+            self.mark_dirty(&Rectangle::with_corners(min, max));
+        }
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let clipped = area.intersection(&self.bounding_box());
+        if clipped != *area {
+            return self.draw_iter(
+                area.points()
+                    .zip(colors)
+                    .map(|(point, color)| Pixel(point, color)),
+            );
+        }
+
+        let mut colors = colors.into_iter();
+        let w = area.size.width as usize;
+        for y in area.rows() {
+            let start = self.index(Point::new(area.top_left.x, y));
+            for (px, color) in self.pixels[start..start + w].iter_mut().zip(&mut colors) {
+                *px = color;
+            }
+        }
+        self.mark_dirty(area);
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        let w = area.size.width as usize;
+        for y in area.rows() {
+            let start = self.index(Point::new(area.top_left.x, y));
+            self.pixels[start..start + w].fill(color);
+        }
+        self.mark_dirty(&area);
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill_solid(&self.bounding_box(), color)
+    }
+}
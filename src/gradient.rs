@@ -0,0 +1,149 @@
+//! Gradient fills with ordered dithering.
+//!
+//! RGB565 has only 32 levels of red and blue, so a smooth sky drawn with it
+//! breaks into visible bands. A [`Gradient`] works out each pixel in 24-bit
+//! colour and dithers it down with a 4×4 Bayer matrix, which trades the
+//! bands for a fine, regular pattern the eye averages out:
+//!
+//! ```rust,ignore
+//! let sky = Rectangle::new(Point::zero(), Size::new(320, 100));
+//! Gradient::new(sky, Rgb888::new(10, 20, 60), Rgb888::new(250, 140, 60)).draw(display)?;
+//! ```
+//!
+//! [`dither`] does the same for a single pixel, for effects that compute
+//! their own colours.
+
+use embedded_graphics::{
+    pixelcolor::{
+        Rgb565,
+        Rgb888,
+    },
+    prelude::*,
+    primitives::Rectangle,
+};
+
+/// 4×4 Bayer matrix, the order in which pixels of a cell round up.
+const BAYER: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Which way a [`Gradient`] runs.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// From the top edge to the bottom edge.
+    Vertical,
+    /// From the left edge to the right edge.
+    Horizontal,
+}
+
+/// A two-colour linear gradient filling a rectangle.
+#[derive(Clone, Copy)]
+pub struct Gradient {
+    area: Rectangle,
+    from: Rgb888,
+    to: Rgb888,
+    direction: Direction,
+    dither: bool,
+}
+
+impl Gradient {
+    /// A dithered gradient from `from` at the top to `to` at the bottom.
+    pub const fn new(area: Rectangle, from: Rgb888, to: Rgb888) -> Self {
+        Self {
+            area,
+            from,
+            to,
+            direction: Direction::Vertical,
+            dither: true,
+        }
+    }
+
+    /// Run the gradient the other way, such as left to right.
+    #[must_use]
+    pub const fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Turn dithering off, rounding each pixel to the nearest colour.
+    #[must_use]
+    pub const fn with_dither(mut self, dither: bool) -> Self {
+        self.dither = dither;
+        self
+    }
+
+    /// The pixels of the gradient, row by row, for `fill_contiguous`.
+    pub fn pixels(&self) -> impl Iterator<Item = Rgb565> + '_ {
+        let Size { width, height } = self.area.size;
+        let steps = match self.direction {
+            Direction::Vertical => height,
+            Direction::Horizontal => width,
+        };
+        let origin = self.area.top_left;
+        (0..height).flat_map(move |y| {
+            (0..width).map(move |x| {
+                let step = match self.direction {
+                    Direction::Vertical => y,
+                    Direction::Horizontal => x,
+                };
+                let color = self.color_at(step, steps);
+                let point = origin + Point::new(x as i32, y as i32);
+                quantize(color, point, self.dither)
+            })
+        })
+    }
+
+    /// Colour `step` of `steps`, as 16 bits per channel.
+    fn color_at(&self, step: u32, steps: u32) -> [u32; 3] {
+        let last = steps.saturating_sub(1).max(1);
+        let mix = |from: u8, to: u8| {
+            let (from, to) = (u32::from(from) * 257, u32::from(to) * 257);
+            (from * (last - step) + to * step) / last
+        };
+        [
+            mix(self.from.r(), self.to.r()),
+            mix(self.from.g(), self.to.g()),
+            mix(self.from.b(), self.to.b()),
+        ]
+    }
+}
+
+impl Drawable for Gradient {
+    type Color = Rgb565;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        target.fill_contiguous(&self.area, self.pixels())
+    }
+}
+
+/// `color` reduced to RGB565 with the dither pattern for `point`.
+///
+/// Pixels are dithered by their screen position, so areas filled separately
+/// line up without seams.
+pub fn dither(color: Rgb888, point: Point) -> Rgb565 {
+    let wide = |c: u8| u32::from(c) * 257;
+    quantize(
+        [wide(color.r()), wide(color.g()), wide(color.b())],
+        point,
+        true,
+    )
+}
+
+/// Reduce 16-bit channels to RGB565, dithered or rounded.
+fn quantize([r, g, b]: [u32; 3], point: Point, dither: bool) -> Rgb565 {
+    // Offset into the interval between two levels at which this pixel
+    // rounds up: half way without dithering, else spread over the cell.
+    let threshold = if dither {
+        let rank = BAYER[(point.y & 3) as usize][(point.x & 3) as usize];
+        (u32::from(rank) * 2 + 1) << 11
+    } else {
+        1 << 15
+    };
+    let level = |value: u32, bits: u32| {
+        let max = (1 << bits) - 1;
+        ((value * max + threshold) >> 16).min(max) as u8
+    };
+    Rgb565::new(level(r, 5), level(g, 6), level(b, 5))
+}
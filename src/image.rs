@@ -0,0 +1,343 @@
+//! Native RGB565 and palettized images, usually produced at compile time by
+//! [`badge_image!`](crate::badge_image) and
+//! [`badge_paletted_image!`](crate::badge_paletted_image).
+//!
+//! Both image types implement `embedded-graphics`' [`ImageDrawable`], so they
+//! are drawn through [`Image`](embedded_graphics::image::Image) like any other
+//! image:
+//!
+//! ```rust,ignore
+//! const LOGO: Rgb565Image = badge_image!("examples/assets/skrolli.png");
+//!
+//! Image::new(&LOGO, Point::new(0, 0)).draw(display)?;
+//! ```
+//!
+//! A [`PalettedImage`] stores 4 or 8 bits per pixel plus a small RGB565
+//! palette, so it takes a quarter or half the flash of an [`Rgb565Image`].
+//! Plain opaque images can also be embedded as a [`RawRgb565Image`] with
+//! [`include_rgb565!`](crate::include_rgb565).
+
+use embedded_graphics::{
+    image::{
+        ImageDrawable,
+        ImageRawBE,
+    },
+    pixelcolor::{
+        Rgb565,
+        raw::RawU16,
+    },
+    prelude::*,
+    primitives::Rectangle,
+};
+
+/// Raw big-endian RGB565 pixels, the panel's native format.
+pub type RawRgb565Image<'a> = ImageRawBE<'a, Rgb565>;
+
+/// Pixel storage of an [`Rgb565Image`], row-major, top row first.
+#[derive(Clone, Copy)]
+pub enum PixelData<'a> {
+    /// One raw RGB565 value per pixel.
+    Raw(&'a [u16]),
+    /// Run-length encoded `(count, colour)` pairs of raw RGB565 values.
+    Rle(&'a [u16]),
+}
+
+/// An RGB565 image with an optional 1-bit transparency mask.
+#[derive(Clone, Copy)]
+pub struct Rgb565Image<'a> {
+    width: u32,
+    height: u32,
+    data: PixelData<'a>,
+    mask: Option<&'a [u8]>,
+}
+
+impl<'a> Rgb565Image<'a> {
+    /// Wrap existing pixel data.
+    ///
+    /// `mask` holds one bit per pixel, MSB first, with each row padded to a
+    /// whole byte (the same layout as `ImageRaw<BinaryColor>`). A set bit
+    /// marks an opaque pixel; `None` means the image is fully opaque.
+    pub const fn new(width: u32, height: u32, data: PixelData<'a>, mask: Option<&'a [u8]>) -> Self {
+        Self {
+            width,
+            height,
+            data,
+            mask,
+        }
+    }
+
+    /// `true` if the image has transparent pixels.
+    pub const fn has_mask(&self) -> bool {
+        self.mask.is_some()
+    }
+
+    /// `true` if the pixel at `(x, y)` should be drawn.
+    pub fn is_opaque(&self, x: u32, y: u32) -> bool {
+        self.mask.is_none_or(|mask| {
+            let row_bytes = self.width.div_ceil(8);
+            let byte = mask[(y * row_bytes + x / 8) as usize];
+            byte & (0x80 >> (x % 8)) != 0
+        })
+    }
+
+    /// All pixel colours in row-major order, including transparent ones.
+    pub fn pixels(&self) -> Pixels<'a> {
+        Pixels {
+            data: self.data,
+            pos: 0,
+            run_left: 0,
+            run_color: 0,
+        }
+    }
+}
+
+impl OriginDimensions for Rgb565Image<'_> {
+    fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+}
+
+impl ImageDrawable for Rgb565Image<'_> {
+    type Color = Rgb565;
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        if self.mask.is_none() {
+            return target.fill_contiguous(&self.bounding_box(), self.pixels());
+        }
+        draw_opaque_runs(target, self.size(), self.pixels(), |x, y| {
+            self.is_opaque(x, y)
+        })
+    }
+
+    fn draw_sub_image<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.draw(&mut target.translated(-area.top_left).clipped(area))
+    }
+}
+
+/// Iterator over the colours of an [`Rgb565Image`].
+pub struct Pixels<'a> {
+    data: PixelData<'a>,
+    pos: usize,
+    run_left: u16,
+    run_color: u16,
+}
+
+impl Iterator for Pixels<'_> {
+    type Item = Rgb565;
+
+    fn next(&mut self) -> Option<Rgb565> {
+        match self.data {
+            PixelData::Raw(raw) => {
+                let px = *raw.get(self.pos)?;
+                self.pos += 1;
+                Some(RawU16::new(px).into())
+            }
+            PixelData::Rle(runs) => {
+                while self.run_left == 0 {
+                    self.run_left = *runs.get(self.pos)?;
+                    self.run_color = *runs.get(self.pos + 1)?;
+                    self.pos += 2;
+                }
+                self.run_left -= 1;
+                Some(RawU16::new(self.run_color).into())
+            }
+        }
+    }
+}
+
+/// Bits per pixel of a [`PalettedImage`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PaletteDepth {
+    /// Two pixels per byte, high nibble first; up to 16 colours.
+    Bits4,
+    /// One pixel per byte; up to 256 colours.
+    Bits8,
+}
+
+impl PaletteDepth {
+    const fn row_bytes(self, width: u32) -> usize {
+        match self {
+            Self::Bits4 => width.div_ceil(2) as usize,
+            Self::Bits8 => width as usize,
+        }
+    }
+}
+
+/// A 4- or 8-bit palettized image.
+///
+/// Each pixel is an index into a palette of raw RGB565 values. One index may
+/// be reserved for transparent pixels, which are skipped when drawing.
+#[derive(Clone, Copy)]
+pub struct PalettedImage<'a> {
+    width: u32,
+    height: u32,
+    depth: PaletteDepth,
+    palette: &'a [u16],
+    indices: &'a [u8],
+    transparent: Option<u8>,
+}
+
+impl<'a> PalettedImage<'a> {
+    /// Wrap existing index data.
+    ///
+    /// `indices` is row-major, top row first, with each row padded to a whole
+    /// byte. Every index must be within `palette`.
+    pub const fn new(
+        width: u32,
+        height: u32,
+        depth: PaletteDepth,
+        palette: &'a [u16],
+        indices: &'a [u8],
+        transparent: Option<u8>,
+    ) -> Self {
+        Self {
+            width,
+            height,
+            depth,
+            palette,
+            indices,
+            transparent,
+        }
+    }
+
+    /// The same image drawn with a different palette.
+    ///
+    /// Handy for colour cycling or team colours without duplicating the
+    /// pixel data. `palette` must be at least as long as the original one.
+    pub const fn with_palette(self, palette: &'a [u16]) -> Self {
+        Self { palette, ..self }
+    }
+
+    /// The palette as raw RGB565 values.
+    pub const fn palette(&self) -> &'a [u16] {
+        self.palette
+    }
+
+    /// Bits per pixel.
+    pub const fn depth(&self) -> PaletteDepth {
+        self.depth
+    }
+
+    /// Palette index of the pixel at `(x, y)`.
+    pub fn index(&self, x: u32, y: u32) -> u8 {
+        let row = y as usize * self.depth.row_bytes(self.width);
+        match self.depth {
+            PaletteDepth::Bits4 => {
+                let byte = self.indices[row + x as usize / 2];
+                if x.is_multiple_of(2) {
+                    byte >> 4
+                } else {
+                    byte & 0x0f
+                }
+            }
+            PaletteDepth::Bits8 => self.indices[row + x as usize],
+        }
+    }
+
+    /// `true` if the pixel at `(x, y)` should be drawn.
+    pub fn is_opaque(&self, x: u32, y: u32) -> bool {
+        self.transparent != Some(self.index(x, y))
+    }
+
+    /// All pixel colours in row-major order, including transparent ones.
+    pub fn pixels(&self) -> PalettedPixels<'a> {
+        PalettedPixels {
+            image: *self,
+            x: 0,
+            y: 0,
+        }
+    }
+}
+
+impl OriginDimensions for PalettedImage<'_> {
+    fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+}
+
+impl ImageDrawable for PalettedImage<'_> {
+    type Color = Rgb565;
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        if self.transparent.is_none() {
+            return target.fill_contiguous(&self.bounding_box(), self.pixels());
+        }
+        draw_opaque_runs(target, self.size(), self.pixels(), |x, y| {
+            self.is_opaque(x, y)
+        })
+    }
+
+    fn draw_sub_image<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.draw(&mut target.translated(-area.top_left).clipped(area))
+    }
+}
+
+/// Iterator over the colours of a [`PalettedImage`].
+pub struct PalettedPixels<'a> {
+    image: PalettedImage<'a>,
+    x: u32,
+    y: u32,
+}
+
+impl Iterator for PalettedPixels<'_> {
+    type Item = Rgb565;
+
+    fn next(&mut self) -> Option<Rgb565> {
+        if self.y >= self.image.height {
+            return None;
+        }
+        let index = self.image.index(self.x, self.y);
+        self.x += 1;
+        if self.x == self.image.width {
+            self.x = 0;
+            self.y += 1;
+        }
+        Some(RawU16::new(self.image.palette[usize::from(index)]).into())
+    }
+}
+
+/// Draw `pixels` (row-major over `size`), skipping pixels that aren't opaque.
+///
+/// Each horizontal run of opaque pixels is pushed as one window write.
+pub(crate) fn draw_opaque_runs<D, I>(
+    target: &mut D,
+    size: Size,
+    mut pixels: I,
+    is_opaque: impl Fn(u32, u32) -> bool,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = Rgb565>,
+    I: Iterator<Item = Rgb565>,
+{
+    for y in 0..size.height {
+        let mut x = 0;
+        while x < size.width {
+            let opaque = is_opaque(x, y);
+            let start = x;
+            while x < size.width && is_opaque(x, y) == opaque {
+                x += 1;
+            }
+            let mut run = pixels.by_ref().take((x - start) as usize);
+            if opaque {
+                let area =
+                    Rectangle::new(Point::new(start as i32, y as i32), Size::new(x - start, 1));
+                target.fill_contiguous(&area, &mut run)?;
+            }
+            // Skip whatever the target didn't consume (clipped or transparent).
+            run.for_each(drop);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,159 @@
+//! Time-based LED effects that never block.
+
+use embassy_time::{
+    Duration,
+    Instant,
+    Timer,
+};
+use palette::Srgb;
+
+use crate::{
+    Leds,
+    leds::{
+        LED_COUNT,
+        mix,
+    },
+};
+
+/// Time between renders in [`LedAnimator::run`].
+const FRAME: Duration = Duration::from_millis(20);
+
+/// An LED effect, worked out from the time since it started.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Effect {
+    /// All LEDs go from `from` to `to` over `duration`, and stay at `to`.
+    Fade {
+        from: Srgb<u8>,
+        to: Srgb<u8>,
+        duration: Duration,
+    },
+    /// All LEDs flash `color`, lit for the first half of each `period`,
+    /// `count` times or forever if 0, then go dark.
+    Blink {
+        color: Srgb<u8>,
+        period: Duration,
+        count: u32,
+    },
+    /// A light with a fading tail runs round the strip, one LED per
+    /// `step`, `laps` times or forever if 0, then goes dark.
+    Chase {
+        color: Srgb<u8>,
+        step: Duration,
+        laps: u32,
+    },
+}
+
+/// Plays one [`Effect`] at a time on the LEDs.
+///
+/// Nothing waits: each [`render`](Self::render) sets the LEDs for the given
+/// moment, so a game can keep its frame loop going while the effect runs:
+///
+/// ```rust,ignore
+/// let mut animator = LedAnimator::new();
+/// animator.play(Effect::Blink { color: RED, period: Duration::from_millis(600), count: 3 });
+/// loop {
+///     game.tick();
+///     if animator.render(leds, Instant::now()) {
+///         leds.update().await;
+///     }
+///     ticker.next().await;
+/// }
+/// ```
+///
+/// While the app has nothing else to do, [`run`](Self::run) keeps the
+/// LEDs going by itself until cancelled, e.g. by a button press:
+///
+/// ```rust,ignore
+/// select(Buttons::debounce_press(&mut buttons.a), animator.run(leds)).await;
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct LedAnimator {
+    playing: Option<(Effect, Instant)>,
+}
+
+impl LedAnimator {
+    /// An animator with no effect playing.
+    pub const fn new() -> Self {
+        Self { playing: None }
+    }
+
+    /// Start `effect` now, replacing whatever was playing.
+    pub fn play(&mut self, effect: Effect) {
+        self.playing = Some((effect, Instant::now()));
+    }
+
+    /// Stop the effect, leaving the LEDs as last rendered.
+    pub const fn stop(&mut self) {
+        self.playing = None;
+    }
+
+    /// `true` until the effect has finished or been stopped.
+    pub const fn is_playing(&self) -> bool {
+        self.playing.is_some()
+    }
+
+    /// Set `leds` to the effect at `now`, and return whether they were
+    /// changed. The call that sees the effect finish sets its final state;
+    /// after that the LEDs are left alone. Call [`Leds::update`] to show
+    /// the result.
+    pub fn render(&mut self, leds: &mut Leds<'_>, now: Instant) -> bool {
+        let Some((effect, start)) = self.playing else {
+            return false;
+        };
+        let elapsed = now.saturating_duration_since(start).as_millis();
+        let done = match effect {
+            Effect::Fade { from, to, duration } => {
+                let total = duration.as_millis().max(1);
+                let t = elapsed.min(total);
+                leds.fill(mix(from, to, (t * 256 / total) as u32));
+                elapsed >= total
+            }
+            Effect::Blink {
+                color,
+                period,
+                count,
+            } => {
+                let period = period.as_millis().max(1);
+                let done = count > 0 && elapsed / period >= u64::from(count);
+                if !done && elapsed % period < period / 2 {
+                    leds.fill(color);
+                } else {
+                    leds.clear();
+                }
+                done
+            }
+            Effect::Chase { color, step, laps } => {
+                let position = elapsed / step.as_millis().max(1);
+                let done = laps > 0 && position / LED_COUNT as u64 >= u64::from(laps);
+                leds.clear();
+                if !done {
+                    let head = (position % LED_COUNT as u64) as usize;
+                    let dark = Srgb::new(0, 0, 0);
+                    for (behind, level) in [(0, 256), (1, 64), (2, 16)] {
+                        if behind > position {
+                            break;
+                        }
+                        let led = (head + LED_COUNT - behind as usize) % LED_COUNT;
+                        leds.set(led, mix(dark, color, level));
+                    }
+                }
+                done
+            }
+        };
+        if done {
+            self.playing = None;
+        }
+        true
+    }
+
+    /// Render and update the LEDs every 20 ms, forever. Drop the future to
+    /// stop.
+    pub async fn run(&mut self, leds: &mut Leds<'_>) {
+        loop {
+            if self.render(leds, Instant::now()) {
+                leds.update().await;
+            }
+            Timer::after(FRAME).await;
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Colour palettes for LED effects.
+//!
+//! An effect picks colours by a position in a palette, 0 to 255 going once
+//! round it, instead of working out colours itself. Sweeping the position
+//! along the strip and moving it each frame gives flames, waves or a party
+//! out of the same few lines:
+//!
+//! ```rust,ignore
+//! leds.fill_from_iter(Palette16::OCEAN.sweep(start, 12));
+//! start = start.wrapping_add(2);
+//! ```
+
+use palette::Srgb;
+
+use crate::leds::mix;
+
+/// Builds a palette from `0xRRGGBB` values, to keep the stock ones short.
+const fn hex(colors: [u32; 16]) -> [Srgb<u8>; 16] {
+    let mut out = [Srgb::new(0, 0, 0); 16];
+    let mut i = 0;
+    while i < 16 {
+        let c = colors[i];
+        out[i] = Srgb::new((c >> 16) as u8, (c >> 8) as u8, c as u8);
+        i += 1;
+    }
+    out
+}
+
+/// Sixteen colours spread evenly round the index range, with colours in
+/// between blended from the two nearest, like FastLED's `CRGBPalette16`.
+///
+/// The last colour blends back into the first, so a sweep that wraps past
+/// 255 has no seam.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Palette16 {
+    colors: [Srgb<u8>; 16],
+}
+
+impl Palette16 {
+    /// Black through deep red, orange and yellow to white, for fire.
+    pub const HEAT: Self = Self::new(hex([
+        0x000000, 0x140000, 0x330000, 0x5a0000, 0x800000, 0xa80800, 0xcc2000, 0xe84000, 0xff6000,
+        0xff8000, 0xffa000, 0xffc000, 0xffe020, 0xfff060, 0xffffa0, 0xffffff,
+    ]));
+
+    /// Deep blues, sea greens and aqua.
+    pub const OCEAN: Self = Self::new(hex([
+        0x191970, 0x00008b, 0x191970, 0x000080, 0x00008b, 0x0000cd, 0x2e8b57, 0x008080, 0x5f9ea0,
+        0x0000ff, 0x008b8b, 0x6495ed, 0x7fffd4, 0x2e8b57, 0x00ffff, 0x87cefa,
+    ]));
+
+    /// Saturated purples, reds and yellows.
+    pub const PARTY: Self = Self::new(hex([
+        0x5500ab, 0x84007c, 0xb5004b, 0xe5001b, 0xe81700, 0xb84700, 0xab7700, 0xabab00, 0xab5500,
+        0xdd2200, 0xf2000e, 0xc2003e, 0x8f0071, 0x5f00a1, 0x2f00d0, 0x0007f9,
+    ]));
+
+    /// The greens of the Disobey logo, between dark and bright.
+    pub const DISOBEY: Self = Self::new(hex([
+        0x02291a, 0x026739, 0x00a550, 0x2bb573, 0x8dc63f, 0x2bb573, 0x00a550, 0x026739, 0x02291a,
+        0x026739, 0x00a550, 0x8dc63f, 0xc6e39f, 0x8dc63f, 0x2bb573, 0x026739,
+    ]));
+
+    /// A palette of `colors`, the first at index 0 and each next one 16
+    /// further on.
+    pub const fn new(colors: [Srgb<u8>; 16]) -> Self {
+        Self { colors }
+    }
+
+    /// The colour at `index`, blended between the two nearest entries.
+    pub fn lookup(&self, index: u8) -> Srgb<u8> {
+        let entry = usize::from(index >> 4);
+        let amount = u32::from(index & 0x0f) * 16;
+        mix(self.colors[entry], self.colors[(entry + 1) % 16], amount)
+    }
+
+    /// Colours from `start`, moving `step` through the palette for each
+    /// one, without end. Pass to [`Leds::fill_from_iter`](crate::Leds::fill_from_iter).
+    pub fn sweep(&self, start: u8, step: u8) -> impl Iterator<Item = Srgb<u8>> + '_ {
+        sweep(start, step).map(|index| self.lookup(index))
+    }
+}
+
+/// A colour for every index, for palettes with more detail than sixteen
+/// entries give, or to do the blending once instead of per lookup.
+///
+/// Build one from a [`Palette16`] with `Palette256::from`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Palette256 {
+    colors: [Srgb<u8>; 256],
+}
+
+impl Palette256 {
+    /// A palette of `colors`, one per index.
+    pub const fn new(colors: [Srgb<u8>; 256]) -> Self {
+        Self { colors }
+    }
+
+    /// The colour at `index`.
+    pub const fn lookup(&self, index: u8) -> Srgb<u8> {
+        self.colors[index as usize]
+    }
+
+    /// Colours from `start`, moving `step` through the palette for each
+    /// one, without end. Pass to [`Leds::fill_from_iter`](crate::Leds::fill_from_iter).
+    pub fn sweep(&self, start: u8, step: u8) -> impl Iterator<Item = Srgb<u8>> + '_ {
+        sweep(start, step).map(|index| self.lookup(index))
+    }
+}
+
+impl From<Palette16> for Palette256 {
+    fn from(palette: Palette16) -> Self {
+        Self::new(core::array::from_fn(|i| palette.lookup(i as u8)))
+    }
+}
+
+/// Indices from `start` in steps of `step`, wrapping at 256.
+fn sweep(start: u8, step: u8) -> impl Iterator<Item = u8> {
+    core::iter::successors(Some(start), move |index| Some(index.wrapping_add(step)))
+}
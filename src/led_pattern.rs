@@ -0,0 +1,238 @@
+//! LED animations described as data rather than code.
+//!
+//! A pattern is a [`Document`] of `key = value` lines, one step per line,
+//! played in order and then from the top again:
+//!
+//! ```text
+//! # Blue and red, swapping sides, then a fade to dark.
+//! name = Police
+//! bars = 0000ff ff0000 150
+//! bars = ff0000 0000ff 150
+//! fade = 000 400
+//! ```
+//!
+//! The steps are, with times in milliseconds:
+//!
+//! - `fill = color time`, every LED one colour
+//! - `bars = left right time`, each bar one colour
+//! - `leds = color × 10 time`, every LED its own colour, in strip order
+//! - `fade = color time`, crossfading every LED to the colour
+//! - `rainbow = step speed time`, a rainbow `step` degrees per LED,
+//!   turning `speed` degrees a frame
+//! - `palette = heat|ocean|party|disobey step speed time`, a
+//!   [`Palette16`] sweep, `step` and `speed` as palette indices
+//! - `wait = time`, keeping the LEDs as they are
+//!
+//! Colours are as in [`level::parse_color`](crate::level::parse_color).
+//!
+//! ```rust,ignore
+//! let pattern = LedPattern::parse(include_str!("assets/led_patterns/police.txt"))?;
+//! pattern.run(&mut leds).await;
+//! ```
+
+use embassy_time::{
+    Duration,
+    Instant,
+    Ticker,
+    Timer,
+};
+use palette::Srgb;
+
+use crate::{
+    Leds,
+    Palette16,
+    leds::LED_COUNT,
+    level::{
+        Document,
+        Error,
+        Words,
+        parse_rgb,
+    },
+};
+
+/// Time between frames of a moving step.
+const FRAME: Duration = Duration::from_millis(20);
+
+/// A parsed LED pattern, borrowing its text.
+#[derive(Clone, Copy)]
+pub struct LedPattern<'a> {
+    document: Document<'a>,
+}
+
+impl<'a> LedPattern<'a> {
+    /// Parse a pattern from `text`, checking every step. A pattern with
+    /// no steps, or only steps that take no time, is [`Error::Empty`].
+    pub fn parse(text: &'a str) -> Result<Self, Error> {
+        Self::new(Document::parse(text, |entry| Step::parse(entry).is_some())?)
+    }
+
+    /// Parse a pattern from raw bytes, such as a flash region.
+    pub fn from_bytes(data: &'a [u8]) -> Result<Self, Error> {
+        Self::new(Document::from_bytes(data, |entry| {
+            Step::parse(entry).is_some()
+        })?)
+    }
+
+    fn new(document: Document<'a>) -> Result<Self, Error> {
+        let pattern = Self { document };
+        if pattern.duration().as_ticks() == 0 {
+            return Err(Error::Empty);
+        }
+        Ok(pattern)
+    }
+
+    /// The pattern's `name`, if it has one.
+    pub fn name(&self) -> Option<&'a str> {
+        self.document.name()
+    }
+
+    /// The time one pass through the steps takes.
+    pub fn duration(&self) -> Duration {
+        self.steps()
+            .map(|step| step.duration())
+            .fold(Duration::from_ticks(0), |total, step| total + step)
+    }
+
+    /// Play the steps once, leaving the LEDs as the last one left them.
+    pub async fn play(&self, leds: &mut Leds<'_>) {
+        for step in self.steps() {
+            step.play(leds).await;
+        }
+    }
+
+    /// Play the steps over and over.
+    pub async fn run(&self, leds: &mut Leds<'_>) -> ! {
+        loop {
+            self.play(leds).await;
+        }
+    }
+
+    fn steps(&self) -> impl Iterator<Item = Step> + use<'a> {
+        self.document.entries().filter_map(Step::parse)
+    }
+}
+
+/// One line of a pattern.
+enum Step {
+    Name,
+    Show([Srgb<u8>; LED_COUNT], Duration),
+    Fade(Srgb<u8>, Duration),
+    Rainbow {
+        step: u16,
+        speed: u16,
+        duration: Duration,
+    },
+    Palette {
+        palette: Palette16,
+        step: u8,
+        speed: u8,
+        duration: Duration,
+    },
+    Wait(Duration),
+}
+
+impl Step {
+    fn parse((key, value): (&str, &str)) -> Option<Self> {
+        let mut words = Words(value);
+        let step = match key {
+            "name" => return Some(Self::Name),
+            "fill" => Self::Show([color(words.next()?)?; LED_COUNT], millis(words.next()?)?),
+            "bars" => {
+                let (left, right) = (color(words.next()?)?, color(words.next()?)?);
+                let mut frame = [right; LED_COUNT];
+                frame[LED_COUNT / 2..].fill(left);
+                Self::Show(frame, millis(words.next()?)?)
+            }
+            "leds" => {
+                let mut frame = [Srgb::new(0, 0, 0); LED_COUNT];
+                for led in &mut frame {
+                    *led = color(words.next()?)?;
+                }
+                Self::Show(frame, millis(words.next()?)?)
+            }
+            "fade" => Self::Fade(color(words.next()?)?, millis(words.next()?)?),
+            "rainbow" => Self::Rainbow {
+                step: words.number()?,
+                speed: words.number()?,
+                duration: millis(words.next()?)?,
+            },
+            "palette" => Self::Palette {
+                palette: match words.next()? {
+                    "heat" => Palette16::HEAT,
+                    "ocean" => Palette16::OCEAN,
+                    "party" => Palette16::PARTY,
+                    "disobey" => Palette16::DISOBEY,
+                    _ => return None,
+                },
+                step: words.number()?,
+                speed: words.number()?,
+                duration: millis(words.next()?)?,
+            },
+            "wait" => Self::Wait(millis(words.next()?)?),
+            _ => return None,
+        };
+        words.end(step)
+    }
+
+    fn duration(&self) -> Duration {
+        match *self {
+            Self::Name => Duration::from_ticks(0),
+            Self::Show(_, duration) | Self::Fade(_, duration) | Self::Wait(duration) => duration,
+            Self::Rainbow { duration, .. } | Self::Palette { duration, .. } => duration,
+        }
+    }
+
+    async fn play(&self, leds: &mut Leds<'_>) {
+        match *self {
+            Self::Name => return,
+            Self::Show(frame, duration) => {
+                leds.fill_from_iter(frame);
+                leds.update().await;
+                Timer::after(duration).await;
+                return;
+            }
+            Self::Wait(duration) => {
+                Timer::after(duration).await;
+                return;
+            }
+            Self::Fade(color, duration) => leds.fade_to([color; LED_COUNT], duration),
+            Self::Rainbow { .. } | Self::Palette { .. } => {}
+        }
+
+        // Moving steps and fades send a frame at a time.
+        let end = Instant::now() + self.duration();
+        let mut ticker = Ticker::every(FRAME);
+        let mut frame = 0u32;
+        while Instant::now() < end {
+            match *self {
+                Self::Rainbow { step, speed, .. } => {
+                    let hue = (frame * u32::from(speed) % 360) as u16;
+                    leds.fill_rainbow(hue, step, 255);
+                }
+                Self::Palette {
+                    palette,
+                    step,
+                    speed,
+                    ..
+                } => {
+                    let start = (frame * u32::from(speed)) as u8;
+                    leds.fill_from_iter(palette.sweep(start, step));
+                }
+                _ => {}
+            }
+            leds.update().await;
+            frame += 1;
+            ticker.next().await;
+        }
+    }
+}
+
+/// A hex colour, as in [`parse_rgb`].
+fn color(s: &str) -> Option<Srgb<u8>> {
+    parse_rgb(s).map(|(r, g, b)| Srgb::new(r, g, b))
+}
+
+/// A time in whole milliseconds.
+fn millis(s: &str) -> Option<Duration> {
+    Some(Duration::from_millis(s.parse().ok()?))
+}
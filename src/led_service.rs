@@ -0,0 +1,223 @@
+//! LEDs shared between tasks.
+//!
+//! A [`LedService`] owns the strip and refreshes it from its own task at a
+//! fixed rate. Every other task gets a [`LedHandle`], which is `Copy`, and
+//! sets colours through it without waiting for the LEDs:
+//!
+//! ```rust,ignore
+//! static LEDS: LedService = LedService::new();
+//!
+//! spawner.must_spawn(led_service_task(&LEDS, leds));
+//! spawner.must_spawn(game_task(LEDS.handle()));
+//! spawner.must_spawn(alert_task(LEDS.handle()));
+//!
+//! // In a task:
+//! leds.set(3, Srgb::new(255, 0, 0));
+//! ```
+
+use core::cell::RefCell;
+
+use embassy_sync::blocking_mutex::{
+    Mutex,
+    raw::CriticalSectionRawMutex,
+};
+use embassy_time::{
+    Duration,
+    Ticker,
+};
+use palette::Srgb;
+
+use crate::{
+    BAR_COUNT,
+    Leds,
+    PowerSave,
+    hsv_to_srgb,
+    leds::LED_COUNT,
+};
+
+/// Refresh rate unless set with [`LedService::with_rate`].
+const DEFAULT_RATE: u64 = 50;
+
+/// What the handles have asked for since the last refresh.
+#[derive(Clone, Copy)]
+struct Shared {
+    frame: [Srgb<u8>; LED_COUNT],
+    brightness: u8,
+    crossfade: Duration,
+    power_save: Option<PowerSave>,
+    changed: bool,
+}
+
+/// Owns the LED colours for every task, and sends them to the strip from
+/// [`run`](Self::run), usually through [`led_service_task`].
+///
+/// Put it in a `static` so handles can be `'static`.
+pub struct LedService {
+    shared: Mutex<CriticalSectionRawMutex, RefCell<Shared>>,
+    period: Duration,
+}
+
+impl LedService {
+    /// All LEDs off, refreshed 50 times a second.
+    pub const fn new() -> Self {
+        Self {
+            shared: Mutex::new(RefCell::new(Shared {
+                frame: [Srgb::new(0, 0, 0); LED_COUNT],
+                brightness: 255,
+                crossfade: Duration::from_ticks(0),
+                power_save: None,
+                changed: true,
+            })),
+            period: Duration::from_hz(DEFAULT_RATE),
+        }
+    }
+
+    /// Refresh `hz` times a second instead.
+    #[must_use]
+    pub const fn with_rate(mut self, hz: u64) -> Self {
+        self.period = Duration::from_hz(if hz == 0 { 1 } else { hz });
+        self
+    }
+
+    /// A handle for a task to set the LEDs with.
+    pub const fn handle(&'static self) -> LedHandle {
+        LedHandle { service: self }
+    }
+
+    /// Send the colours set through the handles to `leds` at the service's
+    /// rate, forever. Changes are crossfaded if a handle asked for it.
+    pub async fn run(&self, leds: &mut Leds<'_>) -> ! {
+        let mut ticker = Ticker::every(self.period);
+        loop {
+            let update = self.shared.lock(|shared| {
+                let mut shared = shared.borrow_mut();
+                let changed = shared.changed;
+                shared.changed = false;
+                changed.then_some(*shared)
+            });
+            if let Some(Shared {
+                frame,
+                brightness,
+                crossfade,
+                power_save,
+                ..
+            }) = update
+            {
+                leds.set_global_brightness(brightness);
+                if leds.power_save() != power_save {
+                    leds.set_power_save(power_save);
+                }
+                if crossfade.as_ticks() == 0 {
+                    leds.fill_from_iter(frame);
+                } else {
+                    leds.fade_to(frame, crossfade);
+                }
+            }
+            leds.update().await;
+            ticker.next().await;
+        }
+    }
+
+    fn modify(&self, f: impl FnOnce(&mut Shared)) {
+        self.shared.lock(|shared| {
+            let mut shared = shared.borrow_mut();
+            f(&mut shared);
+            shared.changed = true;
+        });
+    }
+}
+
+impl Default for LedService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Runs `service` on `leds`, for spawning alongside the tasks that use it.
+#[embassy_executor::task]
+pub async fn led_service_task(service: &'static LedService, leds: &'static mut Leds<'static>) {
+    service.run(leds).await
+}
+
+/// Sets the colours of a [`LedService`] from any task. Changes show on the
+/// next refresh; nothing here waits for the LEDs.
+#[derive(Clone, Copy)]
+pub struct LedHandle {
+    service: &'static LedService,
+}
+
+impl LedHandle {
+    /// Change the colours in place, for anything the other methods don't
+    /// cover. Keep `f` short: other tasks wait for it.
+    pub fn update(&self, f: impl FnOnce(&mut [Srgb<u8>; LED_COUNT])) {
+        self.service.modify(|shared| f(&mut shared.frame));
+    }
+
+    /// The colours set so far.
+    pub fn colors(&self) -> [Srgb<u8>; LED_COUNT] {
+        self.service.shared.lock(|shared| shared.borrow().frame)
+    }
+
+    /// Set a single LED by index.
+    pub fn set(&self, index: usize, color: Srgb<u8>) {
+        self.update(|frame| frame[index] = color);
+    }
+
+    /// Set every LED at once.
+    pub fn set_all(&self, colors: [Srgb<u8>; LED_COUNT]) {
+        self.update(|frame| *frame = colors);
+    }
+
+    /// Fill all LEDs with one colour.
+    pub fn fill(&self, color: Srgb<u8>) {
+        self.update(|frame| frame.fill(color));
+    }
+
+    /// Turn all LEDs off.
+    pub fn clear(&self) {
+        self.fill(Srgb::new(0, 0, 0));
+    }
+
+    /// Set the right LED bar, bottom to top, like
+    /// [`Leds::set_right_bar`].
+    pub fn set_right_bar(&self, colors: &[Srgb<u8>; BAR_COUNT]) {
+        self.update(|frame| frame[..BAR_COUNT].copy_from_slice(colors));
+    }
+
+    /// Set the left LED bar, bottom to top, like [`Leds::set_left_bar`].
+    pub fn set_left_bar(&self, colors: &[Srgb<u8>; BAR_COUNT]) {
+        self.update(|frame| {
+            for i in 0..BAR_COUNT {
+                frame[BAR_COUNT + i] = colors[BAR_COUNT - 1 - i];
+            }
+        });
+    }
+
+    /// Spread a rainbow round the strip, like [`Leds::fill_rainbow`].
+    pub fn fill_rainbow(&self, hue: u16, step: u16, value: u8) {
+        self.update(|frame| {
+            for (i, led) in frame.iter_mut().enumerate() {
+                let hue = u32::from(hue) + i as u32 * u32::from(step);
+                *led = hsv_to_srgb((hue % 360) as u16, 255, value);
+            }
+        });
+    }
+
+    /// Cap the brightness of every LED, like
+    /// [`Leds::set_global_brightness`].
+    pub fn set_global_brightness(&self, brightness: u8) {
+        self.service.modify(|shared| shared.brightness = brightness);
+    }
+
+    /// Crossfade to new colours over `duration` instead of switching at
+    /// once. Zero turns it off again.
+    pub fn set_crossfade(&self, duration: Duration) {
+        self.service.modify(|shared| shared.crossfade = duration);
+    }
+
+    /// Strobe the LEDs to save power, or stop with `None`, like
+    /// [`Leds::set_power_save`].
+    pub fn set_power_save(&self, power_save: Option<PowerSave>) {
+        self.service.modify(|shared| shared.power_save = power_save);
+    }
+}
@@ -5,18 +5,26 @@
 use defmt::error;
 use embassy_time::{
     Duration,
+    Instant,
     Timer,
 };
+use embedded_graphics::prelude::*;
 use esp_hal::{
-    Blocking,
+    Async,
     gpio::Level,
     rmt::{
+        CHANNEL_RAM_SIZE,
         PulseCode,
         Tx,
     },
 };
 use palette::Srgb;
 
+use crate::{
+    dial::on_dial,
+    notification,
+};
+
 /// Number of WS2812 LEDs on the badge.
 /// There are two led bars with 5 leds each. Left and right. Indexing is counter clockwise starting from the bottom right.
 /// Index 0 is bottom right. Index 4 is top right. Index 5 is top left. Index 9 is bottom left.
@@ -25,38 +33,419 @@ pub const LED_COUNT: usize = 10;
 /// Number of LEDs per bar (left or right).
 pub const BAR_COUNT: usize = 5;
 
+/// Where each LED sits, in pixels of the default landscape screen: the
+/// bars run beside its right and left edges, so these lie just outside it.
+const POSITIONS: [Point; LED_COUNT] = [
+    Point::new(336, 153),
+    Point::new(336, 119),
+    Point::new(336, 85),
+    Point::new(336, 51),
+    Point::new(336, 17),
+    Point::new(-16, 17),
+    Point::new(-16, 51),
+    Point::new(-16, 85),
+    Point::new(-16, 119),
+    Point::new(-16, 153),
+];
+
+/// Perceptual brightness to LED duty, `255 * (i / 255)^2.2`.
+///
+/// WS2812 brightness is linear in the byte sent, but the eye sees the
+/// first few steps as huge jumps. Mapping through this curve spreads the
+/// dim end out, like sRGB on a screen.
+const GAMMA: [u8; 256] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2,
+    3, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 6, 6, 6, 6, 7, 7, 7, 8, 8, 8, 9, 9, 9, 10, 10, 11, 11,
+    11, 12, 12, 13, 13, 13, 14, 14, 15, 15, 16, 16, 17, 17, 18, 18, 19, 19, 20, 20, 21, 22, 22, 23,
+    23, 24, 25, 25, 26, 26, 27, 28, 28, 29, 30, 30, 31, 32, 33, 33, 34, 35, 35, 36, 37, 38, 39, 39,
+    40, 41, 42, 43, 43, 44, 45, 46, 47, 48, 49, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61,
+    62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 73, 74, 75, 76, 77, 78, 79, 81, 82, 83, 84, 85, 87, 88,
+    89, 90, 91, 93, 94, 95, 97, 98, 99, 100, 102, 103, 105, 106, 107, 109, 110, 111, 113, 114, 116,
+    117, 119, 120, 121, 123, 124, 126, 127, 129, 130, 132, 133, 135, 137, 138, 140, 141, 143, 145,
+    146, 148, 149, 151, 153, 154, 156, 158, 159, 161, 163, 165, 166, 168, 170, 172, 173, 175, 177,
+    179, 181, 182, 184, 186, 188, 190, 192, 194, 196, 197, 199, 201, 203, 205, 207, 209, 211, 213,
+    215, 217, 219, 221, 223, 225, 227, 229, 231, 234, 236, 238, 240, 242, 244, 246, 248, 251, 253,
+    255,
+];
+
+/// 10 LEDs × 3 bytes × 8 bits + 1 end marker = 241 pulse codes
+const PULSE_COUNT: usize = LED_COUNT * 24 + 1;
+
+/// Blocks of RMT memory the LED channel needs to hold a whole frame, so
+/// nothing has to refill it while it sends.
+pub const RMT_BLOCKS: u8 = PULSE_COUNT.div_ceil(CHANNEL_RAM_SIZE) as u8;
+
+/// Default brightness caps for [`Leds::set_battery_voltage`]: below each
+/// voltage (millivolts of a single-cell LiPo), the LEDs get at most that
+/// brightness, so lighting them all white can't brown out a nearly empty
+/// pack.
+pub const BATTERY_LIMITS: [(u16, u8); 3] = [(3500, 160), (3400, 80), (3300, 24)];
+
+/// How far the voltage has to rise past a threshold before its cap is
+/// lifted again, so a voltage that sags under load doesn't flicker.
+const BATTERY_HYSTERESIS: u16 = 50;
+
+/// How much of each colour channel the LEDs get, 255 for all of it, so
+/// full white looks white rather than tinted on a particular batch of LEDs.
+///
+/// Find the factors by eye with the `led_calibration` example, then keep
+/// them in the app, or wherever it stores settings as [`to_bytes`].
+///
+/// [`to_bytes`]: Self::to_bytes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub struct WhiteBalance {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl WhiteBalance {
+    /// Every channel in full: no correction.
+    pub const NONE: Self = Self::new(255, 255, 255);
+
+    /// Scale red, green and blue by `red / 255`, `green / 255` and
+    /// `blue / 255`.
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue }
+    }
+
+    /// The factors as three bytes, red first, for storing.
+    pub const fn to_bytes(self) -> [u8; 3] {
+        [self.red, self.green, self.blue]
+    }
+
+    /// Factors stored with [`to_bytes`](Self::to_bytes).
+    pub const fn from_bytes(bytes: [u8; 3]) -> Self {
+        Self::new(bytes[0], bytes[1], bytes[2])
+    }
+}
+
+impl Default for WhiteBalance {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+/// A way to drive the LEDs on less power, for [`Leds::set_power_save`]:
+/// lit for `on` out of every `period` and dark the rest, and while lit,
+/// sent at most once every `frame_time`.
+///
+/// The LEDs draw nearly nothing while dark, so the average current falls
+/// with the share of time they're lit, and every frame not sent is CPU
+/// time saved too.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PowerSave {
+    on: Duration,
+    period: Duration,
+    frame_time: Duration,
+}
+
+impl PowerSave {
+    /// For an idle badge: lit a fifth of the time, in half-second flashes,
+    /// at 10 frames a second.
+    pub const IDLE: Self = Self::new(Duration::from_millis(500), Duration::from_millis(2500));
+    /// For a nearly flat battery: a short blip every three seconds.
+    pub const LOW_BATTERY: Self = Self::new(Duration::from_millis(80), Duration::from_millis(3000));
+
+    /// Lit for `on` out of every `period`, at 10 frames a second. An `on`
+    /// as long as the period only lowers the frame rate.
+    pub const fn new(on: Duration, period: Duration) -> Self {
+        Self {
+            on,
+            period,
+            frame_time: Duration::from_millis(100),
+        }
+    }
+
+    /// Send a frame at most once every `time` while lit.
+    #[must_use]
+    pub const fn with_frame_time(mut self, time: Duration) -> Self {
+        self.frame_time = time;
+        self
+    }
+
+    /// `true` in the lit part of the period at `now`.
+    const fn lit(&self, now: Instant) -> bool {
+        let period = self.period.as_micros();
+        period == 0 || now.as_micros() % period < self.on.as_micros()
+    }
+}
+
+/// Why an LED update failed.
+#[derive(Clone, Copy, Debug, PartialEq, defmt::Format)]
+pub enum LedError {
+    /// The RMT peripheral reported an error.
+    Rmt(esp_hal::rmt::Error),
+}
+
+/// A crossfade from the colours shown when it started to the framebuffer.
+#[derive(Clone, Copy)]
+struct Fade {
+    from: [Srgb<u8>; LED_COUNT],
+    /// The framebuffer it was started for, to tell a new target apart.
+    to: [Srgb<u8>; LED_COUNT],
+    start: Instant,
+    duration: Duration,
+}
+
+/// VU meter zones from the bottom LED of a bar to the top.
+const VU_ZONES: [Srgb<u8>; BAR_COUNT] = [
+    Srgb::new(0, 160, 0),   // green
+    Srgb::new(0, 160, 0),   // green
+    Srgb::new(0, 160, 0),   // green
+    Srgb::new(160, 140, 0), // yellow
+    Srgb::new(180, 0, 0),   // red
+];
+/// Time a VU meter takes to fall from full to empty.
+const VU_FALL: Duration = Duration::from_millis(600);
+/// Time a VU meter's peak LED stays up before falling.
+const VU_HOLD: Duration = Duration::from_millis(800);
+
+/// One bar of a VU meter, between calls to [`Leds::set_vu`].
+#[derive(Clone, Copy)]
+struct VuBar {
+    level: u8,
+    peak: u8,
+    peak_at: Instant,
+    updated: Instant,
+}
+
+impl VuBar {
+    const fn new() -> Self {
+        Self {
+            level: 0,
+            peak: 0,
+            peak_at: Instant::from_ticks(0),
+            updated: Instant::from_ticks(0),
+        }
+    }
+
+    /// Take in `input` at `now`, and return the bar's colours.
+    fn update(&mut self, input: u8, now: Instant) -> [Srgb<u8>; BAR_COUNT] {
+        let elapsed = now.saturating_duration_since(self.updated).as_micros();
+        let fall = (elapsed * 255 / VU_FALL.as_micros()).min(255) as u8;
+        self.updated = now;
+        self.level = input.max(self.level.saturating_sub(fall));
+        if self.level >= self.peak {
+            self.peak = self.level;
+            self.peak_at = now;
+        } else if now.saturating_duration_since(self.peak_at) > VU_HOLD {
+            self.peak = self.level.max(self.peak.saturating_sub(fall));
+        }
+
+        // LEDs lit, rounding up so any level above zero shows.
+        let lit = |level: u8| (usize::from(level) * BAR_COUNT).div_ceil(255);
+        let (lit, peak) = (lit(self.level), lit(self.peak));
+        core::array::from_fn(|i| {
+            if i < lit || i + 1 == peak {
+                VU_ZONES[i]
+            } else {
+                Srgb::new(0, 0, 0)
+            }
+        })
+    }
+}
+
 /// WS2812 LED strip driver.
 ///
 /// Maintains an in-memory framebuffer that is flushed to hardware
 /// with [`update`](Leds::update).
+///
+/// Colours are sRGB, so equal steps look like equal changes in brightness:
+/// [`update`](Leds::update) gamma-corrects them on the way out unless
+/// turned off with [`set_gamma_correction`](Leds::set_gamma_correction).
 pub struct Leds<'a> {
-    channel: Option<esp_hal::rmt::Channel<'a, Blocking, Tx>>,
+    channel: esp_hal::rmt::Channel<'a, Async, Tx>,
     framebuffer: [Srgb<u8>; LED_COUNT],
+    /// The colours last sent, part way through a fade.
+    shown: [Srgb<u8>; LED_COUNT],
+    fade: Option<Fade>,
+    gamma: bool,
+    brightness: u8,
+    battery_limits: &'static [(u16, u8)],
+    /// Brightness cap from the last battery voltage.
+    battery_cap: u8,
+    white_balance: WhiteBalance,
+    /// Left and right bars of the VU meter.
+    vu: [VuBar; 2],
+    power_save: Option<PowerSave>,
+    /// When the last lit frame was sent in power save.
+    sent_at: Option<Instant>,
+    /// `true` while power save has the LEDs dark.
+    dark: bool,
 }
 
 impl<'a> Leds<'a> {
-    pub const fn new(channel: esp_hal::rmt::Channel<'a, Blocking, Tx>) -> Self {
+    /// Drive the LEDs through `channel`, which must have
+    /// [`RMT_BLOCKS`] blocks of RMT memory. It's an async channel: call
+    /// `into_async()` on the `Rmt` before configuring it, or convert the
+    /// [`LedResources`](crate::LedResources) with `into()`.
+    pub const fn new(channel: esp_hal::rmt::Channel<'a, Async, Tx>) -> Self {
         Self {
-            channel: Some(channel),
+            channel,
             framebuffer: [Srgb::new(0, 0, 0); LED_COUNT],
+            shown: [Srgb::new(0, 0, 0); LED_COUNT],
+            fade: None,
+            gamma: true,
+            brightness: 255,
+            battery_limits: &BATTERY_LIMITS,
+            battery_cap: 255,
+            white_balance: WhiteBalance::NONE,
+            vu: [VuBar::new(); 2],
+            power_save: None,
+            sent_at: None,
+            dark: false,
         }
     }
 
-    /// Flush the framebuffer to the physical LEDs.
-    pub async fn update(&mut self) {
-        let Some(channel) = self.channel.take() else {
-            error!("RMT channel lost during previous transmission");
-            return;
+    /// Turn gamma correction on (the default) or off. With it off, colour
+    /// values go to the LEDs as they are, for apps that already work in
+    /// raw LED duty.
+    pub const fn set_gamma_correction(&mut self, enabled: bool) {
+        self.gamma = enabled;
+    }
+
+    /// Scale every LED's output by `brightness / 255` on update, whatever
+    /// colours the app sets. Current draw scales with it, so a settings app
+    /// can cap it for the whole badge. Full brightness (255) by default.
+    pub const fn set_global_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness;
+    }
+
+    /// The brightness set with
+    /// [`set_global_brightness`](Self::set_global_brightness).
+    pub const fn global_brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Correct the colours of these particular LEDs on update, after gamma
+    /// correction.
+    pub const fn set_white_balance(&mut self, balance: WhiteBalance) {
+        self.white_balance = balance;
+    }
+
+    /// The correction set with
+    /// [`set_white_balance`](Self::set_white_balance).
+    pub const fn white_balance(&self) -> WhiteBalance {
+        self.white_balance
+    }
+
+    /// Cap the brightness for a battery at `millivolts`, using the limits
+    /// set with [`set_battery_limits`](Self::set_battery_limits). Call it
+    /// whenever the battery is measured; until then there's no cap.
+    ///
+    /// The LEDs get the lower of the cap and the global brightness. A cap
+    /// lifts only once the voltage has recovered a little past its
+    /// threshold.
+    pub fn set_battery_voltage(&mut self, millivolts: u16) {
+        let cap_at = |mv: u16| {
+            self.battery_limits
+                .iter()
+                .filter(|&&(threshold, _)| mv < threshold)
+                .map(|&(_, cap)| cap)
+                .min()
+                .unwrap_or(255)
+        };
+        let cap = cap_at(millivolts);
+        self.battery_cap = if cap <= self.battery_cap {
+            cap
+        } else {
+            self.battery_cap
+                .max(cap_at(millivolts.saturating_sub(BATTERY_HYSTERESIS)))
         };
+    }
+
+    /// Use `limits` instead of [`BATTERY_LIMITS`]: pairs of a voltage in
+    /// millivolts and the brightness allowed below it.
+    pub const fn set_battery_limits(&mut self, limits: &'static [(u16, u8)]) {
+        self.battery_limits = limits;
+    }
+
+    /// The brightness cap from the battery voltage, 255 when there is none.
+    pub const fn battery_cap(&self) -> u8 {
+        self.battery_cap
+    }
+
+    /// Strobe the LEDs and send fewer frames to save power, such as while
+    /// the badge is idle or the battery is low, or go back to normal with
+    /// `None`.
+    ///
+    /// Apps keep setting colours and updating as usual; updates in the dark
+    /// part of the period and updates too soon after the last frame are
+    /// skipped. The flashes only come as often as something updates the
+    /// LEDs, so an app that sets them once should keep updating, or use a
+    /// [`LedService`](crate::LedService).
+    pub const fn set_power_save(&mut self, power_save: Option<PowerSave>) {
+        self.power_save = power_save;
+        self.sent_at = None;
+    }
+
+    /// The mode set with [`set_power_save`](Self::set_power_save).
+    pub const fn power_save(&self) -> Option<PowerSave> {
+        self.power_save
+    }
+
+    /// Flush the framebuffer to the physical LEDs, logging any failure.
+    pub async fn update(&mut self) {
+        if let Err(e) = self.try_update().await {
+            error!("LED update failed: {}", e);
+        }
+    }
 
-        // 10 LEDs × 3 bytes × 8 bits + 1 end marker = 241 pulse codes
-        const PULSE_COUNT: usize = LED_COUNT * 24 + 1;
+    /// Flush the framebuffer to the physical LEDs, and report whether the
+    /// RMT transmission completed.
+    ///
+    /// The whole frame is written to RMT memory before it starts sending,
+    /// so the timing comes from the peripheral alone: a busy CPU can't
+    /// stretch a bit and glitch the colours. Other tasks run while it
+    /// sends.
+    ///
+    /// Active [`notification`]s are drawn over the framebuffer on the way
+    /// out, leaving it as it was.
+    ///
+    /// In [power save](Self::set_power_save), an update that isn't due
+    /// returns at once without sending anything.
+    pub async fn try_update(&mut self) -> Result<(), LedError> {
         let mut pulses = [PulseCode::default(); PULSE_COUNT];
         let mut idx = 0;
-        for color in &self.framebuffer {
+        let now = Instant::now();
+        self.shown = self.faded(now);
+        let mut frame = self.shown;
+        notification::apply(&mut frame, now);
+        if let Some(save) = self.power_save {
+            if !save.lit(now) {
+                if self.dark {
+                    return Ok(());
+                }
+                frame = [Srgb::new(0, 0, 0); LED_COUNT];
+            } else if !self.dark
+                && self
+                    .sent_at
+                    .is_some_and(|at| now.saturating_duration_since(at) < save.frame_time)
+            {
+                return Ok(());
+            } else {
+                self.sent_at = Some(now);
+            }
+            self.dark = !save.lit(now);
+        } else {
+            self.dark = false;
+        }
+        // Brightness and white balance together, per channel in GRB order.
+        let brightness = u32::from(self.brightness.min(self.battery_cap));
+        let balance = self.white_balance;
+        let scale = [balance.green, balance.red, balance.blue]
+            .map(|factor| u32::from(factor) * brightness / 255);
+        for color in &frame {
             let c: palette::rgb::Rgb<palette::encoding::Srgb, u8> = color.into_format::<u8>();
             // WS2812 expects GRB byte order
-            for byte in [c.green, c.red, c.blue] {
+            for (byte, scale) in [c.green, c.red, c.blue].into_iter().zip(scale) {
+                let byte = if self.gamma {
+                    GAMMA[usize::from(byte)]
+                } else {
+                    byte
+                };
+                let byte = (u32::from(byte) * scale / 255) as u8;
                 let bp = Self::byte_to_pulses(byte);
                 pulses[idx..idx + 8].copy_from_slice(&bp);
                 idx += 8;
@@ -64,24 +453,69 @@ impl<'a> Leds<'a> {
         }
         pulses[idx] = PulseCode::end_marker();
 
-        let transaction = match channel.transmit(&pulses) {
-            Ok(t) => t,
-            Err(e) => {
-                error!("RMT transmit failed: {}", e);
-                return;
-            }
-        };
-
-        self.channel = Some(match transaction.wait() {
-            Ok(ch) => ch,
-            Err((err, ch)) => {
-                error!("RMT transaction failed: {}", err);
-                ch
-            }
-        });
+        let result = self.channel.transmit(&pulses).await.map_err(LedError::Rmt);
 
         // WS2812 reset time
         Timer::after(Duration::from_micros(50)).await;
+        result
+    }
+
+    /// Crossfade from the colours on the LEDs now to `frame` over
+    /// `duration`. Each [`update`](Self::update) sends the next step, so
+    /// keep updating, say once a frame, until it's done.
+    pub fn fade_to(&mut self, frame: [Srgb<u8>; LED_COUNT], duration: Duration) {
+        self.framebuffer = frame;
+        self.crossfade(duration);
+    }
+
+    /// Crossfade from the colours on the LEDs now to the colours set since,
+    /// over `duration`. A fade already heading for the same colours carries
+    /// on, so a game can set its LEDs and call this every frame:
+    ///
+    /// ```rust,ignore
+    /// update_leds(leds, &game);
+    /// leds.crossfade(Duration::from_millis(150));
+    /// leds.update().await;
+    /// ```
+    pub fn crossfade(&mut self, duration: Duration) {
+        let to = self.framebuffer;
+        match self.fade {
+            Some(fade) if fade.to == to => return,
+            None if self.shown == to => return,
+            _ => {}
+        }
+        self.fade = Some(Fade {
+            from: self.shown,
+            to,
+            start: Instant::now(),
+            duration,
+        });
+    }
+
+    /// `true` while a fade is under way.
+    pub const fn is_fading(&self) -> bool {
+        self.fade.is_some()
+    }
+
+    /// The colours to show at `now`, ending the fade once it's done.
+    fn faded(&mut self, now: Instant) -> [Srgb<u8>; LED_COUNT] {
+        let Some(fade) = self.fade else {
+            return self.framebuffer;
+        };
+        let total = fade.duration.as_micros().max(1);
+        let elapsed = now.saturating_duration_since(fade.start).as_micros();
+        if elapsed >= total {
+            self.fade = None;
+            return self.framebuffer;
+        }
+        let amount = (elapsed * 256 / total) as u32;
+        core::array::from_fn(|i| mix(fade.from[i], self.framebuffer[i], amount))
+    }
+
+    /// The colours set on the LEDs, e.g. to restore them later with
+    /// [`fill_from_iter`](Self::fill_from_iter).
+    pub const fn colors(&self) -> [Srgb<u8>; LED_COUNT] {
+        self.framebuffer
     }
 
     /// Set a single LED by index.
@@ -106,6 +540,54 @@ impl<'a> Leds<'a> {
         }
     }
 
+    /// Set a single LED from hue (degrees, wrapping at 360), saturation and
+    /// value. See [`hsv_to_srgb`].
+    pub fn set_hsv(&mut self, index: usize, hue: u16, saturation: u8, value: u8) {
+        self.framebuffer[index] = hsv_to_srgb(hue, saturation, value);
+    }
+
+    /// Spread a rainbow round the strip, starting at `hue` and moving
+    /// `step` degrees per LED. Increase `hue` each frame to cycle it.
+    pub fn fill_rainbow(&mut self, hue: u16, step: u16, value: u8) {
+        for (i, led) in self.framebuffer.iter_mut().enumerate() {
+            let hue = u32::from(hue) + i as u32 * u32::from(step);
+            *led = hsv_to_srgb((hue % 360) as u16, 255, value);
+        }
+    }
+
+    /// Where LED `index` is, in pixels of the default landscape screen.
+    /// LEDs are just off its left and right edges.
+    pub const fn position(&self, index: usize) -> Point {
+        POSITIONS[index]
+    }
+
+    /// The LED closest to `point` on the screen, e.g. to light the LED
+    /// nearest the ball.
+    pub fn nearest(&self, point: Point) -> usize {
+        let distance = |led: &Point| {
+            let d = *led - point;
+            i64::from(d.x) * i64::from(d.x) + i64::from(d.y) * i64::from(d.y)
+        };
+        (0..LED_COUNT)
+            .min_by_key(|&i| distance(&POSITIONS[i]))
+            .unwrap_or(0)
+    }
+
+    /// The LED in the direction of `degrees` from the middle of the screen,
+    /// clockwise from 12 o'clock, for effects that go round the badge.
+    pub fn at_angle(&self, degrees: u16) -> usize {
+        let angle = i32::from(degrees % 360) * 1024 / 360;
+        self.nearest(on_dial(Point::new(160, 85), angle, 10_000))
+    }
+
+    /// Set every LED to `color_at` its [`position`](Self::position), for
+    /// effects laid out in space such as a wave rising up both bars.
+    pub fn fill_by_position(&mut self, mut color_at: impl FnMut(Point) -> Srgb<u8>) {
+        for (led, &position) in self.framebuffer.iter_mut().zip(&POSITIONS) {
+            *led = color_at(position);
+        }
+    }
+
     /// Set the right LED bar (5 LEDs).
     ///
     /// Colors are ordered bottom-to-top: index 0 is the bottom LED,
@@ -139,6 +621,21 @@ impl<'a> Leds<'a> {
         self.set_left_bar(colors);
     }
 
+    /// Show `left` and `right` (0 to 255) as a VU meter on the bars,
+    /// green at the bottom through yellow to red at the top.
+    ///
+    /// A level rises at once but falls back smoothly, and the highest LED
+    /// reached stays lit a moment before falling after it, so a meter fed
+    /// from noisy audio or a jumping score still reads well. Call it once
+    /// a frame, even when the levels haven't changed, to keep it falling.
+    pub fn set_vu(&mut self, left: u8, right: u8) {
+        let now = Instant::now();
+        let left = self.vu[0].update(left, now);
+        let right = self.vu[1].update(right, now);
+        self.set_left_bar(&left);
+        self.set_right_bar(&right);
+    }
+
     /// Number of LEDs on the strip.
     pub const fn len(&self) -> usize {
         LED_COUNT
@@ -165,3 +662,75 @@ impl<'a> Leds<'a> {
         pulses
     }
 }
+
+// ── Colour helpers ──────────────────────────────────────────────────────────
+
+/// `from` moved `amount` 256ths of the way to `to`.
+pub(crate) fn mix(from: Srgb<u8>, to: Srgb<u8>, amount: u32) -> Srgb<u8> {
+    let channel = |a: u8, b: u8| {
+        let (a, b) = (i32::from(a), i32::from(b));
+        (a + (b - a) * amount as i32 / 256) as u8
+    };
+    Srgb::new(
+        channel(from.red, to.red),
+        channel(from.green, to.green),
+        channel(from.blue, to.blue),
+    )
+}
+
+/// A colour from hue in degrees (0 red, 120 green, 240 blue, wrapping at
+/// 360), saturation (0 grey to 255 pure) and value (0 black to 255 full).
+pub const fn hsv_to_srgb(hue: u16, saturation: u8, value: u8) -> Srgb<u8> {
+    let hue = (hue % 360) as u32;
+    let (s, v) = (saturation as u32, value as u32);
+    let rem = (hue % 60) * 255 / 60;
+    let p = (v * (255 - s) / 255) as u8;
+    let q = (v * (255 - s * rem / 255) / 255) as u8;
+    let t = (v * (255 - s * (255 - rem) / 255) / 255) as u8;
+    let v = value;
+    match hue / 60 {
+        0 => Srgb::new(v, t, p),
+        1 => Srgb::new(q, v, p),
+        2 => Srgb::new(p, v, t),
+        3 => Srgb::new(p, q, v),
+        4 => Srgb::new(t, p, v),
+        _ => Srgb::new(v, p, q),
+    }
+}
+
+/// Hue in degrees, saturation and value of `color`, the inverse of
+/// [`hsv_to_srgb`]. Greys have hue 0.
+pub const fn srgb_to_hsv(color: Srgb<u8>) -> (u16, u8, u8) {
+    let (r, g, b) = (color.red as i32, color.green as i32, color.blue as i32);
+    let max = if r > g { r } else { g };
+    let max = if max > b { max } else { b };
+    let min = if r < g { r } else { g };
+    let min = if min < b { min } else { b };
+    let delta = max - min;
+    if delta == 0 {
+        return (0, 0, max as u8);
+    }
+    let hue = if max == r {
+        60 * (g - b) / delta
+    } else if max == g {
+        120 + 60 * (b - r) / delta
+    } else {
+        240 + 60 * (r - g) / delta
+    };
+    (
+        hue.rem_euclid(360) as u16,
+        (delta * 255 / max) as u8,
+        max as u8,
+    )
+}
+
+/// `color` with its hue turned `degrees` round the colour wheel, keeping
+/// its saturation and brightness.
+pub const fn rotate_hue(color: Srgb<u8>, degrees: u16) -> Srgb<u8> {
+    let (hue, saturation, value) = srgb_to_hsv(color);
+    hsv_to_srgb(
+        ((hue as u32 + degrees as u32) % 360) as u16,
+        saturation,
+        value,
+    )
+}
@@ -0,0 +1,320 @@
+//! A plain-text level format for grid-based games.
+//!
+//! A level is a header of `key = value` lines, a `---` line, then the grid,
+//! one row per line. Single-character keys form the legend: they say what a
+//! grid cell with that character means. The values are left to the game to
+//! interpret; [`parse_color`] helps with colours.
+//!
+//! ```text
+//! # The classic wall, with a tougher middle row.
+//! name = Rainbow
+//! R = ff0000
+//! G = 00ff00 hits=2
+//! ---
+//! RRRRRRRRRR
+//! GGGG..GGGG
+//! ```
+//!
+//! Lines starting with `#` are comments in the header only, so `#` can be
+//! used in the grid. Rows may be ragged; missing cells read as a space.
+//!
+//! Levels borrow from the text, so they can be parsed straight out of a
+//! flash region or an `include_bytes!` without copying:
+//!
+//! ```rust,ignore
+//! let level = Level::from_bytes(include_bytes!("levels/rainbow.txt"))?;
+//! for (y, row) in level.rows().enumerate() { /* ... */ }
+//! ```
+//!
+//! A [`Pack`] holds several levels in one file, separated by `===` lines.
+//!
+//! The header is a [`Document`], the `key = value` format that
+//! [`watch_face`](crate::watch_face) and [`led_pattern`](crate::led_pattern)
+//! are built on too.
+
+use core::str::FromStr;
+
+use embedded_graphics::{
+    pixelcolor::Rgb565,
+    prelude::Point,
+};
+
+/// Line separating the header from the grid.
+const GRID_START: &str = "---";
+
+/// Line separating the levels of a pack.
+const LEVEL_END: &str = "===";
+
+/// Why a level or other [`Document`] can't be parsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum Error {
+    /// The data is not UTF-8.
+    NotUtf8,
+    /// A line, numbered from 1, is not `key = value`, or not an entry the
+    /// document's format knows.
+    BadLine(usize),
+    /// There is no `---` line, or no rows after it.
+    NoGrid,
+    /// There is nothing to show or play.
+    Empty,
+}
+
+/// Lines of `key = value`, with blank lines and `#` comments skipped,
+/// borrowing its text.
+#[derive(Clone, Copy)]
+pub struct Document<'a> {
+    text: &'a str,
+}
+
+impl<'a> Document<'a> {
+    /// Parse `text`, checking every entry with `valid`.
+    pub fn parse(
+        text: &'a str,
+        mut valid: impl FnMut((&'a str, &'a str)) -> bool,
+    ) -> Result<Self, Error> {
+        for (i, line) in text.lines().enumerate() {
+            if !is_blank(line) && !header_entry(line).is_some_and(&mut valid) {
+                return Err(Error::BadLine(i + 1));
+            }
+        }
+        Ok(Self { text })
+    }
+
+    /// Parse a document from raw bytes, such as a flash region or a buffer
+    /// filled at run time.
+    pub fn from_bytes(
+        data: &'a [u8],
+        valid: impl FnMut((&'a str, &'a str)) -> bool,
+    ) -> Result<Self, Error> {
+        Self::parse(from_utf8(data)?, valid)
+    }
+
+    /// The value of the first entry called `key`.
+    pub fn get(&self, key: &str) -> Option<&'a str> {
+        self.entries().find(|&(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// The document's `name`, if it has one.
+    pub fn name(&self) -> Option<&'a str> {
+        self.get("name")
+    }
+
+    /// Every entry as `(key, value)`, in file order.
+    pub fn entries(&self) -> impl Iterator<Item = (&'a str, &'a str)> + use<'a> {
+        self.text
+            .lines()
+            .filter(|line| !is_blank(line))
+            .filter_map(header_entry)
+    }
+}
+
+/// A parsed level, borrowing its text.
+#[derive(Clone, Copy)]
+pub struct Level<'a> {
+    header: Document<'a>,
+    grid: &'a str,
+    width: usize,
+    height: usize,
+}
+
+impl<'a> Level<'a> {
+    /// Parse a level from `text`.
+    pub fn parse(text: &'a str) -> Result<Self, Error> {
+        let (header, grid) = split_at_line(text, GRID_START).ok_or(Error::NoGrid)?;
+        let header = Document::parse(header, |_| true)?;
+        let grid = grid.trim_end();
+        let (mut width, mut height) = (0, 0);
+        for row in grid.lines() {
+            width = width.max(row.trim_end().chars().count());
+            height += 1;
+        }
+        if width == 0 {
+            return Err(Error::NoGrid);
+        }
+        Ok(Self {
+            header,
+            grid,
+            width,
+            height,
+        })
+    }
+
+    /// Parse a level from raw bytes, such as a flash region.
+    pub fn from_bytes(data: &'a [u8]) -> Result<Self, Error> {
+        Self::parse(from_utf8(data)?)
+    }
+
+    /// The value of the first header entry called `key`.
+    pub fn get(&self, key: &str) -> Option<&'a str> {
+        self.header.get(key)
+    }
+
+    /// The legend entry for grid character `cell`.
+    pub fn legend(&self, cell: char) -> Option<&'a str> {
+        self.entries()
+            .find(|&(k, _)| k.chars().eq([cell]))
+            .map(|(_, v)| v)
+    }
+
+    /// Every header entry as `(key, value)`, in file order.
+    pub fn entries(&self) -> impl Iterator<Item = (&'a str, &'a str)> + use<'a> {
+        self.header.entries()
+    }
+
+    /// Width of the widest row, in cells.
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows.
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The grid rows, top first, without trailing whitespace.
+    pub fn rows(&self) -> impl Iterator<Item = &'a str> + use<'a> {
+        self.grid.lines().map(str::trim_end)
+    }
+
+    /// The character at column `x` of row `y`, or a space outside the grid.
+    pub fn cell(&self, x: usize, y: usize) -> char {
+        self.rows()
+            .nth(y)
+            .and_then(|row| row.chars().nth(x))
+            .unwrap_or(' ')
+    }
+}
+
+/// Several levels in one text, separated by `===` lines.
+///
+/// Each level is only parsed when it is asked for, and line numbers in its
+/// errors count from the start of that level.
+#[derive(Clone, Copy)]
+pub struct Pack<'a> {
+    text: &'a str,
+}
+
+impl<'a> Pack<'a> {
+    /// A pack of the levels in `text`.
+    pub const fn new(text: &'a str) -> Self {
+        Self { text }
+    }
+
+    /// A pack from raw bytes, such as a flash region.
+    pub fn from_bytes(data: &'a [u8]) -> Result<Self, Error> {
+        Ok(Self::new(from_utf8(data)?))
+    }
+
+    /// Number of levels.
+    pub fn len(&self) -> usize {
+        self.sections().count()
+    }
+
+    /// `true` if the pack has no levels.
+    pub fn is_empty(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    /// Parse level `index`, counting from 0.
+    pub fn get(&self, index: usize) -> Option<Result<Level<'a>, Error>> {
+        self.sections().nth(index).map(Level::parse)
+    }
+
+    /// Parse every level in order.
+    pub fn levels(&self) -> impl Iterator<Item = Result<Level<'a>, Error>> + use<'a> {
+        self.sections().map(Level::parse)
+    }
+
+    fn sections(&self) -> impl Iterator<Item = &'a str> + use<'a> {
+        let mut rest = (!self.is_empty()).then_some(self.text);
+        core::iter::from_fn(move || {
+            let text = rest?;
+            let (level, next) = split_at_line(text, LEVEL_END).unwrap_or((text, ""));
+            // A trailing `===` doesn't start another level.
+            rest = (!next.trim().is_empty()).then_some(next);
+            Some(level)
+        })
+    }
+}
+
+/// Parse a hex colour, `rrggbb` or `rgb`, with an optional leading `#`.
+pub fn parse_color(s: &str) -> Option<Rgb565> {
+    let (r, g, b) = parse_rgb(s)?;
+    Some(Rgb565::new(r >> 3, g >> 2, b >> 3))
+}
+
+/// Parse a hex colour as for [`parse_color`], at 8 bits a channel.
+pub(crate) fn parse_rgb(s: &str) -> Option<(u8, u8, u8)> {
+    let s = s.strip_prefix('#').unwrap_or(s);
+    let value = u32::from_str_radix(s, 16).ok()?;
+    let (r, g, b) = match s.len() {
+        6 => (value >> 16, value >> 8 & 0xff, value & 0xff),
+        3 => (
+            (value >> 8) * 0x11,
+            (value >> 4 & 0xf) * 0x11,
+            (value & 0xf) * 0x11,
+        ),
+        _ => return None,
+    };
+    Some((r as u8, g as u8, b as u8))
+}
+
+/// The whitespace-separated words of an entry's value.
+pub(crate) struct Words<'a>(pub(crate) &'a str);
+
+impl<'a> Words<'a> {
+    pub(crate) fn next(&mut self) -> Option<&'a str> {
+        let rest = self.0.trim_start();
+        if rest.is_empty() {
+            return None;
+        }
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let (word, rest) = rest.split_at(end);
+        self.0 = rest;
+        Some(word)
+    }
+
+    pub(crate) fn number<T: FromStr>(&mut self) -> Option<T> {
+        self.next()?.parse().ok()
+    }
+
+    pub(crate) fn point(&mut self) -> Option<Point> {
+        Some(Point::new(self.number()?, self.number()?))
+    }
+
+    pub(crate) fn color(&mut self) -> Option<Rgb565> {
+        parse_color(self.next()?)
+    }
+
+    /// `parsed`, if it used every word. Anything left over is a mistake.
+    pub(crate) fn end<T>(mut self, parsed: T) -> Option<T> {
+        self.next().is_none().then_some(parsed)
+    }
+}
+
+fn from_utf8(data: &[u8]) -> Result<&str, Error> {
+    core::str::from_utf8(data).map_err(|_| Error::NotUtf8)
+}
+
+/// The text before and after the first line reading `separator`.
+fn split_at_line<'a>(text: &'a str, separator: &str) -> Option<(&'a str, &'a str)> {
+    let mut start = 0;
+    for line in text.split_inclusive('\n') {
+        if line.trim_end() == separator {
+            return Some((&text[..start], &text[start + line.len()..]));
+        }
+        start += line.len();
+    }
+    None
+}
+
+fn is_blank(line: &str) -> bool {
+    let line = line.trim_start();
+    line.is_empty() || line.starts_with('#')
+}
+
+fn header_entry(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_once('=')?;
+    let key = key.trim();
+    (!key.is_empty()).then(|| (key, value.trim()))
+}
@@ -6,7 +6,7 @@
 //! - **Display**: 320×170 ST7789 LCD over SPI with DMA
 //! - **Buttons**: 9-button input (D-pad, A/B, Start/Select, joystick click) with debouncing
 //! - **LEDs**: 10× WS2812 addressable RGB LEDs via RMT
-//! - **Backlight**: Display backlight control
+//! - **Backlight**: Display backlight with PWM brightness control
 //! - **Vibration motor**: Haptic feedback
 //! - **Microphone**: I2S MEMS microphone input
 //!
@@ -23,19 +23,88 @@
 
 #![no_std]
 
+#[cfg(any(feature = "png", feature = "psram"))]
+extern crate alloc;
+// Lets the asset macros' `::disobey2026badge` paths resolve in this crate too.
+extern crate self as disobey2026badge;
+
+mod auto_dim;
 mod backlight;
-mod buttons;
+pub mod buttons;
+mod canvas;
+pub mod clock;
+mod compositor;
+mod console;
+pub mod dial;
 mod display;
+pub mod flashlight;
+pub mod font;
+mod frame_ticker;
+mod framebuffer;
+pub mod gradient;
+pub mod image;
+mod led_animator;
+mod led_palette;
+pub mod led_pattern;
+mod led_service;
 mod leds;
+pub mod level;
+mod marquee;
+mod meter;
 pub mod microphone;
+pub mod notification;
+#[cfg(feature = "png")]
+pub mod png;
+mod pov;
+pub mod profiler;
+pub mod pwm;
+pub mod qr;
+mod scaled_display;
+pub mod schedule;
+pub mod screensaver;
+pub mod selftest;
+pub mod serial;
+mod seven_segment;
+pub mod sprite;
+mod stroke_font;
+mod text_box;
+mod ticks;
+mod timeline;
+pub mod totp;
+pub mod transition;
+pub mod ui;
 mod vibration;
+pub mod watch_face;
 
+pub use auto_dim::AutoDim;
 pub use backlight::Backlight;
-pub use buttons::Buttons;
-pub use display::Display;
+pub use buttons::{
+    Button,
+    Buttons,
+    Chord,
+    ComboError,
+    Combos,
+    Sequence,
+};
+pub use canvas::Canvas;
+pub use compositor::{
+    Compositor,
+    Layer,
+};
+pub use console::Console;
+pub use disobey2026badge_macros::{
+    badge_font,
+    badge_image,
+    badge_paletted_image,
+    include_rgb565,
+};
+pub use display::{
+    Display,
+    Orientation,
+    Rotation,
+};
 use esp_hal::{
     Async,
-    Blocking,
     assign_resources,
     clock::{
         Clock,
@@ -55,11 +124,55 @@ use esp_hal::{
     rom,
     time::Rate,
 };
+pub use frame_ticker::FrameTicker;
+pub use framebuffer::Framebuffer;
+pub use led_animator::{
+    Effect,
+    LedAnimator,
+};
+pub use led_palette::{
+    Palette16,
+    Palette256,
+};
+pub use led_pattern::LedPattern;
+pub use led_service::{
+    LedHandle,
+    LedService,
+    led_service_task,
+};
 pub use leds::{
     BAR_COUNT,
+    BATTERY_LIMITS,
+    LedError,
     Leds,
+    PowerSave,
+    RMT_BLOCKS,
+    WhiteBalance,
+    hsv_to_srgb,
+    rotate_hue,
+    srgb_to_hsv,
+};
+pub use marquee::Marquee;
+pub use meter::{
+    Gauge,
+    ProgressBar,
 };
 pub use microphone::Microphone;
+pub use pov::Pov;
+pub use scaled_display::ScaledDisplay;
+pub use seven_segment::SevenSegment;
+pub use stroke_font::StrokeText;
+pub use text_box::TextBox;
+pub use ticks::{
+    Cooldown,
+    Periodic,
+};
+pub use timeline::{
+    Ease,
+    Keyframe,
+    Timeline,
+    Tween,
+};
 pub use vibration::Vibration;
 
 /// StaticCell helper — allocates a value into a `static` exactly once.
@@ -89,6 +202,7 @@ assign_resources! {
         },
         backlight: BacklightResources<'d> {
             led: GPIO19,
+            ledc: LEDC,
         },
         buttons: ButtonResources<'d> {
             up: GPIO11,
@@ -166,20 +280,13 @@ impl From<esp_hal::peripherals::Peripherals> for Resources<'_> {
     }
 }
 
-impl<'a> From<LedResources<'a>> for esp_hal::rmt::Channel<'a, Blocking, Tx> {
-    fn from(res: LedResources<'a>) -> Self {
-        let _ws_power = Output::new(res.power, Level::High, OutputConfig::default());
-        let rmt = Rmt::new(res.rmt, Rate::from_mhz(40)).unwrap();
-        let tx_config = TxChannelConfig::default().with_clk_divider(1);
-        rmt.channel0.configure_tx(res.io, tx_config).unwrap()
-    }
-}
-
 impl<'a> From<LedResources<'a>> for esp_hal::rmt::Channel<'a, Async, Tx> {
     fn from(res: LedResources<'a>) -> Self {
         let _ws_power = Output::new(res.power, Level::High, OutputConfig::default());
         let rmt = Rmt::new(res.rmt, Rate::from_mhz(40)).unwrap().into_async();
-        let tx_config = TxChannelConfig::default().with_clk_divider(1);
+        let tx_config = TxChannelConfig::default()
+            .with_clk_divider(1)
+            .with_memsize(leds::RMT_BLOCKS);
         rmt.channel0.configure_tx(res.io, tx_config).unwrap()
     }
 }
@@ -0,0 +1,226 @@
+//! Horizontally scrolling text marquee.
+
+use core::convert::Infallible;
+
+use embedded_graphics::{
+    Pixel,
+    image::{
+        Image,
+        ImageDrawable,
+    },
+    mono_font::{
+        MonoFont,
+        MonoTextStyle,
+    },
+    pixelcolor::{
+        BinaryColor,
+        Rgb565,
+    },
+    prelude::*,
+    primitives::Rectangle,
+    text::{
+        Baseline,
+        Text,
+    },
+};
+
+/// A scrolling banner of text and 1-bit icons.
+///
+/// Content is rendered once into a 1-bit strip. Every [`draw`](Self::draw)
+/// scales the visible part of the strip up and pushes it as a single window
+/// write, so even screen-sized lettering scrolls smoothly. The strip wraps
+/// around seamlessly; add a [`push_space`](Self::push_space) at the end to
+/// separate repetitions.
+///
+/// ```rust,ignore
+/// let mut strip = [0u8; 4096];
+/// let mut marquee = Marquee::new(&mut strip, 20).with_scale(7);
+/// marquee.push_text("Hello badge!", &FONT_10X20).push_space(40);
+/// loop {
+///     marquee.draw(display, Point::new(0, 15), 320)?;
+///     marquee.step(4);
+///     Timer::after_millis(20).await;
+/// }
+/// ```
+pub struct Marquee<'a> {
+    bits: &'a mut [u8],
+    capacity: u32,
+    height: u32,
+    len: u32,
+    scale: u32,
+    fg: Rgb565,
+    bg: Rgb565,
+    position: u32,
+}
+
+impl<'a> Marquee<'a> {
+    /// Create an empty marquee whose strip is `height` pixels tall.
+    ///
+    /// `buffer` holds the strip at one bit per pixel, so it fits
+    /// `buffer.len() * 8 / height` columns of content. Anything pushed past
+    /// that is cut off.
+    pub fn new(buffer: &'a mut [u8], height: u32) -> Self {
+        buffer.fill(0);
+        let capacity = (buffer.len() as u32 * 8).checked_div(height).unwrap_or(0);
+        Self {
+            bits: buffer,
+            capacity,
+            height,
+            len: 0,
+            scale: 1,
+            fg: Rgb565::WHITE,
+            bg: Rgb565::BLACK,
+            position: 0,
+        }
+    }
+
+    /// Draw every strip pixel as a `scale`×`scale` block.
+    #[must_use]
+    pub fn with_scale(mut self, scale: u32) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Set the text and background colours (white on black by default).
+    #[must_use]
+    pub fn with_colors(mut self, fg: Rgb565, bg: Rgb565) -> Self {
+        self.fg = fg;
+        self.bg = bg;
+        self
+    }
+
+    /// Append `text`, vertically centred in the strip.
+    pub fn push_text(&mut self, text: &str, font: &MonoFont<'_>) -> &mut Self {
+        let y = self.centered(font.character_size.height);
+        let style = MonoTextStyle::new(font, BinaryColor::On);
+        let Ok(next) =
+            Text::with_baseline(text, Point::new(self.len as i32, y), style, Baseline::Top)
+                .draw(&mut self.strip());
+        self.advance(next.x as u32);
+        self
+    }
+
+    /// Append a 1-bit icon, vertically centred in the strip.
+    pub fn push_icon<I>(&mut self, icon: &I) -> &mut Self
+    where
+        I: ImageDrawable<Color = BinaryColor>,
+    {
+        let size = icon.size();
+        let y = self.centered(size.height);
+        let Ok(()) = Image::new(icon, Point::new(self.len as i32, y)).draw(&mut self.strip());
+        self.advance(self.len + size.width);
+        self
+    }
+
+    /// Append `width` blank columns.
+    pub fn push_space(&mut self, width: u32) -> &mut Self {
+        self.advance(self.len + width);
+        self
+    }
+
+    /// Remove all content and rewind.
+    pub fn clear(&mut self) {
+        self.bits.fill(0);
+        self.len = 0;
+        self.position = 0;
+    }
+
+    /// Width of the content in strip pixels, before scaling.
+    pub const fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// `true` if nothing has been pushed yet.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Height of the drawn marquee in screen pixels.
+    pub const fn screen_height(&self) -> u32 {
+        self.height * self.scale
+    }
+
+    /// Scroll left by `pixels` screen pixels.
+    pub fn step(&mut self, pixels: u32) {
+        let period = self.len * self.scale;
+        if period > 0 {
+            self.position = (self.position + pixels) % period;
+        }
+    }
+
+    /// Draw the visible part, `width` screen pixels wide, at `top_left`.
+    pub fn draw<D>(&self, target: &mut D, top_left: Point, width: u32) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let area = Rectangle::new(top_left, Size::new(width, self.screen_height()));
+        if self.len == 0 {
+            return target.fill_solid(&area, self.bg);
+        }
+
+        let period = self.len * self.scale;
+        let colors = (0..self.screen_height()).flat_map(move |y| {
+            let row = (y / self.scale * self.capacity) as usize;
+            (0..width).map(move |x| {
+                let i = row + ((self.position + x) % period / self.scale) as usize;
+                if self.bits[i / 8] & (0x80 >> (i % 8)) != 0 {
+                    self.fg
+                } else {
+                    self.bg
+                }
+            })
+        });
+        target.fill_contiguous(&area, colors)
+    }
+
+    fn centered(&self, height: u32) -> i32 {
+        (self.height as i32 - height as i32) / 2
+    }
+
+    fn advance(&mut self, end: u32) {
+        self.len = end.min(self.capacity);
+    }
+
+    fn strip(&mut self) -> Strip<'_> {
+        Strip {
+            bits: self.bits,
+            size: Size::new(self.capacity, self.height),
+        }
+    }
+}
+
+/// Drawing target writing into the marquee's 1-bit strip.
+struct Strip<'a> {
+    bits: &'a mut [u8],
+    size: Size,
+}
+
+impl OriginDimensions for Strip<'_> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl DrawTarget for Strip<'_> {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounds = self.bounding_box();
+        for Pixel(point, color) in pixels {
+            if bounds.contains(point) {
+                let i = (point.y as u32 * self.size.width + point.x as u32) as usize;
+                let bit = 0x80 >> (i % 8);
+                if color.is_on() {
+                    self.bits[i / 8] |= bit;
+                } else {
+                    self.bits[i / 8] &= !bit;
+                }
+            }
+        }
+        Ok(())
+    }
+}
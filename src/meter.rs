@@ -0,0 +1,244 @@
+//! Progress bars and arc gauges.
+
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::{
+        Arc,
+        PrimitiveStyle,
+        PrimitiveStyleBuilder,
+        Rectangle,
+        StrokeAlignment,
+    },
+    text::{
+        Alignment,
+        Baseline,
+        Text,
+        TextStyleBuilder,
+    },
+};
+
+/// Colour of the unfilled part unless set otherwise.
+const TRACK: Rgb565 = Rgb565::CSS_DIM_GRAY;
+
+/// A label centred on a meter.
+type Label<'a> = (&'a str, MonoTextStyle<'a, Rgb565>);
+
+fn draw_label<D>(target: &mut D, label: Option<Label<'_>>, center: Point) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    let Some((text, style)) = label else {
+        return Ok(());
+    };
+    let text_style = TextStyleBuilder::new()
+        .alignment(Alignment::Center)
+        .baseline(Baseline::Middle)
+        .build();
+    Text::with_text_style(text, center, style, text_style).draw(target)?;
+    Ok(())
+}
+
+/// A horizontal bar filled from the left in proportion to `value / max`,
+/// for loading screens, downloads and battery meters.
+///
+/// The filled and empty parts are drawn side by side, so the bar can be
+/// redrawn in place as the value changes without flicker:
+///
+/// ```rust,ignore
+/// let area = Rectangle::new(Point::new(60, 120), Size::new(200, 16));
+/// ProgressBar::new(area, received, total)
+///     .with_border(Rgb565::WHITE, 1)
+///     .with_label("Loading", MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE))
+///     .draw(display)?;
+/// ```
+#[derive(Clone, Copy)]
+pub struct ProgressBar<'a> {
+    area: Rectangle,
+    value: u32,
+    max: u32,
+    fill: Rgb565,
+    background: Rgb565,
+    border: Option<(Rgb565, u32)>,
+    label: Option<Label<'a>>,
+}
+
+impl<'a> ProgressBar<'a> {
+    /// A green bar on grey filling `area`, `value` of `max` full. Values
+    /// above `max` show as full.
+    pub const fn new(area: Rectangle, value: u32, max: u32) -> Self {
+        Self {
+            area,
+            value,
+            max,
+            fill: Rgb565::GREEN,
+            background: TRACK,
+            border: None,
+            label: None,
+        }
+    }
+
+    /// Colours of the filled and the empty part.
+    #[must_use]
+    pub const fn with_colors(mut self, fill: Rgb565, background: Rgb565) -> Self {
+        self.fill = fill;
+        self.background = background;
+        self
+    }
+
+    /// A border `width` pixels wide inside the area, around the bar.
+    #[must_use]
+    pub const fn with_border(mut self, color: Rgb565, width: u32) -> Self {
+        self.border = Some((color, width));
+        self
+    }
+
+    /// Text centred on the bar.
+    #[must_use]
+    pub const fn with_label(mut self, text: &'a str, style: MonoTextStyle<'a, Rgb565>) -> Self {
+        self.label = Some((text, style));
+        self
+    }
+}
+
+impl Drawable for ProgressBar<'_> {
+    type Color = Rgb565;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let mut bar = self.area;
+        if let Some((color, width)) = self.border {
+            let style = PrimitiveStyleBuilder::new()
+                .stroke_color(color)
+                .stroke_width(width)
+                .stroke_alignment(StrokeAlignment::Inside)
+                .build();
+            self.area.into_styled(style).draw(target)?;
+            bar = self.area.offset(-(width as i32));
+        }
+        let width = bar.size.width;
+        let filled = if self.max == 0 {
+            width
+        } else {
+            (u64::from(width) * u64::from(self.value.min(self.max)) / u64::from(self.max)) as u32
+        };
+        let (left, right) = (
+            Rectangle::new(bar.top_left, Size::new(filled, bar.size.height)),
+            Rectangle::new(
+                bar.top_left + Point::new(filled as i32, 0),
+                Size::new(width - filled, bar.size.height),
+            ),
+        );
+        target.fill_solid(&left, self.fill)?;
+        target.fill_solid(&right, self.background)?;
+        draw_label(target, self.label, self.area.center())
+    }
+}
+
+/// An arc that fills clockwise in proportion to `value / max`, like a
+/// speedometer or a countdown ring.
+///
+/// By default the arc opens at the bottom and spans 270°:
+///
+/// ```rust,ignore
+/// Gauge::new(Point::new(160, 85), 100, level, 255)
+///     .with_label("72%", MonoTextStyle::new(&FONT_10X20, Rgb565::WHITE))
+///     .draw(display)?;
+/// ```
+#[derive(Clone, Copy)]
+pub struct Gauge<'a> {
+    center: Point,
+    diameter: u32,
+    value: u32,
+    max: u32,
+    start: Angle,
+    sweep: Angle,
+    thickness: u32,
+    fill: Rgb565,
+    background: Rgb565,
+    label: Option<Label<'a>>,
+}
+
+impl<'a> Gauge<'a> {
+    /// A green gauge on grey, `diameter` pixels across at `center` and
+    /// `value` of `max` full. Values above `max` show as full.
+    pub fn new(center: Point, diameter: u32, value: u32, max: u32) -> Self {
+        Self {
+            center,
+            diameter,
+            value,
+            max,
+            start: 135.0.deg(),
+            sweep: 270.0.deg(),
+            thickness: (diameter / 8).max(1),
+            fill: Rgb565::GREEN,
+            background: TRACK,
+            label: None,
+        }
+    }
+
+    /// Where the arc starts, clockwise from 3 o'clock, and how far it goes.
+    /// `-90°` and `360°` make a full ring starting at the top.
+    #[must_use]
+    pub const fn with_arc(mut self, start: Angle, sweep: Angle) -> Self {
+        self.start = start;
+        self.sweep = sweep;
+        self
+    }
+
+    /// Width of the arc in pixels.
+    #[must_use]
+    pub const fn with_thickness(mut self, thickness: u32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Colours of the filled and the empty part.
+    #[must_use]
+    pub const fn with_colors(mut self, fill: Rgb565, background: Rgb565) -> Self {
+        self.fill = fill;
+        self.background = background;
+        self
+    }
+
+    /// Text centred in the gauge.
+    #[must_use]
+    pub const fn with_label(mut self, text: &'a str, style: MonoTextStyle<'a, Rgb565>) -> Self {
+        self.label = Some((text, style));
+        self
+    }
+}
+
+impl Drawable for Gauge<'_> {
+    type Color = Rgb565;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let fraction = if self.max == 0 {
+            1.0
+        } else {
+            self.value.min(self.max) as f32 / self.max as f32
+        };
+        let sweep = self.sweep.to_degrees();
+        let filled = sweep * fraction;
+        let mut arc = |start: f32, sweep: f32, color| {
+            if sweep == 0.0 {
+                return Ok(());
+            }
+            Arc::with_center(self.center, self.diameter, start.deg(), sweep.deg())
+                .into_styled(PrimitiveStyle::with_stroke(color, self.thickness))
+                .draw(target)
+        };
+        let start = self.start.to_degrees();
+        arc(start, filled, self.fill)?;
+        arc(start + filled, sweep - filled, self.background)?;
+        draw_label(target, self.label, self.center)
+    }
+}
@@ -0,0 +1,219 @@
+//! LED notifications that briefly take over some LEDs.
+//!
+//! Any task can [`post`] a [`Notification`], such as a blink for an
+//! incoming message, without access to the [`Leds`](crate::Leds). Every
+//! [`Leds::update`](crate::Leds::update) draws the active notifications
+//! over the app's colours, and once one runs out the app's colours show
+//! again. The app doesn't need to know:
+//!
+//! ```rust,ignore
+//! notification::post(
+//!     Notification::new(Srgb::new(0, 0, 255), Duration::from_secs(2))
+//!         .with_leds(notification::LEFT_BAR)
+//!         .with_blink(Duration::from_millis(250)),
+//! );
+//! ```
+//!
+//! Notifications show only as often as something updates the LEDs, so an
+//! app that sets them once and stops should keep updating, or share them
+//! through a [`LedService`](crate::LedService), which refreshes them on
+//! its own.
+
+use core::cell::RefCell;
+
+use embassy_sync::blocking_mutex::{
+    Mutex,
+    raw::CriticalSectionRawMutex,
+};
+use embassy_time::{
+    Duration,
+    Instant,
+};
+use palette::Srgb;
+
+use crate::{
+    BAR_COUNT,
+    leds::LED_COUNT,
+};
+
+/// Notifications that can be active at once.
+const SLOTS: usize = 4;
+
+/// Mask of every LED, for [`Notification::with_leds`].
+pub const ALL: u16 = (1 << LED_COUNT) - 1;
+/// Mask of the right bar's LEDs.
+pub const RIGHT_BAR: u16 = 0b00000_11111;
+/// Mask of the left bar's LEDs.
+pub const LEFT_BAR: u16 = 0b11111_00000;
+
+/// Mask of the bottom `count` LEDs of each bar, such as for a level.
+pub const fn bars_from_bottom(count: usize) -> u16 {
+    let count = if count > BAR_COUNT { BAR_COUNT } else { count };
+    let bar = (1 << count) - 1;
+    // The right bar counts up from its bottom LED, the left bar down to it.
+    bar | bar << (LED_COUNT - count)
+}
+
+/// Which notification wins where two cover the same LED.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, defmt::Format)]
+pub enum Priority {
+    /// Something that can wait, such as charging finished.
+    Low,
+    /// The default: a message came in.
+    Normal,
+    /// Needs attention soon, such as a low battery.
+    High,
+    /// Needs attention now.
+    Critical,
+}
+
+/// A colour shown on some LEDs for a while.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Notification {
+    color: Srgb<u8>,
+    leds: u16,
+    duration: Duration,
+    blink: Duration,
+    priority: Priority,
+}
+
+impl Notification {
+    /// `color` steady on every LED for `duration`, at normal priority.
+    pub const fn new(color: Srgb<u8>, duration: Duration) -> Self {
+        Self {
+            color,
+            leds: ALL,
+            duration,
+            blink: Duration::from_ticks(0),
+            priority: Priority::Normal,
+        }
+    }
+
+    /// Only take over the LEDs set in `mask`, bit `i` for LED `i`, such as
+    /// [`LEFT_BAR`].
+    #[must_use]
+    pub const fn with_leds(mut self, mask: u16) -> Self {
+        self.leds = mask & ALL;
+        self
+    }
+
+    /// Blink instead of staying lit, on for the first half of each
+    /// `period`. The app's colours show in the off half.
+    #[must_use]
+    pub const fn with_blink(mut self, period: Duration) -> Self {
+        self.blink = period;
+        self
+    }
+
+    /// Win over notifications of lower priority on the same LEDs.
+    #[must_use]
+    pub const fn with_priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// `true` if it covers LED `index` at `elapsed` since it was posted.
+    const fn lit(&self, index: usize, elapsed: Duration) -> bool {
+        if self.leds & (1 << index) == 0 {
+            return false;
+        }
+        let period = self.blink.as_micros();
+        period == 0 || elapsed.as_micros() % period < period / 2
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Active {
+    notification: Notification,
+    start: Instant,
+}
+
+static ACTIVE: Mutex<CriticalSectionRawMutex, RefCell<[Option<Active>; SLOTS]>> =
+    Mutex::new(RefCell::new([None; SLOTS]));
+
+/// Show `notification` from the next LED update.
+///
+/// Up to four can be active at once; when all are, it replaces the one
+/// with the lowest priority, as long as that's no higher than its own.
+/// Returns `false` if it was dropped instead.
+pub fn post(notification: Notification) -> bool {
+    let now = Instant::now();
+    ACTIVE.lock(|active| {
+        let mut active = active.borrow_mut();
+        let expired = |slot: &Option<Active>| {
+            slot.is_none_or(|a| now.saturating_duration_since(a.start) >= a.notification.duration)
+        };
+        let slot = match active.iter().position(expired) {
+            Some(free) => free,
+            None => {
+                let lowest = (0..SLOTS)
+                    .min_by_key(|&i| active[i].map(|a| a.notification.priority))
+                    .unwrap_or(0);
+                if active[lowest].is_some_and(|a| a.notification.priority > notification.priority) {
+                    return false;
+                }
+                lowest
+            }
+        };
+        active[slot] = Some(Active {
+            notification,
+            start: now,
+        });
+        true
+    })
+}
+
+/// End `notification` early, if it's showing.
+pub fn dismiss(notification: Notification) {
+    ACTIVE.lock(|active| {
+        for slot in active.borrow_mut().iter_mut() {
+            if slot.is_some_and(|a| a.notification == notification) {
+                *slot = None;
+            }
+        }
+    });
+}
+
+/// End every notification early.
+pub fn dismiss_all() {
+    ACTIVE.lock(|active| *active.borrow_mut() = [None; SLOTS]);
+}
+
+/// `true` while any notification is showing.
+pub fn is_active() -> bool {
+    let now = Instant::now();
+    ACTIVE.lock(|active| {
+        active
+            .borrow()
+            .iter()
+            .flatten()
+            .any(|a| now.saturating_duration_since(a.start) < a.notification.duration)
+    })
+}
+
+/// Draw the notifications active at `now` over `frame`, forgetting the
+/// ones that have run out.
+pub(crate) fn apply(frame: &mut [Srgb<u8>; LED_COUNT], now: Instant) {
+    ACTIVE.lock(|active| {
+        let mut active = active.borrow_mut();
+        let mut winner: [Option<Priority>; LED_COUNT] = [None; LED_COUNT];
+        for slot in active.iter_mut() {
+            let Some(a) = *slot else {
+                continue;
+            };
+            let elapsed = now.saturating_duration_since(a.start);
+            if elapsed >= a.notification.duration {
+                *slot = None;
+                continue;
+            }
+            for (i, led) in frame.iter_mut().enumerate() {
+                if a.notification.lit(i, elapsed)
+                    && winner[i].is_none_or(|p| a.notification.priority >= p)
+                {
+                    *led = a.notification.color;
+                    winner[i] = Some(a.notification.priority);
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,358 @@
+//! Runtime PNG decoding, enabled by the `png` feature.
+//!
+//! For images known at build time, [`badge_image!`](crate::badge_image) is
+//! smaller and faster. This is for PNGs that only turn up at runtime, such
+//! as ones received over the radio or stored in flash.
+//!
+//! Rows are inflated and drawn one at a time, so decoding needs about
+//! 45 KiB of heap for the deflate state, plus up to 10 bytes per pixel of
+//! width for two rows and their colours: at most 10 KiB, as images are
+//! limited to [`MAX_SIZE`] pixels a side. 8-bit images of every colour type are supported, as are 1, 2 and 4-bit
+//! greyscale and palette images. 16-bit and interlaced images are not.
+//!
+//! ```rust,ignore
+//! let png = Png::new(&data)?;
+//! Image::new(&png, Point::zero()).draw(display)?;
+//! ```
+
+use alloc::vec;
+
+use embedded_graphics::{
+    image::ImageDrawable,
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::Rectangle,
+};
+use miniz_oxide::{
+    DataFormat,
+    MZError,
+    MZFlush,
+    MZStatus,
+    inflate::stream::{
+        InflateState,
+        inflate,
+    },
+};
+
+const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Largest width or height decoded. Anything bigger is refused as
+/// [`DecodeError::Unsupported`] rather than allocating row buffers for it.
+pub const MAX_SIZE: u32 = 1024;
+
+/// Why a PNG can't be decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum DecodeError {
+    /// The data is not a PNG file, or it is truncated or corrupt.
+    Invalid,
+    /// A valid PNG this decoder can't handle (16-bit, interlaced or wider
+    /// or taller than [`MAX_SIZE`]).
+    Unsupported,
+}
+
+/// Error returned by [`Png::draw_checked`].
+#[derive(Debug)]
+pub enum Error<E> {
+    /// The image data is bad.
+    Decode(DecodeError),
+    /// The draw target failed.
+    Draw(E),
+}
+
+/// A PNG file in memory, drawn by decoding it on the fly.
+#[derive(Clone, Copy)]
+pub struct Png<'a> {
+    data: &'a [u8],
+    width: u32,
+    height: u32,
+    color_type: u8,
+    bit_depth: u8,
+    palette: &'a [u8],
+    transparency: &'a [u8],
+}
+
+impl<'a> Png<'a> {
+    /// Parse the header of the PNG file in `data`.
+    ///
+    /// The pixel data is only checked when drawing.
+    pub fn new(data: &'a [u8]) -> Result<Self, DecodeError> {
+        let body = data.strip_prefix(SIGNATURE).ok_or(DecodeError::Invalid)?;
+        let mut png = Self {
+            data: body,
+            width: 0,
+            height: 0,
+            color_type: 0,
+            bit_depth: 0,
+            palette: &[],
+            transparency: &[],
+        };
+        for (i, chunk) in chunks(body).enumerate() {
+            let (kind, content) = chunk?;
+            match (kind, content) {
+                (
+                    b"IHDR",
+                    &[
+                        w0,
+                        w1,
+                        w2,
+                        w3,
+                        h0,
+                        h1,
+                        h2,
+                        h3,
+                        depth,
+                        color,
+                        0,
+                        0,
+                        interlace,
+                    ],
+                ) if i == 0 => {
+                    png.width = u32::from_be_bytes([w0, w1, w2, w3]);
+                    png.height = u32::from_be_bytes([h0, h1, h2, h3]);
+                    png.bit_depth = depth;
+                    png.color_type = color;
+                    let supported = match color {
+                        0 | 3 => matches!(depth, 1 | 2 | 4 | 8),
+                        2 | 4 | 6 => depth == 8,
+                        _ => return Err(DecodeError::Invalid),
+                    };
+                    if !supported || interlace != 0 || png.width > MAX_SIZE || png.height > MAX_SIZE
+                    {
+                        return Err(DecodeError::Unsupported);
+                    }
+                }
+                (b"PLTE", _) => png.palette = content,
+                (b"tRNS", _) => png.transparency = content,
+                (b"IEND", _) => break,
+                _ if i == 0 => return Err(DecodeError::Invalid),
+                _ => {}
+            }
+        }
+        if png.width == 0 || png.height == 0 || (png.color_type == 3 && png.palette.is_empty()) {
+            return Err(DecodeError::Invalid);
+        }
+        Ok(png)
+    }
+
+    /// Decode and draw the image, reporting bad pixel data as an error.
+    ///
+    /// Rows decoded before the problem was found stay drawn.
+    pub fn draw_checked<D>(&self, target: &mut D) -> Result<(), Error<D::Error>>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let channels = match self.color_type {
+            2 => 3,
+            4 => 2,
+            6 => 4,
+            _ => 1,
+        };
+        let bits = channels * usize::from(self.bit_depth);
+        // `new` caps the width, but don't let a bad one wrap round.
+        let stride = (self.width as usize)
+            .checked_mul(bits)
+            .ok_or(Error::Decode(DecodeError::Unsupported))?
+            .div_ceil(8);
+
+        let mut state = InflateState::new_boxed(DataFormat::Zlib);
+        // One filter type byte, then the row.
+        let mut row = vec![0u8; 1 + stride];
+        let mut prev = vec![0u8; 1 + stride];
+        let mut colors = vec![Rgb565::BLACK; self.width as usize];
+        let (mut filled, mut y) = (0, 0);
+
+        for chunk in chunks(self.data) {
+            let (kind, mut input) = chunk.map_err(Error::Decode)?;
+            if kind != b"IDAT" {
+                continue;
+            }
+            loop {
+                let result = inflate(&mut state, input, &mut row[filled..], MZFlush::None);
+                input = &input[result.bytes_consumed..];
+                filled += result.bytes_written;
+                if filled == row.len() {
+                    unfilter(&mut row, &prev, bits.div_ceil(8))?;
+                    self.draw_row(target, &row[1..], &mut colors, y)
+                        .map_err(Error::Draw)?;
+                    core::mem::swap(&mut row, &mut prev);
+                    (filled, y) = (0, y + 1);
+                    if y == self.height {
+                        return Ok(());
+                    }
+                }
+                match result.status {
+                    Ok(MZStatus::StreamEnd) => return Err(Error::Decode(DecodeError::Invalid)),
+                    // Out of input for this chunk.
+                    Err(MZError::Buf) => break,
+                    Err(_) => return Err(Error::Decode(DecodeError::Invalid)),
+                    Ok(_) if result.bytes_consumed == 0 && result.bytes_written == 0 => break,
+                    Ok(_) => {}
+                }
+            }
+        }
+        Err(Error::Decode(DecodeError::Invalid))
+    }
+
+    /// Convert one unfiltered row and draw its opaque runs.
+    fn draw_row<D>(
+        &self,
+        target: &mut D,
+        row: &[u8],
+        colors: &mut [Rgb565],
+        y: u32,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let mut start = 0;
+        for x in 0..=self.width as usize {
+            let pixel = (x < self.width as usize).then(|| self.pixel(row, x));
+            if let Some(Some(color)) = pixel {
+                colors[x] = color;
+                continue;
+            }
+            // End of an opaque run.
+            if x > start {
+                let area = Rectangle::new(
+                    Point::new(start as i32, y as i32),
+                    Size::new((x - start) as u32, 1),
+                );
+                target.fill_contiguous(&area, colors[start..x].iter().copied())?;
+            }
+            start = x + 1;
+        }
+        Ok(())
+    }
+
+    /// The colour of pixel `x` of an unfiltered row, or `None` if it is
+    /// transparent.
+    fn pixel(&self, row: &[u8], x: usize) -> Option<Rgb565> {
+        let sample = |i: usize| -> u16 {
+            match self.bit_depth {
+                8 => u16::from(row[i]),
+                depth => {
+                    let depth = usize::from(depth);
+                    let bit = i * depth;
+                    let shift = 8 - depth - bit % 8;
+                    u16::from(row[bit / 8] >> shift) & ((1 << depth) - 1)
+                }
+            }
+        };
+        let key = |i: usize| {
+            self.transparency
+                .get(i * 2..i * 2 + 2)
+                .map(|b| u16::from_be_bytes([b[0], b[1]]))
+        };
+        let (rgb, alpha) = match self.color_type {
+            0 => {
+                let level = sample(x);
+                let max = (1 << self.bit_depth) - 1;
+                let l = (level * 255 / max) as u8;
+                ([l; 3], if key(0) == Some(level) { 0 } else { 255 })
+            }
+            2 => {
+                let rgb = [row[x * 3], row[x * 3 + 1], row[x * 3 + 2]];
+                let keyed = (0..3).all(|c| key(c) == Some(u16::from(rgb[c])));
+                (rgb, if keyed { 0 } else { 255 })
+            }
+            3 => {
+                let index = usize::from(sample(x));
+                let rgb = self.palette.get(index * 3..index * 3 + 3)?;
+                let alpha = self.transparency.get(index).copied().unwrap_or(255);
+                ([rgb[0], rgb[1], rgb[2]], alpha)
+            }
+            4 => ([row[x * 2]; 3], row[x * 2 + 1]),
+            _ => ([row[x * 4], row[x * 4 + 1], row[x * 4 + 2]], row[x * 4 + 3]),
+        };
+        (alpha >= 0x80).then(|| Rgb565::new(rgb[0] >> 3, rgb[1] >> 2, rgb[2] >> 3))
+    }
+}
+
+impl OriginDimensions for Png<'_> {
+    fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+}
+
+impl ImageDrawable for Png<'_> {
+    type Color = Rgb565;
+
+    /// Draws the image; bad pixel data is logged and cuts the image short.
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        match self.draw_checked(target) {
+            Ok(()) => Ok(()),
+            Err(Error::Draw(err)) => Err(err),
+            Err(Error::Decode(err)) => {
+                defmt::warn!("PNG decoding failed: {}", err);
+                Ok(())
+            }
+        }
+    }
+
+    fn draw_sub_image<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.draw(&mut target.translated(-area.top_left).clipped(area))
+    }
+}
+
+/// The `(type, data)` of each chunk, without checking CRCs.
+fn chunks(mut data: &[u8]) -> impl Iterator<Item = Result<(&[u8; 4], &[u8]), DecodeError>> {
+    core::iter::from_fn(move || {
+        if data.is_empty() {
+            return None;
+        }
+        let chunk = (|| {
+            let (len, rest) = data.split_first_chunk::<4>()?;
+            let (kind, rest) = rest.split_first_chunk::<4>()?;
+            let len = u32::from_be_bytes(*len) as usize;
+            let content = rest.get(..len)?;
+            data = rest.get(len + 4..)?;
+            Some((kind, content))
+        })();
+        if chunk.is_none() {
+            data = &[];
+        }
+        Some(chunk.ok_or(DecodeError::Invalid))
+    })
+}
+
+/// Undo the PNG filter of `row` (filter type byte first) in place. `step`
+/// is the distance to the same byte of the previous pixel.
+fn unfilter<E>(row: &mut [u8], prev: &[u8], step: usize) -> Result<(), Error<E>> {
+    let filter = row[0];
+    for i in 1..row.len() {
+        let left = if i > step { row[i - step] } else { 0 };
+        let up = prev[i];
+        let up_left = if i > step { prev[i - step] } else { 0 };
+        row[i] = row[i].wrapping_add(match filter {
+            0 => 0,
+            1 => left,
+            2 => up,
+            3 => ((u16::from(left) + u16::from(up)) / 2) as u8,
+            4 => paeth(left, up, up_left),
+            _ => return Err(Error::Decode(DecodeError::Invalid)),
+        });
+    }
+    Ok(())
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = i16::from(a) + i16::from(b) - i16::from(c);
+    let (pa, pb, pc) = (
+        (p - i16::from(a)).abs(),
+        (p - i16::from(b)).abs(),
+        (p - i16::from(c)).abs(),
+    );
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
@@ -0,0 +1,194 @@
+//! Persistence-of-vision text on the LED bars.
+//!
+//! Both bars flash a message one 5-pixel column at a time, fast enough
+//! that waving the badge side to side spells it out in the air:
+//!
+//! ```rust,ignore
+//! let mut pov = Pov::new("HELLO").with_color(Srgb::new(255, 60, 0));
+//! loop {
+//!     pov.play(&mut leds).await;
+//! }
+//! ```
+//!
+//! The column time sets how wide the letters come out: shorter for a fast
+//! wave, longer for a slow one. A column takes about 0.35 ms to send, so
+//! times much below half a millisecond can't be kept.
+
+use embassy_time::{
+    Duration,
+    Ticker,
+};
+use palette::Srgb;
+
+use crate::{
+    BAR_COUNT,
+    Leds,
+};
+
+/// Blank columns between letters.
+const LETTER_GAP: usize = 1;
+/// Blank columns after the message, before it starts over.
+const MESSAGE_GAP: usize = 6;
+
+/// Rows of a 3×5 glyph, top first, bit 2 the left column. Lowercase is
+/// shown as uppercase, and anything without a glyph as `?`.
+const fn glyph(c: char) -> [u8; 5] {
+    match c.to_ascii_uppercase() {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        ' ' => [0b000, 0b000, 0b000, 0b000, 0b000],
+        '!' => [0b010, 0b010, 0b010, 0b000, 0b010],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        ',' => [0b000, 0b000, 0b000, 0b010, 0b100],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        '*' => [0b000, 0b101, 0b010, 0b101, 0b000],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        '#' => [0b101, 0b111, 0b101, 0b111, 0b101],
+        '<' => [0b001, 0b010, 0b100, 0b010, 0b001],
+        '>' => [0b100, 0b010, 0b001, 0b010, 0b100],
+        _ => [0b110, 0b001, 0b010, 0b000, 0b010],
+    }
+}
+
+/// Column `x` (0 to 2) of `rows`, bit 0 the top row.
+const fn column(rows: [u8; 5], x: usize) -> u8 {
+    let mut bits = 0;
+    let mut y = 0;
+    while y < 5 {
+        if rows[y] & (0b100 >> x) != 0 {
+            bits |= 1 << y;
+        }
+        y += 1;
+    }
+    bits
+}
+
+/// A message to wave on the LED bars.
+#[derive(Clone, Copy, Debug)]
+pub struct Pov<'a> {
+    text: &'a str,
+    color: Srgb<u8>,
+    column_time: Duration,
+    reversed: bool,
+}
+
+impl<'a> Pov<'a> {
+    /// `text` in white, a column every millisecond.
+    pub const fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            color: Srgb::new(255, 255, 255),
+            column_time: Duration::from_millis(1),
+            reversed: false,
+        }
+    }
+
+    /// Light the message in `color`.
+    #[must_use]
+    pub const fn with_color(mut self, color: Srgb<u8>) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Show each column for `time`.
+    #[must_use]
+    pub const fn with_column_time(mut self, time: Duration) -> Self {
+        self.column_time = time;
+        self
+    }
+
+    /// Send the columns last to first, so the message reads right when
+    /// swung the other way.
+    #[must_use]
+    pub const fn reversed(mut self) -> Self {
+        self.reversed = true;
+        self
+    }
+
+    /// Show `text` from the next pass.
+    pub const fn set_text(&mut self, text: &'a str) {
+        self.text = text;
+    }
+
+    /// Show each column for `time` from the next pass.
+    pub const fn set_column_time(&mut self, time: Duration) {
+        self.column_time = time;
+    }
+
+    /// The time each column is shown.
+    pub const fn column_time(&self) -> Duration {
+        self.column_time
+    }
+
+    /// Columns in one pass, blank ones included.
+    pub fn width(&self) -> usize {
+        self.text.chars().count() * (3 + LETTER_GAP) + MESSAGE_GAP
+    }
+
+    /// Column `x` of the message, bit 0 the top row.
+    fn column_at(&self, x: usize) -> u8 {
+        let (letter, x) = (x / (3 + LETTER_GAP), x % (3 + LETTER_GAP));
+        match self.text.chars().nth(letter) {
+            Some(c) if x < 3 => column(glyph(c), x),
+            _ => 0,
+        }
+    }
+
+    /// Flash the message once on both bars, column by column, then leave
+    /// them dark.
+    pub async fn play(&self, leds: &mut Leds<'_>) {
+        let width = self.width();
+        let mut ticker = Ticker::every(self.column_time);
+        for i in 0..width {
+            let x = if self.reversed { width - 1 - i } else { i };
+            let bits = self.column_at(x);
+            let bar: [Srgb<u8>; BAR_COUNT] = core::array::from_fn(|led| {
+                // Bars run bottom to top, the glyph rows top to bottom.
+                if bits & (1 << (BAR_COUNT - 1 - led)) != 0 {
+                    self.color
+                } else {
+                    Srgb::new(0, 0, 0)
+                }
+            });
+            leds.set_both_bars(&bar);
+            leds.update().await;
+            ticker.next().await;
+        }
+    }
+}
@@ -0,0 +1,257 @@
+//! Lightweight frame-budget profiler with per-phase timing.
+//!
+//! Wrap each phase of a game loop in [`profile!`](crate::profile) and call
+//! [`end_frame`] once per frame. The time spent in each named phase is
+//! accumulated for the current frame; the totals of the last completed frame
+//! can be logged with [`log_last_frame`] or drawn as a stacked bar with
+//! [`draw_bar`].
+//!
+//! ```rust,ignore
+//! loop {
+//!     {
+//!         profile!("input");
+//!         read_buttons(&mut game);
+//!     }
+//!     {
+//!         profile!("draw");
+//!         draw_frame(display, &game);
+//!     }
+//!     profiler::end_frame();
+//!     profiler::draw_bar(display, bar_area, Duration::from_millis(20)).unwrap();
+//! }
+//! ```
+
+use core::cell::RefCell;
+
+use defmt::info;
+use embassy_sync::blocking_mutex::{
+    Mutex,
+    raw::CriticalSectionRawMutex,
+};
+use embassy_time::{
+    Duration,
+    Instant,
+};
+use embedded_graphics::{
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::{
+        PrimitiveStyle,
+        Rectangle,
+    },
+};
+
+/// Maximum number of distinct phase names tracked at once.
+///
+/// Scopes with names beyond this limit are silently ignored.
+pub const MAX_PHASES: usize = 8;
+
+/// Height of the red underline marking an overrun in [`draw_bar`].
+const OVERRUN_MARKER: u32 = 3;
+
+/// Bar colours, assigned to phases in the order they are first seen.
+pub const PHASE_COLORS: [Rgb565; MAX_PHASES] = [
+    Rgb565::CSS_DODGER_BLUE,
+    Rgb565::CSS_LIME_GREEN,
+    Rgb565::CSS_ORANGE,
+    Rgb565::CSS_MAGENTA,
+    Rgb565::CSS_CYAN,
+    Rgb565::CSS_YELLOW,
+    Rgb565::CSS_SALMON,
+    Rgb565::CSS_SLATE_BLUE,
+];
+
+/// Timings of one completed frame.
+#[derive(Clone, Copy)]
+pub struct FrameStats {
+    names: [&'static str; MAX_PHASES],
+    micros: [u64; MAX_PHASES],
+    count: usize,
+    frame_micros: u64,
+}
+
+impl FrameStats {
+    const fn new() -> Self {
+        Self {
+            names: [""; MAX_PHASES],
+            micros: [0; MAX_PHASES],
+            count: 0,
+            frame_micros: 0,
+        }
+    }
+
+    /// Iterate over `(phase name, time spent)` in first-seen order.
+    pub fn phases(&self) -> impl Iterator<Item = (&'static str, Duration)> + '_ {
+        self.names[..self.count]
+            .iter()
+            .zip(&self.micros[..self.count])
+            .map(|(&name, &us)| (name, Duration::from_micros(us)))
+    }
+
+    /// Sum of all profiled phases.
+    pub fn profiled(&self) -> Duration {
+        Duration::from_micros(self.micros[..self.count].iter().sum())
+    }
+
+    /// Wall-clock time between the two [`end_frame`] calls bounding this frame.
+    pub const fn frame(&self) -> Duration {
+        Duration::from_micros(self.frame_micros)
+    }
+}
+
+struct State {
+    current: FrameStats,
+    last: FrameStats,
+    frame_start: Option<Instant>,
+}
+
+impl State {
+    fn slot(&mut self, name: &'static str) -> Option<usize> {
+        let stats = &mut self.current;
+        if let Some(i) = stats.names[..stats.count].iter().position(|&n| n == name) {
+            return Some(i);
+        }
+        if stats.count == MAX_PHASES {
+            return None;
+        }
+        stats.names[stats.count] = name;
+        stats.count += 1;
+        Some(stats.count - 1)
+    }
+}
+
+static STATE: Mutex<CriticalSectionRawMutex, RefCell<State>> = Mutex::new(RefCell::new(State {
+    current: FrameStats::new(),
+    last: FrameStats::new(),
+    frame_start: None,
+}));
+
+/// Time the enclosing block as the named phase.
+///
+/// Expands to a guard that records the elapsed time when it goes out of
+/// scope, so the phase ends at the closing brace of the surrounding block.
+#[macro_export]
+macro_rules! profile {
+    ($name:expr) => {
+        let _profile_scope = $crate::profiler::Scope::enter($name);
+    };
+}
+
+/// Guard created by [`profile!`](crate::profile); records its lifetime on drop.
+pub struct Scope {
+    name: &'static str,
+    start: Instant,
+}
+
+impl Scope {
+    /// Start timing the phase `name`.
+    #[must_use]
+    pub fn enter(name: &'static str) -> Self {
+        Self {
+            name,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed().as_micros();
+        STATE.lock(|state| {
+            let mut state = state.borrow_mut();
+            if let Some(i) = state.slot(self.name) {
+                state.current.micros[i] += elapsed;
+            }
+        });
+    }
+}
+
+/// Close the current frame and start accumulating the next one.
+///
+/// Phase names persist across frames so bar colours stay stable.
+pub fn end_frame() {
+    let now = Instant::now();
+    STATE.lock(|state| {
+        let mut state = state.borrow_mut();
+        let frame_micros = state
+            .frame_start
+            .map_or(0, |start| (now - start).as_micros());
+        state.current.frame_micros = frame_micros;
+        state.last = state.current;
+        state.current.micros = [0; MAX_PHASES];
+        state.frame_start = Some(now);
+    });
+}
+
+/// Timings of the last frame closed with [`end_frame`].
+pub fn last_frame() -> FrameStats {
+    STATE.lock(|state| state.borrow().last)
+}
+
+/// Log the last frame's phase timings via defmt.
+pub fn log_last_frame() {
+    let stats = last_frame();
+    info!("frame: {=u64} us", stats.frame().as_micros());
+    for (name, time) in stats.phases() {
+        info!("  {=str}: {=u64} us", name, time.as_micros());
+    }
+}
+
+/// Draw the last frame's phases as a horizontal stacked bar.
+///
+/// The full width of `area` represents `budget`. Phases are drawn left to
+/// right in [`PHASE_COLORS`]; the rest of the bar is dark grey. If the
+/// profiled phases together exceeded the budget, the bar spans their total
+/// instead, with a red line where the budget ends and the overrun after it
+/// underlined in red; time outside any phase doesn't count.
+pub fn draw_bar<D>(target: &mut D, area: Rectangle, budget: Duration) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    let stats = last_frame();
+    let budget_us = budget.as_micros().max(1);
+    let scale_us = budget_us.max(stats.profiled().as_micros());
+    let width = u64::from(area.size.width);
+
+    let mut x = 0u64;
+    for (i, (_, time)) in stats.phases().enumerate() {
+        let w = time.as_micros() * width / scale_us;
+        if w > 0 {
+            Rectangle::new(
+                area.top_left + Point::new(x as i32, 0),
+                Size::new(w as u32, area.size.height),
+            )
+            .into_styled(PrimitiveStyle::with_fill(PHASE_COLORS[i]))
+            .draw(target)?;
+        }
+        x += w;
+    }
+
+    if x < width {
+        Rectangle::new(
+            area.top_left + Point::new(x as i32, 0),
+            Size::new((width - x) as u32, area.size.height),
+        )
+        .into_styled(PrimitiveStyle::with_fill(Rgb565::CSS_DIM_GRAY))
+        .draw(target)?;
+    }
+
+    if scale_us > budget_us {
+        let end = (budget_us * width / scale_us) as i32;
+        let red = PrimitiveStyle::with_fill(Rgb565::RED);
+        let marker = OVERRUN_MARKER.min(area.size.height);
+        Rectangle::new(
+            area.top_left + Point::new(end.saturating_sub(1).max(0), 0),
+            Size::new(2, area.size.height),
+        )
+        .into_styled(red)
+        .draw(target)?;
+        Rectangle::new(
+            area.top_left + Point::new(end, (area.size.height - marker) as i32),
+            Size::new(area.size.width - end as u32, marker),
+        )
+        .into_styled(red)
+        .draw(target)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,257 @@
+//! PWM and servo outputs on the spare GPIOs of the expansion header.
+//!
+//! The outputs share the LEDC peripheral with the [`Backlight`](crate::Backlight),
+//! which owns it and keeps timer 0 and channel 0 for itself. It hands out
+//! the rest once, as a [`Pwm`]: three timers, one per distinct frequency,
+//! and seven channels, one per pin:
+//!
+//! ```rust,ignore
+//! let mut pwm = backlight.take_pwm().unwrap();
+//! let mut fan = pwm.output(peripherals.GPIO9, Rate::from_khz(25))?;
+//! fan.set_duty(fan.max_duty() / 2);
+//!
+//! let mut servo = pwm.servo(peripherals.GPIO10)?;
+//! servo.set_angle(90);
+//! ```
+
+use embassy_time::Duration;
+use embedded_hal::pwm::SetDutyCycle;
+use esp_hal::{
+    gpio::{
+        DriveMode,
+        interconnect::PeripheralOutput,
+    },
+    ledc::{
+        Ledc,
+        LowSpeed,
+        channel::{
+            self,
+            ChannelIFace,
+        },
+        timer::{
+            self,
+            TimerIFace,
+        },
+    },
+    time::Rate,
+};
+
+use crate::mk_static;
+
+/// LEDC timers not used by the backlight.
+const TIMERS: [timer::Number; 3] = [
+    timer::Number::Timer1,
+    timer::Number::Timer2,
+    timer::Number::Timer3,
+];
+
+/// LEDC channels not used by the backlight.
+const CHANNELS: [channel::Number; 7] = [
+    channel::Number::Channel1,
+    channel::Number::Channel2,
+    channel::Number::Channel3,
+    channel::Number::Channel4,
+    channel::Number::Channel5,
+    channel::Number::Channel6,
+    channel::Number::Channel7,
+];
+
+/// LEDC counter clock.
+const APB_CLOCK_HZ: u32 = 80_000_000;
+
+/// Finest duty resolution the LEDC has on the ESP32-S3.
+const MAX_DUTY_BITS: u32 = 14;
+
+/// Hobby servos expect a pulse every 20 ms.
+const SERVO_FREQUENCY: Rate = Rate::from_hz(50);
+
+/// Why an output can't be set up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum Error {
+    /// All three timers already run at other frequencies.
+    NoTimer,
+    /// All seven channels are in use.
+    NoChannel,
+    /// The LEDC can't produce this frequency.
+    Frequency,
+}
+
+enum TimerSlot {
+    Free(&'static mut timer::Timer<'static, LowSpeed>),
+    Running(&'static timer::Timer<'static, LowSpeed>, Rate),
+}
+
+/// Hands out PWM outputs on the LEDC timers and channels the backlight
+/// doesn't use.
+pub struct Pwm {
+    ledc: &'static Ledc<'static>,
+    timers: [Option<TimerSlot>; 3],
+    next_channel: usize,
+}
+
+impl Pwm {
+    /// The timers and channels of `ledc` the backlight doesn't use. Only
+    /// the backlight makes one, once.
+    pub(crate) fn new(ledc: &'static Ledc<'static>) -> Self {
+        // Channels borrow their timer, so the timers have to live forever.
+        let [a, b, c] = mk_static!(
+            [timer::Timer<'static, LowSpeed>; 3],
+            TIMERS.map(|number| ledc.timer(number))
+        );
+        Self {
+            ledc,
+            timers: [
+                Some(TimerSlot::Free(a)),
+                Some(TimerSlot::Free(b)),
+                Some(TimerSlot::Free(c)),
+            ],
+            next_channel: 0,
+        }
+    }
+
+    /// A PWM output on `pin` at `frequency`, starting low.
+    ///
+    /// The duty resolution is the finest the frequency allows, up to 14 bits
+    /// at 4.8 kHz and below.
+    pub fn output(
+        &mut self,
+        pin: impl PeripheralOutput<'static>,
+        frequency: Rate,
+    ) -> Result<PwmOutput, Error> {
+        let number = *CHANNELS.get(self.next_channel).ok_or(Error::NoChannel)?;
+        let timer = self.timer(frequency)?;
+        let mut channel = self.ledc.channel(number, pin);
+        channel
+            .configure(channel::config::Config {
+                timer,
+                duty_pct: 0,
+                drive_mode: DriveMode::PushPull,
+            })
+            .map_err(|_| Error::Frequency)?;
+        self.next_channel += 1;
+        Ok(PwmOutput { channel })
+    }
+
+    /// A hobby servo on `pin`, limp until its first
+    /// [`set_angle`](Servo::set_angle).
+    pub fn servo(&mut self, pin: impl PeripheralOutput<'static>) -> Result<Servo, Error> {
+        Ok(Servo::new(self.output(pin, SERVO_FREQUENCY)?))
+    }
+
+    /// A timer running at `frequency`, shared with earlier outputs at the
+    /// same frequency.
+    fn timer(
+        &mut self,
+        frequency: Rate,
+    ) -> Result<&'static timer::Timer<'static, LowSpeed>, Error> {
+        let shared = self.timers.iter().flatten().find_map(|slot| match slot {
+            TimerSlot::Running(timer, running) if *running == frequency => Some(*timer),
+            _ => None,
+        });
+        if let Some(timer) = shared {
+            return Ok(timer);
+        }
+        let slot = self
+            .timers
+            .iter_mut()
+            .find(|slot| matches!(slot, Some(TimerSlot::Free(_))))
+            .ok_or(Error::NoTimer)?;
+        let Some(TimerSlot::Free(timer)) = slot.take() else {
+            unreachable!()
+        };
+        let ratio = APB_CLOCK_HZ / frequency.as_hz().max(1);
+        let duty = (ratio >= 2)
+            .then(|| timer::config::Duty::try_from(ratio.ilog2().min(MAX_DUTY_BITS)).ok())
+            .flatten();
+        let configured = duty.is_some_and(|duty| {
+            timer
+                .configure(timer::config::Config {
+                    duty,
+                    clock_source: timer::LSClockSource::APBClk,
+                    frequency,
+                })
+                .is_ok()
+        });
+        if !configured {
+            *slot = Some(TimerSlot::Free(timer));
+            return Err(Error::Frequency);
+        }
+        let timer: &'static timer::Timer<'static, LowSpeed> = timer;
+        *slot = Some(TimerSlot::Running(timer, frequency));
+        Ok(timer)
+    }
+}
+
+/// One PWM pin.
+pub struct PwmOutput {
+    channel: channel::Channel<'static, LowSpeed>,
+}
+
+impl PwmOutput {
+    /// Duty value for always high.
+    pub fn max_duty(&self) -> u16 {
+        self.channel.max_duty_cycle()
+    }
+
+    /// Set the high time, from 0 (always low) to [`max_duty`](Self::max_duty).
+    /// Larger values are clamped.
+    pub fn set_duty(&mut self, duty: u16) {
+        self.channel.set_duty_cycle(duty).unwrap();
+    }
+
+    /// Set the high time in percent, from 0 to 100.
+    pub fn set_duty_percent(&mut self, percent: u8) {
+        self.channel
+            .set_duty_cycle_percent(percent.min(100))
+            .unwrap();
+    }
+}
+
+/// A hobby servo, positioned by the length of a pulse sent every 20 ms.
+pub struct Servo {
+    output: PwmOutput,
+    min_pulse: Duration,
+    max_pulse: Duration,
+}
+
+impl Servo {
+    /// Wrap an output already running at 50 Hz, with the common 1–2 ms
+    /// pulse range.
+    pub fn new(output: PwmOutput) -> Self {
+        Self {
+            output,
+            min_pulse: Duration::from_micros(1000),
+            max_pulse: Duration::from_micros(2000),
+        }
+    }
+
+    /// Pulse lengths for 0° and 180°. Many servos turn further than the
+    /// default 1–2 ms, often 0.5–2.5 ms; check the datasheet before
+    /// widening it, as driving a servo into its end stop damages it.
+    #[must_use]
+    pub const fn with_pulse_range(mut self, min: Duration, max: Duration) -> Self {
+        self.min_pulse = min;
+        self.max_pulse = max;
+        self
+    }
+
+    /// Turn to `degrees`, from 0 to 180.
+    pub fn set_angle(&mut self, degrees: u8) {
+        let degrees = u64::from(degrees.min(180));
+        let (min, max) = (self.min_pulse.as_micros(), self.max_pulse.as_micros());
+        let pulse = min + max.saturating_sub(min) * degrees / 180;
+        self.set_pulse(Duration::from_micros(pulse));
+    }
+
+    /// Send pulses of exactly `pulse`, for servos with unusual ranges.
+    pub fn set_pulse(&mut self, pulse: Duration) {
+        let period = u64::from(1_000_000 / SERVO_FREQUENCY.as_hz());
+        let duty = pulse.as_micros().min(period) * u64::from(self.output.max_duty()) / period;
+        self.output.set_duty(duty as u16);
+    }
+
+    /// Stop sending pulses, so the servo goes limp.
+    pub fn release(&mut self) {
+        self.output.set_duty(0);
+    }
+}
@@ -0,0 +1,548 @@
+//! QR codes generated on the badge.
+//!
+//! Text is encoded in byte mode with medium error correction (about 15% of
+//! the code can be damaged), in the smallest version from 1 (21×21 modules)
+//! to 10 (57×57) that holds it. That is up to 213 bytes, plenty for a URL,
+//! a contact card or a flag submission link. Larger codes would need
+//! modules under 2 px wide on this panel, which phones struggle to read.
+//!
+//! ```rust,ignore
+//! // Top-left corner at (10, 10), 4 px per module.
+//! qr::draw_qr(display, "https://disobey.fi", Point::new(10, 10), 4)?;
+//! ```
+//!
+//! The code is drawn dark on light with the standard 4-module light border,
+//! so it scans on any background.
+
+use embedded_graphics::{
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::Rectangle,
+};
+
+/// Largest version generated.
+const MAX_VERSION: usize = 10;
+
+/// Modules per side of the largest version.
+const MAX_SIZE: usize = MAX_VERSION * 4 + 17;
+
+/// Bytes in a one-bit-per-module grid of the largest version.
+const GRID_BYTES: usize = (MAX_SIZE * MAX_SIZE).div_ceil(8);
+
+/// Codewords, data and error correction, of the largest version.
+const MAX_CODEWORDS: usize = 346;
+
+/// Width of the light border, in modules.
+pub const QUIET_ZONE: u32 = 4;
+
+/// Error correction codewords per block, medium level, by version.
+const ECC_PER_BLOCK: [usize; MAX_VERSION + 1] = [0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26];
+
+/// Error correction blocks, medium level, by version.
+const BLOCKS: [usize; MAX_VERSION + 1] = [0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5];
+
+/// Why a QR code can't be drawn.
+#[derive(Debug)]
+pub enum Error<E> {
+    /// The data doesn't fit in a version 10 code.
+    TooLong,
+    /// The draw target failed.
+    Draw(E),
+}
+
+/// An encoded QR code.
+#[derive(Clone)]
+pub struct QrCode {
+    version: usize,
+    modules: Grid,
+}
+
+impl QrCode {
+    /// Encode `data`, or `None` if it is too long.
+    pub fn new(data: impl AsRef<[u8]>) -> Option<Self> {
+        let data = data.as_ref();
+        let version = (1..=MAX_VERSION).find(|&v| data.len() <= capacity(v))?;
+        let mut code = Self {
+            version,
+            modules: Grid::new(),
+        };
+        let mut function = Grid::new();
+        code.draw_function_patterns(&mut function);
+        let mut codewords = [0; MAX_CODEWORDS];
+        let len = code.codewords(data, &mut codewords);
+        code.draw_codewords(&codewords[..len], &function);
+
+        let mut best = (u32::MAX, 0);
+        for mask in 0..8 {
+            code.apply_mask(mask, &function);
+            code.draw_format_bits(mask, &mut function);
+            best = best.min((code.penalty(), mask));
+            code.apply_mask(mask, &function);
+        }
+        code.apply_mask(best.1, &function);
+        code.draw_format_bits(best.1, &mut function);
+        Some(code)
+    }
+
+    /// The version, from 1 to 10.
+    pub const fn version(&self) -> u8 {
+        self.version as u8
+    }
+
+    /// Modules per side, without the quiet zone.
+    pub const fn size(&self) -> u32 {
+        self.version as u32 * 4 + 17
+    }
+
+    /// `true` if the module at column `x`, row `y` is dark.
+    ///
+    /// Modules outside the code, such as the quiet zone, are light.
+    pub fn module(&self, x: i32, y: i32) -> bool {
+        let size = self.size() as i32;
+        (0..size).contains(&x) && (0..size).contains(&y) && self.get(x as usize, y as usize)
+    }
+
+    /// Draw the code and its quiet zone with the top-left corner at
+    /// `position`, `scale` pixels per module. Returns the size drawn.
+    pub fn draw<D>(&self, target: &mut D, position: Point, scale: u32) -> Result<Size, D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let scale = scale.max(1);
+        let side = (self.size() + 2 * QUIET_ZONE) * scale;
+        let area = Rectangle::new(position, Size::new(side, side));
+        let quiet = QUIET_ZONE as i32;
+        let pixels = area.points().map(|p| {
+            let p = p - position;
+            let (x, y) = (p.x / scale as i32 - quiet, p.y / scale as i32 - quiet);
+            if self.module(x, y) {
+                Rgb565::BLACK
+            } else {
+                Rgb565::WHITE
+            }
+        });
+        target.fill_contiguous(&area, pixels)?;
+        Ok(area.size)
+    }
+
+    fn get(&self, x: usize, y: usize) -> bool {
+        self.modules.get(y * self.size() as usize + x)
+    }
+
+    fn set(&mut self, x: usize, y: usize, dark: bool) {
+        let i = y * self.size() as usize + x;
+        self.modules.set(i, dark);
+    }
+
+    fn set_function(&mut self, function: &mut Grid, x: usize, y: usize, dark: bool) {
+        self.set(x, y, dark);
+        function.set(y * self.size() as usize + x, true);
+    }
+
+    // ── Function patterns ──────────────────────────────────────────────────
+
+    fn draw_function_patterns(&mut self, function: &mut Grid) {
+        let size = self.size() as usize;
+        for i in 0..size {
+            self.set_function(function, 6, i, i % 2 == 0);
+            self.set_function(function, i, 6, i % 2 == 0);
+        }
+        for (x, y) in [(3, 3), (size - 4, 3), (3, size - 4)] {
+            self.draw_finder(function, x, y);
+        }
+        let (positions, count) = alignment_positions(self.version);
+        for (i, &x) in positions[..count].iter().enumerate() {
+            for (j, &y) in positions[..count].iter().enumerate() {
+                // Skip the three corners with finders.
+                let corner = |k| k == 0 || k == count - 1;
+                if !(i == 0 && corner(j) || j == 0 && i == count - 1) {
+                    self.draw_alignment(function, x, y);
+                }
+            }
+        }
+        // Reserve the format areas; the real bits are drawn after masking.
+        self.draw_format_bits(0, function);
+        self.draw_version(function);
+    }
+
+    /// A finder and its separator, centred on `(x, y)`.
+    fn draw_finder(&mut self, function: &mut Grid, x: usize, y: usize) {
+        let size = self.size() as i32;
+        for dy in -4..=4 {
+            for dx in -4..=4 {
+                let (xx, yy) = (x as i32 + dx, y as i32 + dy);
+                if (0..size).contains(&xx) && (0..size).contains(&yy) {
+                    let ring = dx.abs().max(dy.abs());
+                    self.set_function(function, xx as usize, yy as usize, ring != 2 && ring != 4);
+                }
+            }
+        }
+    }
+
+    fn draw_alignment(&mut self, function: &mut Grid, x: usize, y: usize) {
+        for dy in -2..=2_i32 {
+            for dx in -2..=2_i32 {
+                let (xx, yy) = ((x as i32 + dx) as usize, (y as i32 + dy) as usize);
+                self.set_function(function, xx, yy, dx.abs().max(dy.abs()) != 1);
+            }
+        }
+    }
+
+    /// Both copies of the medium level and `mask`, plus the dark module.
+    fn draw_format_bits(&mut self, mask: u32, function: &mut Grid) {
+        // Medium is level 0b00.
+        let data = mask;
+        let mut rem = data;
+        for _ in 0..10 {
+            rem = (rem << 1) ^ ((rem >> 9) * 0x537);
+        }
+        let bits = (data << 10 | rem) ^ 0x5412;
+        let bit = |i: usize| bits >> i & 1 != 0;
+        let size = self.size() as usize;
+
+        for i in 0..6 {
+            self.set_function(function, 8, i, bit(i));
+        }
+        self.set_function(function, 8, 7, bit(6));
+        self.set_function(function, 8, 8, bit(7));
+        self.set_function(function, 7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(function, 14 - i, 8, bit(i));
+        }
+
+        for i in 0..8 {
+            self.set_function(function, size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(function, 8, size - 15 + i, bit(i));
+        }
+        self.set_function(function, 8, size - 8, true);
+    }
+
+    /// Version information, present from version 7.
+    fn draw_version(&mut self, function: &mut Grid) {
+        if self.version < 7 {
+            return;
+        }
+        let mut rem = self.version as u32;
+        for _ in 0..12 {
+            rem = (rem << 1) ^ ((rem >> 11) * 0x1f25);
+        }
+        let bits = (self.version as u32) << 12 | rem;
+        let size = self.size() as usize;
+        for i in 0..18 {
+            let dark = bits >> i & 1 != 0;
+            let (a, b) = (size - 11 + i % 3, i / 3);
+            self.set_function(function, a, b, dark);
+            self.set_function(function, b, a, dark);
+        }
+    }
+
+    // ── Data ───────────────────────────────────────────────────────────────
+
+    /// Fill `out` with the interleaved data and error correction codewords
+    /// for `data`, returning how many there are.
+    fn codewords(&self, data: &[u8], out: &mut [u8; MAX_CODEWORDS]) -> usize {
+        let total = raw_codewords(self.version);
+        let blocks = BLOCKS[self.version];
+        let ecc_len = ECC_PER_BLOCK[self.version];
+        let data_len = total - blocks * ecc_len;
+
+        // Byte mode segment, terminator and padding.
+        let mut bits = BitWriter {
+            bytes: [0; MAX_CODEWORDS],
+            len: 0,
+        };
+        bits.push(0b0100, 4);
+        bits.push(data.len() as u32, if self.version < 10 { 8 } else { 16 });
+        for &byte in data {
+            bits.push(u32::from(byte), 8);
+        }
+        bits.push(0, (data_len * 8 - bits.len).min(4));
+        bits.push(0, bits.len.next_multiple_of(8) - bits.len);
+        for pad in [0xec, 0x11].into_iter().cycle() {
+            if bits.len == data_len * 8 {
+                break;
+            }
+            bits.push(pad, 8);
+        }
+
+        // Short blocks come first; long blocks have one more data codeword.
+        let short_blocks = blocks - total % blocks;
+        let short_len = total / blocks - ecc_len;
+        let block_data = |b: usize| {
+            let start = b * short_len + b.saturating_sub(short_blocks);
+            let len = short_len + usize::from(b >= short_blocks);
+            &bits.bytes[start..start + len]
+        };
+
+        let divisor = rs_divisor(ecc_len);
+        let mut n = 0;
+        for i in 0..=short_len {
+            for b in 0..blocks {
+                if let Some(&byte) = block_data(b).get(i) {
+                    out[n] = byte;
+                    n += 1;
+                }
+            }
+        }
+        let mut ecc = [[0; 30]; 5];
+        for (b, ecc) in ecc.iter_mut().enumerate().take(blocks) {
+            rs_remainder(block_data(b), &divisor[..ecc_len], &mut ecc[..ecc_len]);
+        }
+        for i in 0..ecc_len {
+            for ecc in &ecc[..blocks] {
+                out[n] = ecc[i];
+                n += 1;
+            }
+        }
+        n
+    }
+
+    /// Place codewords in the zigzag order, skipping function modules.
+    fn draw_codewords(&mut self, codewords: &[u8], function: &Grid) {
+        let size = self.size() as usize;
+        let mut i = 0;
+        let mut right = size - 1;
+        while right >= 1 {
+            // The vertical timing pattern takes a whole column.
+            if right == 6 {
+                right = 5;
+            }
+            let upward = (right + 1) & 2 == 0;
+            for vert in 0..size {
+                let y = if upward { size - 1 - vert } else { vert };
+                for x in [right, right - 1] {
+                    if !function.get(y * size + x) && i < codewords.len() * 8 {
+                        self.set(x, y, codewords[i / 8] >> (7 - i % 8) & 1 != 0);
+                        i += 1;
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    // ── Masking ────────────────────────────────────────────────────────────
+
+    /// XOR mask pattern `mask` onto the data modules; applying it again
+    /// undoes it.
+    fn apply_mask(&mut self, mask: u32, function: &Grid) {
+        let size = self.size() as usize;
+        for y in 0..size {
+            for x in 0..size {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                if invert && !function.get(y * size + x) {
+                    self.set(x, y, !self.get(x, y));
+                }
+            }
+        }
+    }
+
+    /// The standard penalty score; the mask with the lowest one is used.
+    fn penalty(&self) -> u32 {
+        let size = self.size() as i32;
+        let mut penalty = 0;
+        let mut dark = 0;
+        for transpose in [false, true] {
+            let at = |a: i32, b: i32| {
+                if transpose {
+                    self.module(b, a)
+                } else {
+                    self.module(a, b)
+                }
+            };
+            for line in 0..size {
+                // Runs of five or more modules of one colour.
+                let mut run = 0;
+                for i in 0..size {
+                    run = if i > 0 && at(i, line) == at(i - 1, line) {
+                        run + 1
+                    } else {
+                        1
+                    };
+                    if run == 5 {
+                        penalty += 3;
+                    } else if run > 5 {
+                        penalty += 1;
+                    }
+                }
+                // Finder-like 1:1:3:1:1 patterns, once for each side with
+                // four light modules. Modules outside the code are light.
+                let core = [true, false, true, true, true, false, true];
+                let light = |start: i32, len: i32| (start..start + len).all(|k| !at(k, line));
+                for i in -1..size {
+                    if (0..7).all(|k| at(i + 1 + k, line) == core[k as usize]) {
+                        let before = light(i - 3, 4) && light(i + 8, 1);
+                        let after = light(i, 1) && light(i + 8, 4);
+                        penalty += 40 * (u32::from(before) + u32::from(after));
+                    }
+                }
+            }
+        }
+        for y in 0..size {
+            for x in 0..size {
+                let color = self.module(x, y);
+                dark += u32::from(color);
+                // 2×2 blocks of one colour.
+                if x + 1 < size
+                    && y + 1 < size
+                    && [(1, 0), (0, 1), (1, 1)]
+                        .iter()
+                        .all(|&(dx, dy)| self.module(x + dx, y + dy) == color)
+                {
+                    penalty += 3;
+                }
+            }
+        }
+        // Every 5% away from half dark.
+        let total = (size * size) as u32;
+        let deviation = (dark * 20).abs_diff(total * 10);
+        penalty + deviation.div_ceil(total).saturating_sub(1) * 10
+    }
+}
+
+/// Draw `data` as a QR code with the top-left corner of its quiet zone at
+/// `position`, `scale` pixels per module. Returns the size drawn.
+pub fn draw_qr<D>(
+    display: &mut D,
+    data: impl AsRef<[u8]>,
+    position: Point,
+    scale: u32,
+) -> Result<Size, Error<D::Error>>
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    QrCode::new(data)
+        .ok_or(Error::TooLong)?
+        .draw(display, position, scale)
+        .map_err(Error::Draw)
+}
+
+/// One bit per module.
+#[derive(Clone)]
+struct Grid([u8; GRID_BYTES]);
+
+impl Grid {
+    const fn new() -> Self {
+        Self([0; GRID_BYTES])
+    }
+
+    fn get(&self, i: usize) -> bool {
+        self.0[i / 8] >> (i % 8) & 1 != 0
+    }
+
+    fn set(&mut self, i: usize, value: bool) {
+        if value {
+            self.0[i / 8] |= 1 << (i % 8);
+        } else {
+            self.0[i / 8] &= !(1 << (i % 8));
+        }
+    }
+}
+
+struct BitWriter {
+    bytes: [u8; MAX_CODEWORDS],
+    len: usize,
+}
+
+impl BitWriter {
+    /// Append the low `count` bits of `value`, most significant first.
+    fn push(&mut self, value: u32, count: usize) {
+        for i in (0..count).rev() {
+            if value >> i & 1 != 0 {
+                self.bytes[self.len / 8] |= 0x80 >> (self.len % 8);
+            }
+            self.len += 1;
+        }
+    }
+}
+
+/// Bytes of data that fit in `version`.
+fn capacity(version: usize) -> usize {
+    let data_len = raw_codewords(version) - BLOCKS[version] * ECC_PER_BLOCK[version];
+    let header = if version < 10 { 2 } else { 3 };
+    data_len - header
+}
+
+/// Codewords that fit in the data area of `version`.
+fn raw_codewords(version: usize) -> usize {
+    let mut modules = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let count = version / 7 + 2;
+        modules -= (25 * count - 10) * count - 55;
+        if version >= 7 {
+            modules -= 36;
+        }
+    }
+    modules / 8
+}
+
+/// Centre coordinates of the alignment patterns, and how many there are.
+fn alignment_positions(version: usize) -> ([usize; 3], usize) {
+    let mut positions = [0; 3];
+    if version == 1 {
+        return (positions, 0);
+    }
+    let count = version / 7 + 2;
+    let step = (version * 8 + count * 3 + 5) / (count * 4 - 4) * 2;
+    positions[0] = 6;
+    for i in 1..count {
+        positions[count - i] = version * 4 + 10 - (i - 1) * step;
+    }
+    (positions, count)
+}
+
+/// Generator polynomial of degree `degree` for Reed-Solomon error
+/// correction, without its leading 1.
+fn rs_divisor(degree: usize) -> [u8; 30] {
+    let mut result = [0; 30];
+    result[degree - 1] = 1;
+    let mut root = 1;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_multiply(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_multiply(root, 0x02);
+    }
+    result
+}
+
+/// The remainder of `data` divided by `divisor`.
+fn rs_remainder(data: &[u8], divisor: &[u8], out: &mut [u8]) {
+    out.fill(0);
+    for &byte in data {
+        let factor = byte ^ out[0];
+        out.copy_within(1.., 0);
+        let last = out.len() - 1;
+        out[last] = 0;
+        for (o, &d) in out.iter_mut().zip(divisor) {
+            *o ^= gf_multiply(d, factor);
+        }
+    }
+}
+
+/// Multiply in GF(2⁸) modulo x⁸ + x⁴ + x³ + x² + 1.
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    let mut z: u16 = 0;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x11d);
+        z ^= ((u16::from(y) >> i) & 1) * u16::from(x);
+    }
+    z as u8
+}
@@ -0,0 +1,128 @@
+//! Low-resolution canvas scaled up to the whole screen.
+
+use core::{
+    convert::Infallible,
+    iter,
+};
+
+use embedded_graphics::{
+    Pixel,
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::Rectangle,
+};
+
+use crate::{
+    Display,
+    Framebuffer,
+    display::Error,
+};
+
+/// A canvas of 1/`N` the screen's resolution, shown with each pixel blown
+/// up to an `N`×`N` block.
+///
+/// `ScaledDisplay<2>` is 160×85 in landscape and `ScaledDisplay<4>` 80×42,
+/// centred where the screen doesn't divide evenly. Retro games get their chunky look for
+/// free, and have a quarter or a sixteenth of the pixels to draw.
+///
+/// The canvas is a [`Framebuffer`] in RAM the caller provides, at least
+/// [`PIXELS`](Self::PIXELS) long. Draw into it, then
+/// [`flush`](Self::flush) to scale the changed region onto the panel:
+///
+/// ```rust,ignore
+/// let pixels = mk_static!([Rgb565; ScaledDisplay::<2>::PIXELS], [Rgb565::BLACK; ScaledDisplay::<2>::PIXELS]);
+/// let mut canvas = ScaledDisplay::<2>::new(&display, pixels);
+/// loop {
+///     canvas.clear(Rgb565::BLACK)?;
+///     ship.draw(&mut canvas)?;
+///     canvas.flush(&mut display)?;
+/// }
+/// ```
+///
+/// It writes straight to the panel, so don't mix it with
+/// [`Display::frame`] or [`Display::framebuffer`].
+pub struct ScaledDisplay<'a, const N: u32> {
+    framebuffer: Framebuffer<'a>,
+}
+
+impl<'a, const N: u32> ScaledDisplay<'a, N> {
+    /// Pixels of canvas needed in either orientation.
+    pub const PIXELS: usize = ((crate::display::WIDTH / N) * (crate::display::HEIGHT / N)) as usize;
+
+    /// A canvas for `display` in its current orientation, black until
+    /// drawn to.
+    ///
+    /// # Panics
+    ///
+    /// If `N` is 0 or `pixels` is shorter than [`PIXELS`](Self::PIXELS).
+    pub fn new(display: &Display, pixels: &'a mut [Rgb565]) -> Self {
+        assert!(N > 0, "scale must be at least 1");
+        let size = display.size() / N;
+        let mut framebuffer = Framebuffer::new(pixels, size);
+        framebuffer.clear(Rgb565::BLACK).unwrap();
+        Self { framebuffer }
+    }
+
+    /// The canvas as a plain framebuffer, e.g. for
+    /// [`draw_blended`](Framebuffer::draw_blended).
+    pub const fn framebuffer(&mut self) -> &mut Framebuffer<'a> {
+        &mut self.framebuffer
+    }
+
+    /// Scale the region changed since the last flush onto `display`,
+    /// blocking until the transfer is done.
+    pub fn flush(&mut self, display: &mut Display) -> Result<(), Error> {
+        let Some(dirty) = self.framebuffer.take_dirty() else {
+            return Ok(());
+        };
+        let border = (display.size() - self.framebuffer.size() * N) / 2;
+        let area = Rectangle::new(
+            Point::zero() + border + dirty.top_left * N as i32,
+            dirty.size * N,
+        );
+        let width = self.framebuffer.size().width as usize;
+        let (x, w) = (dirty.top_left.x as usize, dirty.size.width as usize);
+        let pixels = self.framebuffer.pixels();
+        let scaled = dirty.rows().flat_map(move |y| {
+            let row = &pixels[y as usize * width + x..][..w];
+            (0..N).flat_map(move |_| {
+                row.iter()
+                    .flat_map(|&color| iter::repeat_n(color, N as usize))
+            })
+        });
+        display.fill_contiguous(&area, scaled)
+    }
+}
+
+impl<const N: u32> OriginDimensions for ScaledDisplay<'_, N> {
+    fn size(&self) -> Size {
+        self.framebuffer.size()
+    }
+}
+
+impl<const N: u32> DrawTarget for ScaledDisplay<'_, N> {
+    type Color = Rgb565;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.framebuffer.draw_iter(pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.framebuffer.fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.framebuffer.fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.framebuffer.clear(color)
+    }
+}
@@ -0,0 +1,216 @@
+//! Random game events that replay the same way from the same seed.
+//!
+//! A [`Schedule`] fires events such as enemy waves and power-up drops at
+//! random ticks, but the randomness comes only from its seed. Two games
+//! started with the same seed and played with the same inputs see the same
+//! events at the same ticks, so a bug can be replayed and scores can be
+//! compared fairly:
+//!
+//! ```rust,ignore
+//! const WAVE: usize = 0;
+//! const POWER_UP: usize = 1;
+//!
+//! let mut schedule = Schedule::new(seed, [Rule::new(200, 300), Rule::new(600, 1200)]);
+//! loop {
+//!     for event in schedule.tick() {
+//!         match event.rule {
+//!             WAVE => spawn_wave(event.roll),
+//!             POWER_UP => drop_power_up(event.roll % 320),
+//!             _ => {}
+//!         }
+//!     }
+//!     ticker.next().await;
+//! }
+//! ```
+//!
+//! Each rule draws from its own stream of numbers, so adding a rule or
+//! changing one's gaps leaves the others' timing as it was.
+
+/// A xorshift32 pseudo-random number generator: fast, small and the same
+/// on every badge. Not for secrets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub struct Random {
+    state: u32,
+}
+
+impl Random {
+    /// A generator that always gives the same numbers for `seed`.
+    pub const fn new(seed: u32) -> Self {
+        // A zero state would stay zero, and close seeds start close, so
+        // scramble the seed first.
+        let state = mix(seed);
+        Self {
+            state: if state == 0 { 0x2545_f491 } else { state },
+        }
+    }
+
+    /// The next number.
+    pub const fn next_u32(&mut self) -> u32 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        self.state
+    }
+
+    /// A number from 0 up to but not including `n`, or 0 if `n` is 0.
+    pub const fn below(&mut self, n: u32) -> u32 {
+        if n == 0 {
+            return 0;
+        }
+        ((self.next_u32() as u64 * n as u64) >> 32) as u32
+    }
+
+    /// A number from `low` to `high`, both included.
+    pub const fn between(&mut self, low: u32, high: u32) -> u32 {
+        if high <= low {
+            return low;
+        }
+        // Wider than u32 when the range is all of it.
+        let span = high as u64 - low as u64 + 1;
+        low + ((self.next_u32() as u64 * span) >> 32) as u32
+    }
+}
+
+/// Scrambles the bits of `x` (the murmur3 finaliser), to turn a seed and a
+/// rule index into an unrelated stream.
+const fn mix(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x85eb_ca6b);
+    x ^= x >> 13;
+    x = x.wrapping_mul(0xc2b2_ae35);
+    x ^= x >> 16;
+    x
+}
+
+/// When an event of one kind happens: after a random gap of `min` to
+/// `max` ticks, again and again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub struct Rule {
+    min: u32,
+    max: u32,
+    first: Option<u32>,
+    count: u32,
+}
+
+impl Rule {
+    /// Fire after `min` to `max` ticks, and again after each fire.
+    pub const fn new(min: u32, max: u32) -> Self {
+        let min = if min == 0 { 1 } else { min };
+        Self {
+            min,
+            max: if max < min { min } else { max },
+            first: None,
+            count: 0,
+        }
+    }
+
+    /// Fire the first time at exactly `tick` instead, such as a wave right
+    /// at the start.
+    #[must_use]
+    pub const fn starting_at(mut self, tick: u32) -> Self {
+        self.first = Some(tick);
+        self
+    }
+
+    /// Stop after firing `count` times. 0, the default, never stops.
+    #[must_use]
+    pub const fn times(mut self, count: u32) -> Self {
+        self.count = count;
+        self
+    }
+}
+
+/// An event fired by a [`Schedule`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub struct Event {
+    /// Index of the rule that fired.
+    pub rule: usize,
+    /// The tick it fired on.
+    pub tick: u32,
+    /// A random number to place or pick what the event makes, from the
+    /// same seeded stream as its timing.
+    pub roll: u32,
+}
+
+/// Where one rule has got to.
+#[derive(Clone, Copy)]
+struct Track {
+    rule: Rule,
+    random: Random,
+    next: u32,
+    fired: u32,
+}
+
+/// Fires the events of `N` [`Rule`]s at ticks worked out from a seed.
+pub struct Schedule<const N: usize> {
+    seed: u32,
+    tracks: [Track; N],
+    tick: u32,
+}
+
+impl<const N: usize> Schedule<N> {
+    /// Rules that fire at ticks worked out from `seed`, starting at tick 0.
+    pub fn new(seed: u32, rules: [Rule; N]) -> Self {
+        let mut tracks = rules.map(|rule| Track {
+            rule,
+            random: Random::new(0),
+            next: 0,
+            fired: 0,
+        });
+        for (i, track) in tracks.iter_mut().enumerate() {
+            track.random = Random::new(seed ^ mix(i as u32 + 1));
+            track.next = match track.rule.first {
+                Some(tick) => tick,
+                None => track.random.between(track.rule.min, track.rule.max),
+            };
+        }
+        Self {
+            seed,
+            tracks,
+            tick: 0,
+        }
+    }
+
+    /// The seed, to show or log so the game can be replayed.
+    pub const fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    /// Ticks counted so far.
+    pub const fn ticks(&self) -> u32 {
+        self.tick
+    }
+
+    /// Start over from tick 0, firing the same events again.
+    pub fn restart(&mut self) {
+        *self = Self::new(self.seed, self.tracks.map(|track| track.rule));
+    }
+
+    /// Change the gaps of rule `index` from its next fire on, such as to
+    /// spawn faster as the game gets harder.
+    pub const fn set_gaps(&mut self, index: usize, min: u32, max: u32) {
+        let rule = &mut self.tracks[index].rule;
+        let new = Rule::new(min, max);
+        rule.min = new.min;
+        rule.max = new.max;
+    }
+
+    /// Count one tick and return the events that fire on it.
+    pub fn tick(&mut self) -> impl Iterator<Item = Event> {
+        let tick = self.tick;
+        self.tick += 1;
+        let mut events = [None; N];
+        for (rule, (track, event)) in self.tracks.iter_mut().zip(&mut events).enumerate() {
+            let done = track.rule.count != 0 && track.fired >= track.rule.count;
+            if done || track.next != tick {
+                continue;
+            }
+            track.fired += 1;
+            let roll = track.random.next_u32();
+            let gap = track.random.between(track.rule.min, track.rule.max);
+            track.next = tick.saturating_add(gap);
+            *event = Some(Event { rule, tick, roll });
+        }
+        events.into_iter().flatten()
+    }
+}
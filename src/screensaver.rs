@@ -0,0 +1,420 @@
+//! Screensaver that takes over the display after a period without input.
+
+use core::convert::Infallible;
+
+use embassy_futures::select::{
+    Either,
+    select,
+};
+use embassy_time::{
+    Duration,
+    Instant,
+    Timer,
+};
+use embedded_graphics::{
+    mono_font::ascii::FONT_10X20,
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::Rectangle,
+};
+use palette::Srgb;
+
+use crate::{
+    Button,
+    Buttons,
+    Display,
+    Leds,
+    Marquee,
+    clock,
+    dial::{
+        ClockFace,
+        sweep_seconds,
+    },
+    display::Error,
+    image::Rgb565Image,
+    schedule::Random,
+};
+
+const FRAME: Duration = Duration::from_millis(33);
+
+/// Frames per breath of the LEDs, about four seconds.
+const BREATH_FRAMES: u32 = 120;
+/// Brightest the LEDs get while breathing.
+const BREATH_PEAK: u32 = 87;
+
+const STARS: usize = 64;
+/// Depth at which stars appear; they fly towards the viewer at z = 0.
+const STAR_DEPTH: i32 = 256;
+const STAR_SPEED: i32 = 4;
+
+/// Strip for the marquee, 800 columns of 20-pixel-high text.
+const MARQUEE_BYTES: usize = 2000;
+const MARQUEE_SCALE: u32 = 3;
+const MARQUEE_SPEED: u32 = 3;
+
+/// Gap between the clock face and the top and bottom of the screen.
+const CLOCK_MARGIN: i32 = 10;
+
+/// What the screensaver shows.
+#[derive(Clone, Copy)]
+pub enum Animation {
+    /// An image drifting around and bouncing off the edges. Transparent
+    /// pixels show as black.
+    Logo(Rgb565Image<'static>),
+    /// Stars flying towards the viewer.
+    Starfield,
+    /// Text, such as the owner's nick, scrolling across the screen.
+    Marquee(&'static str),
+    /// An analog clock showing [`clock::now`], with the LEDs counting the
+    /// seconds instead of breathing.
+    Clock,
+}
+
+/// What [`Screensaver::wait_for_press`] saw.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum Event {
+    /// A button was pressed while the app was showing.
+    Press(Button),
+    /// The screensaver ran and was dismissed. Redraw anything drawn
+    /// directly to the panel; a framebuffer has already been put back.
+    Resumed,
+}
+
+/// Takes over the display and LEDs when no button has been pressed for a
+/// while, and hands them back on the next press.
+///
+/// Event-driven apps swap [`Buttons::wait_for_press`] for
+/// [`wait_for_press`](Self::wait_for_press):
+///
+/// ```rust,ignore
+/// let mut saver = Screensaver::new(Animation::Marquee("h4x0r"));
+/// loop {
+///     match saver.wait_for_press(display, buttons, leds).await? {
+///         Event::Press(Button::A) => { /* ... */ }
+///         Event::Resumed => redraw(display),
+///         _ => {}
+///     }
+/// }
+/// ```
+///
+/// Apps that poll the buttons every frame call [`activity`](Self::activity)
+/// when they see input, and [`run`](Self::run) once [`is_due`](Self::is_due).
+///
+/// Whatever was on the LEDs is restored afterwards. Apps that draw through
+/// [`Display::frame`] or [`Display::framebuffer`] get their last frame back
+/// as well; others need to redraw.
+pub struct Screensaver {
+    animation: Animation,
+    timeout: Duration,
+    last_activity: Instant,
+}
+
+impl Screensaver {
+    /// Show `animation` after a minute without input.
+    pub fn new(animation: Animation) -> Self {
+        Self {
+            animation,
+            timeout: Duration::from_secs(60),
+            last_activity: Instant::now(),
+        }
+    }
+
+    /// Idle time before the screensaver starts.
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Show `animation` from now on.
+    pub const fn set_animation(&mut self, animation: Animation) {
+        self.animation = animation;
+    }
+
+    /// Record input, putting the screensaver off for another timeout.
+    pub fn activity(&mut self) {
+        self.last_activity = Instant::now();
+    }
+
+    /// `true` once the timeout has passed without input.
+    pub fn is_due(&self) -> bool {
+        self.last_activity.elapsed() >= self.timeout
+    }
+
+    /// Show the animation until a button is pressed, then restore the LEDs
+    /// and the framebuffer. The press that ends it is not reported.
+    pub async fn run(
+        &mut self,
+        display: &mut Display<'_>,
+        buttons: &mut Buttons,
+        leds: &mut Leds<'_>,
+    ) -> Result<(), Error> {
+        let saved = leds.colors();
+        display.clear(Rgb565::BLACK)?;
+        let result = match select(
+            buttons.wait_for_press(),
+            animate(self.animation, display, leds),
+        )
+        .await
+        {
+            Either::First(_) => Ok(()),
+            Either::Second(Err(e)) => Err(e),
+        };
+        leds.fill_from_iter(saved);
+        leds.update().await;
+        display.invalidate();
+        display.flush()?;
+        self.activity();
+        result
+    }
+
+    /// Wait for a button press, running the screensaver if none comes
+    /// before the timeout.
+    pub async fn wait_for_press(
+        &mut self,
+        display: &mut Display<'_>,
+        buttons: &mut Buttons,
+        leds: &mut Leds<'_>,
+    ) -> Result<Event, Error> {
+        let deadline = self.last_activity.saturating_add(self.timeout);
+        match select(buttons.wait_for_press(), Timer::at(deadline)).await {
+            Either::First(button) => {
+                self.activity();
+                Ok(Event::Press(button))
+            }
+            Either::Second(()) => {
+                self.run(display, buttons, leds).await?;
+                Ok(Event::Resumed)
+            }
+        }
+    }
+}
+
+/// Play `animation` until cancelled, breathing the LEDs along.
+async fn animate(
+    animation: Animation,
+    display: &mut Display<'_>,
+    leds: &mut Leds<'_>,
+) -> Result<Infallible, Error> {
+    let size = display.size();
+    let mut frame = 0;
+    match animation {
+        Animation::Logo(image) => {
+            let mut logo = Logo::new(image, size);
+            loop {
+                logo.step(display)?;
+                next_frame(leds, &mut frame).await;
+            }
+        }
+        Animation::Starfield => {
+            let mut stars = Starfield::new();
+            loop {
+                stars.step(display)?;
+                next_frame(leds, &mut frame).await;
+            }
+        }
+        Animation::Marquee(text) => {
+            let mut strip = [0; MARQUEE_BYTES];
+            let mut marquee = Marquee::new(&mut strip, FONT_10X20.character_size.height)
+                .with_scale(MARQUEE_SCALE);
+            marquee.push_text(text, &FONT_10X20).push_space(40);
+            let y = (size.height.saturating_sub(marquee.screen_height()) / 2) as i32;
+            loop {
+                marquee.draw(display, Point::new(0, y), size.width)?;
+                marquee.step(MARQUEE_SPEED);
+                next_frame(leds, &mut frame).await;
+            }
+        }
+        Animation::Clock => {
+            let center = Point::new(size.width as i32 / 2, size.height as i32 / 2);
+            let face = ClockFace::new(center, center.y - CLOCK_MARGIN);
+            let mut shown = None;
+            loop {
+                let now = clock::now();
+                if shown != Some(now) {
+                    if let Some(old) = shown {
+                        face.erase_hands(display, old, Rgb565::BLACK)?;
+                    }
+                    face.draw(display, now)?;
+                    shown = Some(now);
+                }
+                sweep_seconds(leds, now);
+                leds.update().await;
+                Timer::after(FRAME).await;
+            }
+        }
+    }
+}
+
+/// Update the LEDs for `frame` and wait for the next one.
+async fn next_frame(leds: &mut Leds<'_>, frame: &mut u32) {
+    leds.fill(breath(*frame));
+    leds.update().await;
+    *frame = (*frame + 1) % BREATH_FRAMES;
+    Timer::after(FRAME).await;
+}
+
+/// A slow blue pulse, `frame` of [`BREATH_FRAMES`] into the breath.
+fn breath(frame: u32) -> Srgb<u8> {
+    let half = BREATH_FRAMES / 2;
+    let rise = if frame < half {
+        frame
+    } else {
+        BREATH_FRAMES - frame
+    };
+    Srgb::new(0, 0, (rise * BREATH_PEAK / half) as u8)
+}
+
+// ── Bouncing logo ───────────────────────────────────────────────────────────
+
+struct Logo {
+    image: Rgb565Image<'static>,
+    /// Area the image moves within.
+    bounds: Size,
+    position: Point,
+    velocity: Point,
+    /// The first step draws the whole image rather than just its motion.
+    drawn: bool,
+}
+
+impl Logo {
+    fn new(image: Rgb565Image<'static>, screen: Size) -> Self {
+        let bounds = screen.saturating_sub(image.size());
+        Self {
+            image,
+            bounds,
+            position: Point::new(bounds.width as i32 / 2, bounds.height as i32 / 3),
+            velocity: Point::new(2, 1),
+            drawn: false,
+        }
+    }
+
+    fn step(&mut self, display: &mut Display<'_>) -> Result<(), Error> {
+        let old = Rectangle::new(self.position, self.image.size());
+        let mut next = self.position + self.velocity;
+        if next.x < 0 || next.x > self.bounds.width as i32 {
+            self.velocity.x = -self.velocity.x;
+            next.x = self.position.x + self.velocity.x;
+        }
+        if next.y < 0 || next.y > self.bounds.height as i32 {
+            self.velocity.y = -self.velocity.y;
+            next.y = self.position.y + self.velocity.y;
+        }
+        let next = next.component_max(Point::zero());
+        if self.drawn && next == self.position {
+            return Ok(());
+        }
+        self.position = next;
+        let area = Rectangle::new(next, self.image.size());
+
+        // Blank the strips the image moved off, then redraw it whole in one
+        // window write so it doesn't flicker.
+        let delta = next - old.top_left;
+        let (w, h) = (old.size.width as i32, old.size.height as i32);
+        let column = if delta.x > 0 {
+            Rectangle::new(old.top_left, Size::new(delta.x as u32, h as u32))
+        } else {
+            let x = old.top_left.x + w + delta.x;
+            Rectangle::new(
+                Point::new(x, old.top_left.y),
+                Size::new(-delta.x as u32, h as u32),
+            )
+        };
+        let row = if delta.y > 0 {
+            Rectangle::new(old.top_left, Size::new(w as u32, delta.y as u32))
+        } else {
+            let y = old.top_left.y + h + delta.y;
+            Rectangle::new(
+                Point::new(old.top_left.x, y),
+                Size::new(w as u32, -delta.y as u32),
+            )
+        };
+        display.fill_solid(&column, Rgb565::BLACK)?;
+        display.fill_solid(&row, Rgb565::BLACK)?;
+
+        let image = &self.image;
+        let width = image.size().width;
+        let pixels = image.pixels().enumerate().map(|(i, color)| {
+            let (x, y) = (i as u32 % width, i as u32 / width);
+            if image.is_opaque(x, y) {
+                color
+            } else {
+                Rgb565::BLACK
+            }
+        });
+        display.fill_contiguous(&area, pixels)?;
+        self.drawn = true;
+        Ok(())
+    }
+}
+
+// ── Starfield ───────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy)]
+struct Star {
+    x: i32,
+    y: i32,
+    z: i32,
+    /// Where the star was last drawn, to erase it.
+    shown: Option<Point>,
+}
+
+struct Starfield {
+    stars: [Star; STARS],
+    random: Random,
+}
+
+impl Starfield {
+    fn new() -> Self {
+        let mut field = Self {
+            stars: [Star {
+                x: 0,
+                y: 0,
+                z: 0,
+                shown: None,
+            }; STARS],
+            // A different sky each time it starts.
+            random: Random::new(Instant::now().as_ticks() as u32),
+        };
+        for i in 0..STARS {
+            field.respawn(i);
+            field.stars[i].z = (i as i32 + 1) * STAR_DEPTH / STARS as i32;
+        }
+        field
+    }
+
+    fn respawn(&mut self, i: usize) {
+        let x = self.random.below(512) as i32 - 256;
+        let y = self.random.below(512) as i32 - 256;
+        let shown = self.stars[i].shown;
+        self.stars[i] = Star {
+            x,
+            y,
+            z: STAR_DEPTH,
+            shown,
+        };
+    }
+
+    fn step(&mut self, display: &mut Display<'_>) -> Result<(), Error> {
+        let screen = display.bounding_box();
+        let center = screen.center();
+        for i in 0..STARS {
+            if let Some(point) = self.stars[i].shown.take() {
+                display.fill_solid(&Rectangle::new(point, Size::new(2, 2)), Rgb565::BLACK)?;
+            }
+            self.stars[i].z -= STAR_SPEED;
+            let Star { x, y, z, .. } = self.stars[i];
+            let point = center + Point::new(x * 64 / z.max(1), y * 64 / z.max(1));
+            if z <= 0 || !screen.contains(point) {
+                self.respawn(i);
+                continue;
+            }
+            // Nearer stars are brighter.
+            let level = (255 - z * 255 / STAR_DEPTH).clamp(64, 255) as u8;
+            let color = Rgb565::new(level >> 3, level >> 2, level >> 3);
+            display.fill_solid(&Rectangle::new(point, Size::new(2, 2)), color)?;
+            self.stars[i].shown = Some(point);
+        }
+        Ok(())
+    }
+}
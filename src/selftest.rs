@@ -0,0 +1,280 @@
+//! Hardware checks that need nobody at the badge, for validating a batch of
+//! badges before an event.
+//!
+//! Each check exercises one peripheral and says whether it worked, and
+//! [`run`] does them all. Checks can't see whether an LED or pixel actually
+//! lights up; they catch dead buses, stuck inputs and failed transfers.
+//! [`identify_leds`] is for a person to watch instead.
+//!
+//! [`report`] logs the results one line per check, for a script reading
+//! the log of a whole batch:
+//!
+//! ```text
+//! SELFTEST <name> PASS
+//! SELFTEST <name> FAIL <reason>
+//! SELFTEST overall PASS|FAIL
+//! ```
+//!
+//! A badge can run them whenever a `selftest` line arrives on the USB
+//! serial port, so a script needs no button presses:
+//!
+//! ```rust,ignore
+//! loop {
+//!     match lines.next().await {
+//!         "selftest" => {
+//!             selftest::report(&selftest::run(display, leds, buttons, mic).await);
+//!         }
+//!         line => warn!("Unknown command {=str}", line),
+//!     }
+//! }
+//! ```
+
+use defmt::info;
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        ascii::FONT_10X20,
+    },
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::{
+        PrimitiveStyle,
+        Triangle,
+    },
+    text::{
+        Alignment,
+        Text,
+    },
+};
+use esp_hal::rng::Rng;
+use palette::Srgb;
+
+use crate::{
+    Buttons,
+    Chord,
+    Display,
+    LedError,
+    Leds,
+    Microphone,
+    display::Error as DisplayError,
+    font::FONT_20X40,
+    leds::{
+        BAR_COUNT,
+        LED_COUNT,
+    },
+};
+
+/// Why a check failed.
+#[derive(Clone, Copy, Debug, PartialEq, defmt::Format)]
+pub enum Failure {
+    /// Writing to the display over SPI failed.
+    Display,
+    /// The LED update didn't complete.
+    Leds(LedError),
+    /// These buttons read as held with nobody touching them.
+    ButtonsHeld(Chord),
+    /// The microphone DMA read failed.
+    MicrophoneRead,
+    /// Every microphone sample was the same, so its data line is stuck.
+    MicrophoneSilent,
+    /// The random number generator repeated itself.
+    Rng,
+}
+
+/// The result of one check.
+#[derive(Clone, Copy, Debug, PartialEq, defmt::Format)]
+pub struct Check {
+    /// Short name for logs, e.g. `"leds"`.
+    pub name: &'static str,
+    /// What went wrong, if anything.
+    pub result: Result<(), Failure>,
+}
+
+impl Check {
+    /// `true` if the check passed.
+    pub const fn passed(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// Draw the display test pattern, which writes every pixel.
+pub fn display(display: &mut Display<'_>) -> Result<(), Failure> {
+    display.test_pattern().map_err(|_| Failure::Display)
+}
+
+/// Send red, green, blue and then black to the LEDs. The LED colours are
+/// restored afterwards but not sent.
+pub async fn leds(leds: &mut Leds<'_>) -> Result<(), Failure> {
+    let saved = leds.colors();
+    let mut result = Ok(());
+    for color in [
+        Srgb::new(80, 0, 0),
+        Srgb::new(0, 80, 0),
+        Srgb::new(0, 0, 80),
+        Srgb::new(0, 0, 0),
+    ] {
+        leds.fill(color);
+        result = result.and(leds.try_update().await.map_err(Failure::Leds));
+    }
+    leds.fill_from_iter(saved);
+    result
+}
+
+/// Check that no button is held, e.g. by a short or a stuck switch.
+pub fn buttons(buttons: &Buttons) -> Result<(), Failure> {
+    let held = buttons.held();
+    if held.is_empty() {
+        Ok(())
+    } else {
+        Err(Failure::ButtonsHeld(held))
+    }
+}
+
+/// Read a block of samples and check that they vary. Even a quiet room
+/// gives some noise; a flat line means no data is arriving.
+pub fn microphone(mic: &mut Microphone<'_>) -> Result<(), Failure> {
+    let mut samples = [0i16; 256];
+    mic.rx
+        .read_words(&mut samples)
+        .map_err(|_| Failure::MicrophoneRead)?;
+    if samples.iter().all(|&s| s == samples[0]) {
+        Err(Failure::MicrophoneSilent)
+    } else {
+        Ok(())
+    }
+}
+
+/// Draw a few words from the hardware random number generator and check
+/// that they differ.
+pub fn rng() -> Result<(), Failure> {
+    let rng = Rng::new();
+    let mut words = [0u32; 8];
+    for word in &mut words {
+        *word = rng.random();
+    }
+    let repeated = words
+        .iter()
+        .enumerate()
+        .any(|(i, word)| words[i + 1..].contains(word));
+    if repeated { Err(Failure::Rng) } else { Ok(()) }
+}
+
+/// Light each LED in turn for a second, with its index and place shown
+/// on `display`, to match buffer indices to the LEDs on the badge while
+/// writing effects. The LEDs get their colours back afterwards.
+///
+/// It draws straight to the panel and leaves it black; an app using the
+/// framebuffer gets its last frame back with the next flush.
+pub async fn identify_leds(leds: &mut Leds<'_>, display: &mut Display<'_>) -> Result<(), Failure> {
+    let saved = leds.colors();
+    let mut result = Ok(());
+    for index in 0..LED_COUNT {
+        if result.is_ok() {
+            result = draw_led_label(display, index, leds.position(index).y)
+                .map_err(|_| Failure::Display);
+        }
+        leds.clear();
+        leds.set(index, Srgb::new(255, 255, 255));
+        leds.update().await;
+        Timer::after(Duration::from_secs(1)).await;
+    }
+    if result.is_ok() {
+        result = display.clear(Rgb565::BLACK).map_err(|_| Failure::Display);
+    }
+    display.invalidate();
+    leds.fill_from_iter(saved);
+    leds.update().await;
+    result
+}
+
+/// LED `index`'s number and place, with an arrow at the screen's edge
+/// level with `y`, laid out on whatever size the screen is.
+fn draw_led_label(display: &mut Display<'_>, index: usize, y: i32) -> Result<(), DisplayError> {
+    /// Places on a bar, from the bottom.
+    const PLACES: [&str; BAR_COUNT] = [
+        "bottom",
+        "second from bottom",
+        "middle",
+        "second from top",
+        "top",
+    ];
+    let screen = display.bounding_box();
+    let center = screen.center();
+    let right = screen.size.width as i32 - 1;
+    let (side, place, edge, tip) = if index < BAR_COUNT {
+        ("right bar", PLACES[index], right - 22, right)
+    } else {
+        ("left bar", PLACES[LED_COUNT - 1 - index], 22, 0)
+    };
+    display.clear(Rgb565::BLACK)?;
+    let y = y.clamp(12, screen.size.height as i32 - 13);
+    Triangle::new(
+        Point::new(tip, y),
+        Point::new(edge, y - 12),
+        Point::new(edge, y + 12),
+    )
+    .into_styled(PrimitiveStyle::with_fill(Rgb565::CSS_ORANGE))
+    .draw(display)?;
+    // "LED n", five 20-pixel characters wide.
+    let big = MonoTextStyle::new(&FONT_20X40, Rgb565::WHITE);
+    let next = Text::new("LED ", center - Point::new(50, 15), big).draw(display)?;
+    Text::new(&"0123456789"[index..=index], next, big).draw(display)?;
+    let small = MonoTextStyle::new(&FONT_10X20, Rgb565::CSS_ORANGE);
+    for (line, dy) in [(side, 20), (place, 43)] {
+        Text::with_alignment(line, center + Point::new(0, dy), small, Alignment::Center)
+            .draw(display)?;
+    }
+    Ok(())
+}
+
+/// Run every check, in the order they're listed here.
+pub async fn run(
+    display: &mut Display<'_>,
+    leds: &mut Leds<'_>,
+    buttons: &Buttons,
+    mic: &mut Microphone<'_>,
+) -> [Check; 5] {
+    [
+        Check {
+            name: "display",
+            result: self::display(display),
+        },
+        Check {
+            name: "leds",
+            result: self::leds(leds).await,
+        },
+        Check {
+            name: "buttons",
+            result: self::buttons(buttons),
+        },
+        Check {
+            name: "microphone",
+            result: self::microphone(mic),
+        },
+        Check {
+            name: "rng",
+            result: self::rng(),
+        },
+    ]
+}
+
+/// Log one `SELFTEST` line per check and an overall verdict, as in the
+/// module docs. Returns `true` if every check passed.
+pub fn report(checks: &[Check]) -> bool {
+    for check in checks {
+        match check.result {
+            Ok(()) => info!("SELFTEST {=str} PASS", check.name),
+            Err(failure) => info!("SELFTEST {=str} FAIL {}", check.name, failure),
+        }
+    }
+    let passed = checks.iter().all(Check::passed);
+    info!(
+        "SELFTEST overall {=str}",
+        if passed { "PASS" } else { "FAIL" }
+    );
+    passed
+}
@@ -0,0 +1,96 @@
+//! Commands typed into the USB serial port.
+//!
+//! The badge's USB port is a serial port as well as the flashing and log
+//! link. [`Lines`] reads what a terminal or a script sends to it, a line at
+//! a time:
+//!
+//! ```rust,ignore
+//! let (rx, _) = UsbSerialJtag::new(peripherals.USB_DEVICE).split();
+//! let mut lines = Lines::new(rx);
+//! loop {
+//!     match lines.next().await {
+//!         "selftest" => run_selftest().await,
+//!         line => warn!("Unknown command {=str}", line),
+//!     }
+//! }
+//! ```
+//!
+//! Replies go out through the defmt log, like everything else the badge
+//! prints.
+
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use esp_hal::{
+    Blocking,
+    usb_serial_jtag::UsbSerialJtagRx,
+};
+
+/// Longest line in bytes; longer ones are dropped.
+pub const LINE_BYTES: usize = 256;
+
+/// How often to look for new input.
+const POLL: Duration = Duration::from_millis(50);
+
+/// Reads lines from the USB serial port.
+pub struct Lines {
+    rx: UsbSerialJtagRx<'static, Blocking>,
+    buf: [u8; LINE_BYTES],
+    /// Bytes in `buf`.
+    len: usize,
+    /// Bytes of the line returned last, removed on the next call.
+    taken: usize,
+    /// Dropping the rest of a line that didn't fit.
+    overflow: bool,
+}
+
+impl Lines {
+    /// Read lines from `rx`.
+    pub const fn new(rx: UsbSerialJtagRx<'static, Blocking>) -> Self {
+        Self {
+            rx,
+            buf: [0; LINE_BYTES],
+            len: 0,
+            taken: 0,
+            overflow: false,
+        }
+    }
+
+    /// Wait for the next line that isn't blank, trimmed of spaces and its
+    /// line break. Lines that are too long or not UTF-8 are dropped.
+    ///
+    /// Safe to cancel, such as in a `select` with a timer: input already
+    /// read is kept for the next call.
+    pub async fn next(&mut self) -> &str {
+        let end = loop {
+            self.buf.copy_within(self.taken..self.len, 0);
+            self.len -= self.taken;
+            self.taken = 0;
+            // Terminals send Enter as `\r`, scripts as `\n`.
+            let Some(end) = self.buf[..self.len]
+                .iter()
+                .position(|&b| b == b'\r' || b == b'\n')
+            else {
+                if self.len == LINE_BYTES {
+                    self.len = 0;
+                    self.overflow = true;
+                }
+                let n = self.rx.drain_rx_fifo(&mut self.buf[self.len..]);
+                self.len += n;
+                if n == 0 {
+                    Timer::after(POLL).await;
+                }
+                continue;
+            };
+            self.taken = end + 1;
+            if core::mem::take(&mut self.overflow) {
+                continue;
+            }
+            if core::str::from_utf8(&self.buf[..end]).is_ok_and(|line| !line.trim().is_empty()) {
+                break end;
+            }
+        };
+        core::str::from_utf8(&self.buf[..end]).unwrap_or("").trim()
+    }
+}
@@ -0,0 +1,196 @@
+//! Big seven-segment digits drawn from rectangles, at any size.
+
+use embedded_graphics::{
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::Rectangle,
+};
+
+/// Segments lit for `0`–`9`, bit 0 = a (top) through bit 6 = g (middle).
+const DIGITS: [u8; 10] = [
+    0b011_1111, 0b000_0110, 0b101_1011, 0b100_1111, 0b110_0110, 0b110_1101, 0b111_1101, 0b000_0111,
+    0b111_1111, 0b110_1111,
+];
+
+/// Segments lit for a character, or `None` if it isn't a digit cell.
+const fn segments(c: char) -> Option<u8> {
+    Some(match c {
+        '0'..='9' => DIGITS[c as usize - '0' as usize],
+        'A' | 'a' => 0b111_0111,
+        'B' | 'b' => 0b111_1100,
+        'C' | 'c' => 0b011_1001,
+        'D' | 'd' => 0b101_1110,
+        'E' | 'e' => 0b111_1001,
+        'F' | 'f' => 0b111_0001,
+        '-' => 0b100_0000,
+        '_' => 0b000_1000,
+        ' ' => 0,
+        _ => return None,
+    })
+}
+
+/// Text in seven-segment digits, like an LED clock, for times, countdowns
+/// and scores too big for a bitmap font.
+///
+/// Shows `0`–`9`, `A`–`F`, `-`, `_` and spaces as digits, and `:` and `.`
+/// as narrow dots; anything else is skipped. Digits are half as wide as
+/// they are high unless set otherwise:
+///
+/// ```rust,ignore
+/// SevenSegment::new("12:34", Point::new(20, 40), 80, Rgb565::RED)
+///     .with_off_color(Rgb565::new(4, 0, 0))
+///     .draw(display)?;
+/// ```
+#[derive(Clone, Copy)]
+pub struct SevenSegment<'a> {
+    text: &'a str,
+    top_left: Point,
+    digit: Size,
+    thickness: u32,
+    spacing: u32,
+    color: Rgb565,
+    off_color: Option<Rgb565>,
+}
+
+impl<'a> SevenSegment<'a> {
+    /// `text` with its top left at `top_left`, `height` pixels high.
+    pub const fn new(text: &'a str, top_left: Point, height: u32, color: Rgb565) -> Self {
+        let thickness = if height >= 20 { height / 10 } else { 2 };
+        Self {
+            text,
+            top_left,
+            digit: Size::new(height / 2, height),
+            thickness,
+            spacing: thickness,
+            color,
+            off_color: None,
+        }
+    }
+
+    /// Width of each digit.
+    #[must_use]
+    pub const fn with_digit_width(mut self, width: u32) -> Self {
+        self.digit.width = width;
+        self
+    }
+
+    /// Width of the segments and dots.
+    #[must_use]
+    pub const fn with_thickness(mut self, thickness: u32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Gap between characters.
+    #[must_use]
+    pub const fn with_spacing(mut self, spacing: u32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Draw unlit segments too, in `color`, like a real display.
+    #[must_use]
+    pub const fn with_off_color(mut self, color: Rgb565) -> Self {
+        self.off_color = Some(color);
+        self
+    }
+
+    /// Width of a character, without the spacing after it.
+    fn advance(&self, c: char) -> Option<u32> {
+        match c {
+            ':' | '.' => Some(self.thickness),
+            _ => segments(c).map(|_| self.digit.width),
+        }
+    }
+
+    /// The seven segments of a digit at `x`, a (top) to g (middle).
+    fn segment_areas(&self, x: i32) -> [Rectangle; 7] {
+        let (w, h, t) = (self.digit.width, self.digit.height, self.thickness);
+        let middle = h.saturating_sub(t) / 2;
+        let across = Size::new(w.saturating_sub(2 * t), t);
+        let upper = Size::new(t, middle.saturating_sub(t));
+        let lower = Size::new(t, h.saturating_sub(middle + 2 * t));
+        let at = |dx: u32, dy: u32, size: Size| {
+            Rectangle::new(self.top_left + Point::new(x + dx as i32, dy as i32), size)
+        };
+        let right = w.saturating_sub(t);
+        [
+            at(t, 0, across),
+            at(right, t, upper),
+            at(right, middle + t, lower),
+            at(t, h.saturating_sub(t), across),
+            at(0, middle + t, lower),
+            at(0, t, upper),
+            at(t, middle, across),
+        ]
+    }
+}
+
+impl Dimensions for SevenSegment<'_> {
+    fn bounding_box(&self) -> Rectangle {
+        let mut width = 0;
+        for advance in self.text.chars().filter_map(|c| self.advance(c)) {
+            width += advance + self.spacing;
+        }
+        let width = width.saturating_sub(self.spacing);
+        Rectangle::new(self.top_left, Size::new(width, self.digit.height))
+    }
+}
+
+impl Transform for SevenSegment<'_> {
+    fn translate(&self, by: Point) -> Self {
+        Self {
+            top_left: self.top_left + by,
+            ..*self
+        }
+    }
+
+    fn translate_mut(&mut self, by: Point) -> &mut Self {
+        self.top_left += by;
+        self
+    }
+}
+
+impl Drawable for SevenSegment<'_> {
+    type Color = Rgb565;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let t = self.thickness;
+        let mut x = 0;
+        for c in self.text.chars() {
+            let Some(advance) = self.advance(c) else {
+                continue;
+            };
+            let dot = |y: u32| {
+                Rectangle::new(self.top_left + Point::new(x, y as i32), Size::new_equal(t))
+            };
+            match c {
+                ':' => {
+                    let h = self.digit.height;
+                    target.fill_solid(&dot(h / 3 - t / 2), self.color)?;
+                    target.fill_solid(&dot(h * 2 / 3 - t / 2), self.color)?;
+                }
+                '.' => target.fill_solid(&dot(self.digit.height - t), self.color)?,
+                _ => {
+                    let lit = segments(c).unwrap_or(0);
+                    for (i, area) in self.segment_areas(x).iter().enumerate() {
+                        let color = if lit & (1 << i) != 0 {
+                            self.color
+                        } else if let Some(off) = self.off_color {
+                            off
+                        } else {
+                            continue;
+                        };
+                        target.fill_solid(area, color)?;
+                    }
+                }
+            }
+            x += (advance + self.spacing) as i32;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+//! Sprites: RGB565 pixel slices drawn with transparency and clipping.
+//!
+//! A [`Sprite`] wraps pixels that live anywhere — a `const` table, a buffer
+//! filled at runtime, or a region copied out of a
+//! [`Framebuffer`](crate::Framebuffer) — and blits them with
+//! [`Sprite::blit`]. Only the part inside the target's bounds is touched, and
+//! each horizontal run of opaque pixels goes out as one window write.
+//!
+//! ```rust,ignore
+//! const SHIP: Sprite = Sprite::new(8, 8, &SHIP_PIXELS).with_color_key(Rgb565::MAGENTA);
+//!
+//! SHIP.blit(display, Point::new(x, y))?;
+//! ```
+
+use embedded_graphics::{
+    image::ImageDrawable,
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::Rectangle,
+};
+
+/// How a [`Sprite`] marks the pixels to skip.
+#[derive(Clone, Copy)]
+pub enum Transparency<'a> {
+    /// Every pixel is drawn.
+    Opaque,
+    /// Pixels of this colour are skipped.
+    ColorKey(Rgb565),
+    /// One bit per pixel, MSB first, rows padded to a whole byte (the layout
+    /// of `ImageRaw<BinaryColor>`). Pixels with a clear bit are skipped.
+    Mask(&'a [u8]),
+}
+
+/// An RGB565 sprite, row-major, top row first.
+#[derive(Clone, Copy)]
+pub struct Sprite<'a> {
+    width: u32,
+    height: u32,
+    pixels: &'a [Rgb565],
+    transparency: Transparency<'a>,
+}
+
+impl<'a> Sprite<'a> {
+    /// A fully opaque sprite.
+    ///
+    /// # Panics
+    ///
+    /// If `pixels` is smaller than `width` × `height`.
+    pub const fn new(width: u32, height: u32, pixels: &'a [Rgb565]) -> Self {
+        assert!(pixels.len() >= (width * height) as usize);
+        Self {
+            width,
+            height,
+            pixels,
+            transparency: Transparency::Opaque,
+        }
+    }
+
+    /// Skip every pixel of colour `key`.
+    #[must_use]
+    pub const fn with_color_key(self, key: Rgb565) -> Self {
+        Self {
+            transparency: Transparency::ColorKey(key),
+            ..self
+        }
+    }
+
+    /// Only draw the pixels whose bit is set in `mask`, see
+    /// [`Transparency::Mask`].
+    ///
+    /// # Panics
+    ///
+    /// If `mask` is smaller than `height` rows of `width` bits, each row
+    /// padded to a whole byte.
+    #[must_use]
+    pub const fn with_mask(self, mask: &'a [u8]) -> Self {
+        assert!(mask.len() >= (self.height * self.width.div_ceil(8)) as usize);
+        Self {
+            transparency: Transparency::Mask(mask),
+            ..self
+        }
+    }
+
+    /// The raw pixels, row-major.
+    pub const fn pixels(&self) -> &'a [Rgb565] {
+        self.pixels
+    }
+
+    /// `true` if the pixel at `(x, y)` should be drawn.
+    pub fn is_opaque(&self, x: u32, y: u32) -> bool {
+        match self.transparency {
+            Transparency::Opaque => true,
+            Transparency::ColorKey(key) => self.pixels[self.index(x, y)] != key,
+            Transparency::Mask(mask) => {
+                let byte = mask[(y * self.width.div_ceil(8) + x / 8) as usize];
+                byte & (0x80 >> (x % 8)) != 0
+            }
+        }
+    }
+
+    /// Draw the sprite with its top left corner at `top_left`, clipped to
+    /// `target`'s bounds.
+    pub fn blit<D>(&self, target: &mut D, top_left: Point) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let area = Rectangle::new(top_left, self.size());
+        let visible = area.intersection(&target.bounding_box());
+        if visible.is_zero_sized() {
+            return Ok(());
+        }
+        // The visible part in sprite coordinates.
+        let start = (visible.top_left - top_left).x as u32;
+        let end = start + visible.size.width;
+        let rows = visible.rows().map(|y| (y, (y - top_left.y) as u32));
+
+        if let Transparency::Opaque = self.transparency {
+            let pixels = rows.flat_map(|(_, sy)| {
+                let row = self.index(0, sy);
+                self.pixels[row + start as usize..row + end as usize]
+                    .iter()
+                    .copied()
+            });
+            return target.fill_contiguous(&visible, pixels);
+        }
+
+        for (y, sy) in rows {
+            let mut x = start;
+            while x < end {
+                while x < end && !self.is_opaque(x, sy) {
+                    x += 1;
+                }
+                let run = x;
+                while x < end && self.is_opaque(x, sy) {
+                    x += 1;
+                }
+                if x > run {
+                    let run_area = Rectangle::new(
+                        Point::new(top_left.x + run as i32, y),
+                        Size::new(x - run, 1),
+                    );
+                    let pixels = &self.pixels[self.index(run, sy)..self.index(x, sy)];
+                    target.fill_contiguous(&run_area, pixels.iter().copied())?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    const fn index(&self, x: u32, y: u32) -> usize {
+        (y * self.width + x) as usize
+    }
+}
+
+impl OriginDimensions for Sprite<'_> {
+    fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+}
+
+impl ImageDrawable for Sprite<'_> {
+    type Color = Rgb565;
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.blit(target, Point::zero())
+    }
+
+    fn draw_sub_image<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.blit(
+            &mut target.clipped(&Rectangle::new(Point::zero(), area.size)),
+            -area.top_left,
+        )
+    }
+}
@@ -0,0 +1,145 @@
+//! Word-wrapped, aligned text.
+
+use embedded_graphics::{
+    prelude::*,
+    primitives::Rectangle,
+    text::{
+        Alignment,
+        Baseline,
+        Text,
+        renderer::TextRenderer,
+    },
+};
+
+/// Text wrapped to the width of a box and aligned within it.
+///
+/// Lines break at spaces and `\n`; a word wider than the box is split
+/// between characters. Lines that would extend below the box are not drawn.
+/// Drawing returns the height of the lines drawn, so boxes can be stacked:
+///
+/// ```rust,ignore
+/// let area = Rectangle::new(Point::new(0, 40), Size::new(320, 130));
+/// let height = TextBox::new("GAME OVER", area, big)
+///     .with_alignment(Alignment::Center)
+///     .draw(display)?;
+/// let below = Rectangle::new(area.top_left + Point::new(0, height as i32 + 4), area.size);
+/// TextBox::new(hint, below, small).with_alignment(Alignment::Center).draw(display)?;
+/// ```
+#[derive(Clone, Copy)]
+pub struct TextBox<'a, S> {
+    text: &'a str,
+    bounds: Rectangle,
+    style: S,
+    alignment: Alignment,
+}
+
+impl<'a, S> TextBox<'a, S>
+where
+    S: TextRenderer + Clone,
+{
+    /// Left-aligned `text` inside `bounds`.
+    pub const fn new(text: &'a str, bounds: Rectangle, style: S) -> Self {
+        Self {
+            text,
+            bounds,
+            style,
+            alignment: Alignment::Left,
+        }
+    }
+
+    /// Align each line to the left, center or right of the box.
+    #[must_use]
+    pub const fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// The wrapped lines, without the spaces they were broken at.
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.text.split('\n').flat_map(move |paragraph| {
+            let mut rest = Some(paragraph);
+            core::iter::from_fn(move || {
+                let text = rest?;
+                let (line, tail) = text.split_at(self.line_end(text));
+                let tail = tail.trim_start_matches(' ');
+                rest = (!tail.is_empty()).then_some(tail);
+                Some(line.trim_end_matches(' '))
+            })
+        })
+    }
+
+    /// Height of the whole wrapped text, whether or not it fits the box.
+    pub fn height(&self) -> u32 {
+        self.lines().count() as u32 * self.style.line_height()
+    }
+
+    /// Byte offset where the first line of `text` ends.
+    fn line_end(&self, text: &str) -> usize {
+        let fits = |end: usize| self.width_of(&text[..end]) <= self.bounds.size.width;
+        let word_ends = text
+            .match_indices(' ')
+            .map(|(i, _)| i)
+            .chain([text.len()])
+            .filter(|&i| i > 0 && !text[..i].ends_with(' '));
+        let mut end = 0;
+        for i in word_ends {
+            if !fits(i) {
+                break;
+            }
+            end = i;
+        }
+        if end > 0 || text.is_empty() {
+            return end;
+        }
+        // Not even one word fits: break it, keeping at least one character.
+        let mut boundaries = text
+            .char_indices()
+            .map(|(i, _)| i)
+            .skip(1)
+            .chain([text.len()]);
+        let first = boundaries.next().unwrap_or(text.len());
+        boundaries.take_while(|&i| fits(i)).last().unwrap_or(first)
+    }
+
+    fn width_of(&self, line: &str) -> u32 {
+        self.style
+            .measure_string(line, Point::zero(), Baseline::Top)
+            .bounding_box
+            .size
+            .width
+    }
+}
+
+impl<S> Drawable for TextBox<'_, S>
+where
+    S: TextRenderer + Clone,
+{
+    type Color = S::Color;
+    /// Height of the lines drawn.
+    type Output = u32;
+
+    fn draw<D>(&self, target: &mut D) -> Result<u32, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let line_height = self.style.line_height();
+        let bottom = self.bounds.top_left.y + self.bounds.size.height as i32;
+        let mut y = self.bounds.top_left.y;
+        for line in self.lines() {
+            if y + line_height as i32 > bottom {
+                break;
+            }
+            let spare = self.bounds.size.width.saturating_sub(self.width_of(line)) as i32;
+            let x = self.bounds.top_left.x
+                + match self.alignment {
+                    Alignment::Left => 0,
+                    Alignment::Center => spare / 2,
+                    Alignment::Right => spare,
+                };
+            Text::with_baseline(line, Point::new(x, y), self.style.clone(), Baseline::Top)
+                .draw(target)?;
+            y += line_height as i32;
+        }
+        Ok((y - self.bounds.top_left.y) as u32)
+    }
+}
@@ -0,0 +1,129 @@
+//! Tick-counted timers for game effects that last or repeat a number of
+//! frames.
+
+/// Counts down from a number of ticks, for effects that last a while after
+/// something happens: a flash on a hit, invulnerability after losing a
+/// life, a delay before a power-up can be used again.
+///
+/// ```rust,ignore
+/// if hit_brick {
+///     flash.start(6);
+/// }
+/// flash.tick();
+/// if flash.is_active() {
+///     let level = flash.remaining() as u8 * 14;
+///     leds.fill(Srgb::new(level, level, level));
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, defmt::Format)]
+pub struct Cooldown {
+    remaining: u32,
+}
+
+impl Cooldown {
+    /// A cooldown that isn't running.
+    pub const fn new() -> Self {
+        Self { remaining: 0 }
+    }
+
+    /// Run for `ticks` ticks from now, restarting if already running.
+    pub const fn start(&mut self, ticks: u32) {
+        self.remaining = ticks;
+    }
+
+    /// End it early.
+    pub const fn stop(&mut self) {
+        self.remaining = 0;
+    }
+
+    /// Count one tick, and return `true` on the tick that ends it.
+    pub const fn tick(&mut self) -> bool {
+        if self.remaining == 0 {
+            return false;
+        }
+        self.remaining -= 1;
+        self.remaining == 0
+    }
+
+    /// `true` while it has ticks left.
+    pub const fn is_active(&self) -> bool {
+        self.remaining > 0
+    }
+
+    /// Ticks left, e.g. to fade an effect out as it ends.
+    pub const fn remaining(&self) -> u32 {
+        self.remaining
+    }
+
+    /// While active, alternate `true` and `false` every `half` ticks, for
+    /// a sprite that blinks while invulnerable. `false` once it's over.
+    pub const fn blink(&self, half: u32) -> bool {
+        self.is_active() && (self.remaining / if half == 0 { 1 } else { half }).is_multiple_of(2)
+    }
+}
+
+/// Fires every `period` ticks, for things that happen at a steady cadence:
+/// spawning an enemy, moving a slow snake, blinking a cursor.
+///
+/// ```rust,ignore
+/// let mut spawn = Periodic::new(90);
+/// loop {
+///     if spawn.tick() {
+///         enemies.push(Enemy::new());
+///     }
+///     cursor_visible = cursor.is_on();
+///     cursor.tick();
+///     ticker.next().await;
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub struct Periodic {
+    period: u32,
+    count: u32,
+}
+
+impl Periodic {
+    /// Fire every `period` ticks, the first time `period` ticks from now.
+    pub const fn new(period: u32) -> Self {
+        Self {
+            period: if period == 0 { 1 } else { period },
+            count: 0,
+        }
+    }
+
+    /// Count one tick, and return `true` if it's time to fire.
+    pub const fn tick(&mut self) -> bool {
+        self.count += 1;
+        if self.count >= self.period {
+            self.count = 0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Start the period over from now.
+    pub const fn reset(&mut self) {
+        self.count = 0;
+    }
+
+    /// Change the period, e.g. to speed a game up, keeping the ticks
+    /// counted so far.
+    pub const fn set_period(&mut self, period: u32) {
+        self.period = if period == 0 { 1 } else { period };
+        if self.count >= self.period {
+            self.count = self.period - 1;
+        }
+    }
+
+    /// Ticks counted since it last fired.
+    pub const fn phase(&self) -> u32 {
+        self.count
+    }
+
+    /// `true` for the first half of each period and `false` for the
+    /// second, for a blink or a toggle at that cadence.
+    pub const fn is_on(&self) -> bool {
+        self.count < self.period.div_ceil(2)
+    }
+}
@@ -0,0 +1,255 @@
+//! Keyframed light shows.
+//!
+//! A [`Timeline`] goes through a list of [`Keyframe`]s, each a time and the
+//! LED colours at that time, and works out the colours in between. Start it
+//! on a game event or the first beat of a tune, and it stays in step:
+//!
+//! ```rust,ignore
+//! const SHOW: [Keyframe; 3] = [
+//!     Keyframe::fill(Duration::from_millis(0), Srgb::new(0, 0, 0)),
+//!     Keyframe::fill(Duration::from_millis(500), Srgb::new(255, 0, 80)).with_ease(Ease::Step),
+//!     Keyframe::fill(Duration::from_millis(1500), Srgb::new(0, 0, 0)),
+//! ];
+//! let mut show = Timeline::new(&SHOW);
+//! show.play();
+//! loop {
+//!     if show.render(leds, Instant::now()) {
+//!         leds.update().await;
+//!     }
+//!     ticker.next().await;
+//! }
+//! ```
+//!
+//! Keyframes can hold effect parameters instead of colours, anything that
+//! implements [`Tween`], such as a hue to pass to
+//! [`Leds::fill_rainbow`](crate::Leds::fill_rainbow) or a speed.
+
+use embassy_time::{
+    Duration,
+    Instant,
+    Timer,
+};
+use palette::Srgb;
+
+use crate::{
+    Leds,
+    leds::{
+        LED_COUNT,
+        mix,
+    },
+};
+
+/// Time between renders in [`Timeline::run`].
+const FRAME: Duration = Duration::from_millis(20);
+
+/// A value a [`Timeline`] can move smoothly between keyframes.
+pub trait Tween: Copy {
+    /// The value `amount / 256` of the way from `from` to `to`.
+    fn tween(from: Self, to: Self, amount: u32) -> Self;
+}
+
+impl Tween for Srgb<u8> {
+    fn tween(from: Self, to: Self, amount: u32) -> Self {
+        mix(from, to, amount)
+    }
+}
+
+impl<const N: usize> Tween for [Srgb<u8>; N] {
+    fn tween(from: Self, to: Self, amount: u32) -> Self {
+        core::array::from_fn(|i| mix(from[i], to[i], amount))
+    }
+}
+
+macro_rules! tween_int {
+    ($($t:ty),*) => {$(
+        impl Tween for $t {
+            fn tween(from: Self, to: Self, amount: u32) -> Self {
+                let (from, to) = (i64::from(from), i64::from(to));
+                (from + (to - from) * i64::from(amount) / 256) as $t
+            }
+        }
+    )*};
+}
+tween_int!(u8, u16, u32, i16, i32);
+
+/// How a [`Timeline`] gets from one keyframe to the next.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum Ease {
+    /// Evenly.
+    Linear,
+    /// Slowly at both ends and quickest in the middle.
+    InOut,
+    /// Not at all: hold the keyframe, then jump to the next one.
+    Step,
+}
+
+impl Ease {
+    /// `amount` (out of 256) of the way through, eased.
+    const fn apply(self, amount: u32) -> u32 {
+        match self {
+            Self::Linear => amount,
+            // Smoothstep: 3t² - 2t³.
+            Self::InOut => amount * amount * (3 * 256 - 2 * amount) / (256 * 256),
+            Self::Step => 0,
+        }
+    }
+}
+
+/// A value a [`Timeline`] passes through at a time since it started.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Keyframe<T = [Srgb<u8>; LED_COUNT]> {
+    /// When, from the start of the timeline.
+    pub at: Duration,
+    /// The colours or parameters at that time.
+    pub value: T,
+    /// How to get from here to the next keyframe.
+    pub ease: Ease,
+}
+
+impl<T> Keyframe<T> {
+    /// `value` at `at`, moving on to the next keyframe evenly.
+    pub const fn new(at: Duration, value: T) -> Self {
+        Self {
+            at,
+            value,
+            ease: Ease::Linear,
+        }
+    }
+
+    /// Move on to the next keyframe with `ease` instead.
+    #[must_use]
+    pub const fn with_ease(mut self, ease: Ease) -> Self {
+        self.ease = ease;
+        self
+    }
+}
+
+impl Keyframe {
+    /// Every LED `color` at `at`.
+    pub const fn fill(at: Duration, color: Srgb<u8>) -> Self {
+        Self::new(at, [color; LED_COUNT])
+    }
+}
+
+/// Plays a list of [`Keyframe`]s, sorted by time, working out the values
+/// in between.
+///
+/// Before the first keyframe the timeline holds its value, and after the
+/// last one it holds that, or starts over if it loops.
+#[derive(Clone, Copy)]
+pub struct Timeline<'a, T = [Srgb<u8>; LED_COUNT]> {
+    keyframes: &'a [Keyframe<T>],
+    looping: bool,
+    start: Option<Instant>,
+}
+
+impl<'a, T: Tween> Timeline<'a, T> {
+    /// A stopped timeline through `keyframes`.
+    pub const fn new(keyframes: &'a [Keyframe<T>]) -> Self {
+        Self {
+            keyframes,
+            looping: false,
+            start: None,
+        }
+    }
+
+    /// Start over from the beginning after the last keyframe.
+    #[must_use]
+    pub const fn looping(mut self) -> Self {
+        self.looping = true;
+        self
+    }
+
+    /// Start from the beginning now.
+    pub fn play(&mut self) {
+        self.play_from(Instant::now());
+    }
+
+    /// Start from the beginning at `start`, such as a beat's time, to stay
+    /// in step with something else timed from it.
+    pub const fn play_from(&mut self, start: Instant) {
+        self.start = Some(start);
+    }
+
+    /// Jump to `position` from the start, carrying on from there.
+    pub fn seek(&mut self, position: Duration) {
+        let now = Instant::now();
+        self.start = Some(now.checked_sub(position).unwrap_or(now));
+    }
+
+    /// Stop, leaving the LEDs as last rendered.
+    pub const fn stop(&mut self) {
+        self.start = None;
+    }
+
+    /// `true` from [`play`](Self::play) until the last keyframe has been
+    /// reached or it's stopped. A looping timeline plays until stopped.
+    pub fn is_playing(&self, now: Instant) -> bool {
+        self.start.is_some_and(|start| {
+            self.looping || now.saturating_duration_since(start) < self.duration()
+        })
+    }
+
+    /// Time of the last keyframe.
+    pub fn duration(&self) -> Duration {
+        self.keyframes
+            .last()
+            .map_or(Duration::from_ticks(0), |key| key.at)
+    }
+
+    /// The value at `position` from the start, or `None` without
+    /// keyframes.
+    pub fn value_at(&self, position: Duration) -> Option<T> {
+        let first = self.keyframes.first()?;
+        let duration = self.duration();
+        let position = if self.looping && duration.as_ticks() > 0 {
+            Duration::from_ticks(position.as_ticks() % duration.as_ticks())
+        } else {
+            position
+        };
+        let next = self.keyframes.iter().position(|key| key.at > position);
+        let Some(next) = next else {
+            return self.keyframes.last().map(|key| key.value);
+        };
+        if next == 0 {
+            return Some(first.value);
+        }
+        let (from, to) = (&self.keyframes[next - 1], &self.keyframes[next]);
+        let span = (to.at - from.at).as_micros().max(1);
+        let amount = ((position - from.at).as_micros() * 256 / span) as u32;
+        Some(T::tween(from.value, to.value, from.ease.apply(amount)))
+    }
+
+    /// The value at `now`, or `None` while stopped.
+    pub fn value(&self, now: Instant) -> Option<T> {
+        self.value_at(now.saturating_duration_since(self.start?))
+    }
+}
+
+impl Timeline<'_> {
+    /// Set `leds` to the timeline at `now`, and return whether they were
+    /// changed. The call that sees the last keyframe sets it; after that
+    /// the LEDs are left alone. Call [`Leds::update`] to show the result.
+    pub fn render(&mut self, leds: &mut Leds<'_>, now: Instant) -> bool {
+        let Some(frame) = self.value(now) else {
+            return false;
+        };
+        leds.fill_from_iter(frame);
+        if !self.is_playing(now) {
+            self.start = None;
+        }
+        true
+    }
+
+    /// Render and update the LEDs every 20 ms until the last keyframe, or
+    /// forever if it loops. Drop the future to stop.
+    pub async fn run(&mut self, leds: &mut Leds<'_>) {
+        if self.start.is_none() {
+            self.play();
+        }
+        while self.render(leds, Instant::now()) {
+            leds.update().await;
+            Timer::after(FRAME).await;
+        }
+    }
+}
@@ -0,0 +1,428 @@
+//! Time-based one-time passwords (RFC 6238), the codes authenticator apps
+//! show for two-factor logins.
+//!
+//! A [`Totp`] is built from the base32 secret a service shows next to its
+//! enrolment QR code, and turns the Unix time into the current code:
+//!
+//! ```rust,ignore
+//! let totp = Totp::from_base32("JBSWY3DPEHPK3PXP").unwrap();
+//! let code = totp.code(clock::unix_time());
+//! info!("{=u32:06} ({=u64} s left)", code, totp.remaining(clock::unix_time()));
+//! ```
+//!
+//! The codes are only as right as the clock, so set it with
+//! [`clock::set_unix_time`](crate::clock::set_unix_time) first.
+//!
+//! ## Sealed secrets
+//!
+//! Secrets stored in flash as they are can be read by anyone who dumps it.
+//! A [`Vault`] seals them instead, with a key burned into one of the chip's
+//! eFuse key blocks that software can't read back: the HMAC peripheral
+//! derives a key stream and an authentication tag from it for each secret.
+//! Burn the key once per badge, with purpose `HMAC_UP`:
+//!
+//! ```sh
+//! dd if=/dev/urandom of=vault.key bs=32 count=1
+//! espefuse.py burn_key BLOCK_KEY0 vault.key HMAC_UP
+//! rm vault.key
+//! ```
+//!
+//! Seal each secret on the badge, keep only the sealed bytes, and open them
+//! into RAM at run time:
+//!
+//! ```rust,ignore
+//! let mut vault = Vault::new(peripherals.HMAC, KeyId::Key0);
+//! let sealed = vault.seal(&Totp::from_base32(secret).unwrap(), nonce, &mut buf)?;
+//! // ...later, from flash:
+//! let totp = vault.open(sealed)?;
+//! ```
+//!
+//! Sealed secrets only open on the badge that sealed them.
+
+pub use esp_hal::hmac::KeyId;
+use esp_hal::{
+    hmac::{
+        Hmac,
+        HmacPurpose,
+    },
+    peripherals::HMAC,
+};
+
+/// Longest secret in bytes, one SHA-1 block. Services use 10 or 20.
+pub const MAX_SECRET: usize = 64;
+
+/// SHA-1 block size, which HMAC pads the key to.
+const BLOCK: usize = 64;
+
+/// A TOTP generator for one account.
+#[derive(Clone)]
+pub struct Totp {
+    key: [u8; MAX_SECRET],
+    key_len: usize,
+    digits: u32,
+    period: u64,
+}
+
+impl Totp {
+    /// A generator for six-digit codes that change every 30 seconds, the
+    /// settings nearly every service uses.
+    ///
+    /// Returns `None` for an empty key or one longer than [`MAX_SECRET`].
+    pub fn new(secret: &[u8]) -> Option<Self> {
+        if secret.is_empty() || secret.len() > MAX_SECRET {
+            return None;
+        }
+        let mut key = [0; MAX_SECRET];
+        key[..secret.len()].copy_from_slice(secret);
+        Some(Self {
+            key,
+            key_len: secret.len(),
+            digits: 6,
+            period: 30,
+        })
+    }
+
+    /// A generator for a base32 secret as services show it. Case, spaces,
+    /// dashes and `=` padding are ignored.
+    ///
+    /// Returns `None` if the secret contains other characters or is too
+    /// long.
+    pub fn from_base32(secret: &str) -> Option<Self> {
+        let mut key = [0; MAX_SECRET];
+        let (mut len, mut bits, mut count) = (0, 0u32, 0);
+        for c in secret.bytes() {
+            let value = match c.to_ascii_uppercase() {
+                c @ b'A'..=b'Z' => c - b'A',
+                c @ b'2'..=b'7' => c - b'2' + 26,
+                b' ' | b'-' | b'=' => continue,
+                _ => return None,
+            };
+            bits = bits << 5 | u32::from(value);
+            count += 5;
+            if count >= 8 {
+                count -= 8;
+                *key.get_mut(len)? = (bits >> count) as u8;
+                len += 1;
+            }
+        }
+        Self::new(&key[..len])
+    }
+
+    /// Codes with `digits` digits, from 6 to 8.
+    #[must_use]
+    pub const fn with_digits(mut self, digits: u32) -> Self {
+        self.digits = if digits < 6 {
+            6
+        } else if digits > 8 {
+            8
+        } else {
+            digits
+        };
+        self
+    }
+
+    /// Codes that change every `seconds` instead of 30.
+    #[must_use]
+    pub const fn with_period(mut self, seconds: u64) -> Self {
+        self.period = if seconds == 0 { 1 } else { seconds };
+        self
+    }
+
+    /// Number of digits in a code, to pad it with leading zeros.
+    pub const fn digits(&self) -> u32 {
+        self.digits
+    }
+
+    /// Seconds each code is valid.
+    pub const fn period(&self) -> u64 {
+        self.period
+    }
+
+    /// The code at `unix_time`.
+    pub fn code(&self, unix_time: u64) -> u32 {
+        hotp(
+            &self.key[..self.key_len],
+            unix_time / self.period,
+            self.digits,
+        )
+    }
+
+    /// Seconds until the code after the one at `unix_time`.
+    pub const fn remaining(&self, unix_time: u64) -> u64 {
+        self.period - unix_time % self.period
+    }
+}
+
+/// The HMAC-based one-time password (RFC 4226) for `counter`, with `digits`
+/// digits.
+pub fn hotp(key: &[u8], counter: u64, digits: u32) -> u32 {
+    let mac = hmac_sha1(key, &counter.to_be_bytes());
+    let offset = usize::from(mac[19] & 0xf);
+    let [a, b, c, d] = [
+        mac[offset],
+        mac[offset + 1],
+        mac[offset + 2],
+        mac[offset + 3],
+    ];
+    let truncated = u32::from_be_bytes([a & 0x7f, b, c, d]);
+    truncated % 10u32.pow(digits)
+}
+
+// ── Sealed secrets ──────────────────────────────────────────────────────────
+
+/// Bytes of nonce at the start of a sealed secret. Use a fresh random one
+/// for every secret sealed.
+pub const NONCE: usize = 12;
+
+/// Bytes of authentication tag at the end of a sealed secret.
+const TAG: usize = 16;
+
+/// Longest sealed secret in bytes.
+pub const MAX_SEALED: usize = NONCE + MAX_SECRET + TAG;
+
+/// HMAC-SHA-256 output size, the key stream per block.
+const STREAM_BLOCK: usize = 32;
+
+/// Why a secret can't be sealed or opened.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum VaultError {
+    /// The eFuse key block has no key with purpose `HMAC_UP`.
+    NoKey,
+    /// The sealed bytes were changed, cut short, or sealed on another
+    /// badge.
+    Tampered,
+}
+
+/// Seals and opens TOTP secrets with a key that never leaves the chip.
+pub struct Vault<'d> {
+    hmac: Hmac<'d>,
+    key: KeyId,
+}
+
+impl<'d> Vault<'d> {
+    /// A vault using the eFuse key block `key`, where `KeyId::Key0` is
+    /// `BLOCK_KEY0`.
+    pub fn new(hmac: HMAC<'d>, key: KeyId) -> Self {
+        Self {
+            hmac: Hmac::new(hmac),
+            key,
+        }
+    }
+
+    /// Encrypt the secret of `totp` into `out` under `nonce`, and return
+    /// the sealed bytes: the nonce, the encrypted secret and a tag. Its
+    /// digits and period aren't kept.
+    pub fn seal<'o>(
+        &mut self,
+        totp: &Totp,
+        nonce: [u8; NONCE],
+        out: &'o mut [u8; MAX_SEALED],
+    ) -> Result<&'o [u8], VaultError> {
+        let len = NONCE + totp.key_len;
+        out[..NONCE].copy_from_slice(&nonce);
+        out[NONCE..len].copy_from_slice(&totp.key[..totp.key_len]);
+        self.apply_stream(&nonce, &mut out[NONCE..len])?;
+        let tag = self.mac(&[b"totp tag", &out[..len]])?;
+        out[len..len + TAG].copy_from_slice(&tag[..TAG]);
+        Ok(&out[..len + TAG])
+    }
+
+    /// Check and decrypt a secret sealed with [`seal`](Self::seal) on this
+    /// badge, for six-digit codes every 30 seconds.
+    pub fn open(&mut self, sealed: &[u8]) -> Result<Totp, VaultError> {
+        if sealed.len() <= NONCE + TAG || sealed.len() > MAX_SEALED {
+            return Err(VaultError::Tampered);
+        }
+        let (body, tag) = sealed.split_at(sealed.len() - TAG);
+        let expected = self.mac(&[b"totp tag", body])?;
+        // Compare every byte, so the time taken gives nothing away.
+        let diff = tag
+            .iter()
+            .zip(&expected)
+            .fold(0, |diff, (a, b)| diff | (a ^ b));
+        if diff != 0 {
+            return Err(VaultError::Tampered);
+        }
+        let (nonce, encrypted) = body.split_at(NONCE);
+        let mut secret = [0; MAX_SECRET];
+        let secret = &mut secret[..encrypted.len()];
+        secret.copy_from_slice(encrypted);
+        self.apply_stream(nonce, secret)?;
+        let totp = Totp::new(secret).ok_or(VaultError::Tampered);
+        secret.fill(0);
+        totp
+    }
+
+    /// XOR `data` with the key stream for `nonce`.
+    fn apply_stream(&mut self, nonce: &[u8], data: &mut [u8]) -> Result<(), VaultError> {
+        for (block, chunk) in data.chunks_mut(STREAM_BLOCK).enumerate() {
+            let stream = self.mac(&[b"totp stream", nonce, &[block as u8]])?;
+            for (byte, key) in chunk.iter_mut().zip(stream) {
+                *byte ^= key;
+            }
+        }
+        Ok(())
+    }
+
+    /// HMAC-SHA-256 of `parts` joined, under the eFuse key.
+    fn mac(&mut self, parts: &[&[u8]]) -> Result<[u8; STREAM_BLOCK], VaultError> {
+        self.hmac.init();
+        self.hmac
+            .configure(HmacPurpose::ToUser, self.key)
+            .map_err(|_| VaultError::NoKey)?;
+        for part in parts {
+            let mut rest = *part;
+            while !rest.is_empty() {
+                if let Ok(remaining) = self.hmac.update(rest) {
+                    rest = remaining;
+                }
+            }
+        }
+        let mut out = [0; STREAM_BLOCK];
+        while self.hmac.finalize(&mut out).is_err() {}
+        Ok(out)
+    }
+}
+
+// ── HMAC-SHA-1 ──────────────────────────────────────────────────────────────
+
+/// HMAC-SHA-1 of `message`. Keys longer than a block are hashed first.
+fn hmac_sha1(key: &[u8], message: &[u8]) -> [u8; 20] {
+    let mut pad = [0; BLOCK];
+    if key.len() > BLOCK {
+        let mut hash = Sha1::new();
+        hash.update(key);
+        pad[..20].copy_from_slice(&hash.finish());
+    } else {
+        pad[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sha1::new();
+    inner.update(&pad.map(|b| b ^ 0x36));
+    inner.update(message);
+    let inner = inner.finish();
+
+    let mut outer = Sha1::new();
+    outer.update(&pad.map(|b| b ^ 0x5c));
+    outer.update(&inner);
+    outer.finish()
+}
+
+/// Streaming SHA-1, enough for HMAC of short messages.
+struct Sha1 {
+    state: [u32; 5],
+    block: [u8; BLOCK],
+    /// Bytes in `block`.
+    fill: usize,
+    /// Bytes hashed so far.
+    length: u64,
+}
+
+impl Sha1 {
+    const fn new() -> Self {
+        Self {
+            state: [
+                0x6745_2301,
+                0xefcd_ab89,
+                0x98ba_dcfe,
+                0x1032_5476,
+                0xc3d2_e1f0,
+            ],
+            block: [0; BLOCK],
+            fill: 0,
+            length: 0,
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.block[self.fill] = byte;
+            self.fill += 1;
+            if self.fill == BLOCK {
+                self.compress();
+                self.fill = 0;
+            }
+        }
+        self.length += data.len() as u64;
+    }
+
+    fn finish(mut self) -> [u8; 20] {
+        let bits = self.length * 8;
+        self.update(&[0x80]);
+        while self.fill != BLOCK - 8 {
+            self.update(&[0]);
+        }
+        self.update(&bits.to_be_bytes());
+        let mut digest = [0; 20];
+        for (chunk, word) in digest.chunks_exact_mut(4).zip(self.state) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+
+    fn compress(&mut self) {
+        let mut w = [0u32; 80];
+        for (word, chunk) in w.iter_mut().zip(self.block.chunks_exact(4)) {
+            *word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = self.state;
+        for (i, &word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..20 => ((b & c) | (!b & d), 0x5a82_7999),
+                20..40 => (b ^ c ^ d, 0x6ed9_eba1),
+                40..60 => ((b & c) | (b & d) | (c & d), 0x8f1b_bcdc),
+                _ => (b ^ c ^ d, 0xca62_c1d6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(word);
+            (e, d, c, b, a) = (d, c, b.rotate_left(30), a, temp);
+        }
+        for (state, value) in self.state.iter_mut().zip([a, b, c, d, e]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The SHA-1 vectors of RFC 6238, appendix B.
+    #[test]
+    fn rfc6238() {
+        let totp = Totp::new(b"12345678901234567890").unwrap().with_digits(8);
+        for (time, code) in [
+            (59, 94_287_082),
+            (1_111_111_109, 7_081_804),
+            (1_111_111_111, 14_050_471),
+            (1_234_567_890, 89_005_924),
+            (2_000_000_000, 69_279_037),
+            (20_000_000_000, 65_353_130),
+        ] {
+            assert_eq!(totp.code(time), code);
+        }
+    }
+
+    /// RFC 2202 test case 6, a key longer than a block.
+    #[test]
+    fn long_key() {
+        let mac = hmac_sha1(
+            &[0xaa; 80],
+            b"Test Using Larger Than Block-Size Key - Hash Key First",
+        );
+        assert_eq!(
+            mac,
+            [
+                0xaa, 0x4a, 0xe5, 0xe1, 0x52, 0x72, 0xd0, 0x0e, 0x95, 0x70, 0x56, 0x37, 0xce, 0x8a,
+                0x3b, 0x55, 0xed, 0x40, 0x21, 0x12,
+            ]
+        );
+    }
+}
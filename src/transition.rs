@@ -0,0 +1,170 @@
+//! Transitions between screens, in place of an abrupt clear.
+//!
+//! Each transition takes the whole screen to a plain colour over a given
+//! time, after which the app draws its next screen as usual:
+//!
+//! ```rust,ignore
+//! transition::dissolve(display, Rgb565::BLACK, Duration::from_millis(500)).await?;
+//! draw_game_over(display, score);
+//! ```
+//!
+//! They draw straight to the panel, so they work whether or not the app
+//! uses the framebuffer. Afterwards the framebuffer still holds the old
+//! screen, and its next flush sends all of it.
+//!
+//! With [`slow_animations`](crate::ui::Accessibility::slow_animations) on,
+//! every transition takes longer than asked.
+
+use embassy_time::{
+    Duration,
+    Instant,
+    Timer,
+};
+use embedded_graphics::{
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::Rectangle,
+};
+
+use crate::{
+    Backlight,
+    Display,
+    display::Error,
+    ui,
+};
+
+/// Time between the steps of a transition.
+const STEP: Duration = Duration::from_millis(16);
+
+/// Side of the squares [`dissolve`] fills.
+const BLOCK: u32 = 8;
+
+/// Fade the backlight out, clear the screen to black and turn the
+/// backlight back up, so the next screen appears at the old brightness.
+pub async fn fade_to_black(
+    display: &mut Display<'_>,
+    backlight: &mut Backlight,
+    duration: Duration,
+) -> Result<(), Error> {
+    let brightness = backlight.brightness();
+    backlight.fade_to(0, ui::animation_time(duration)).await;
+    display.clear(Rgb565::BLACK)?;
+    display.invalidate();
+    backlight.set_brightness(brightness);
+    Ok(())
+}
+
+/// Sweep `color` across the screen from the right edge to the left.
+pub async fn wipe_left(
+    display: &mut Display<'_>,
+    color: Rgb565,
+    duration: Duration,
+) -> Result<(), Error> {
+    let size = display.size();
+    let mut covered = 0;
+    run(duration, |progress| {
+        let reach = size.width * progress / PROGRESS_MAX;
+        let strip = Rectangle::new(
+            Point::new((size.width - reach) as i32, 0),
+            Size::new(reach - covered, size.height),
+        );
+        covered = reach;
+        display.fill_solid(&strip, color)
+    })
+    .await?;
+    display.invalidate();
+    Ok(())
+}
+
+/// Fill the screen with `color` in small squares, in scattered order.
+pub async fn dissolve(
+    display: &mut Display<'_>,
+    color: Rgb565,
+    duration: Duration,
+) -> Result<(), Error> {
+    let size = display.size();
+    let columns = size.width.div_ceil(BLOCK);
+    let blocks = columns * size.height.div_ceil(BLOCK);
+    let mut order = Scatter::new(blocks);
+    let mut filled = 0;
+    run(duration, |progress| {
+        let target = blocks * progress / PROGRESS_MAX;
+        while filled < target {
+            let Some(block) = order.next() else {
+                break;
+            };
+            let top_left = Point::new(
+                (block % columns * BLOCK) as i32,
+                (block / columns * BLOCK) as i32,
+            );
+            display.fill_solid(&Rectangle::new(top_left, Size::new_equal(BLOCK)), color)?;
+            filled += 1;
+        }
+        Ok(())
+    })
+    .await?;
+    display.invalidate();
+    Ok(())
+}
+
+/// [`run`]'s progress when the transition is done.
+const PROGRESS_MAX: u32 = 1024;
+
+/// Call `step` every [`STEP`] with the progress so far, from 0 up to
+/// [`PROGRESS_MAX`] at `duration`. The last call is always at the end.
+async fn run(
+    duration: Duration,
+    mut step: impl FnMut(u32) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let start = Instant::now();
+    let total = ui::animation_time(duration).as_micros().max(1);
+    loop {
+        let elapsed = start.elapsed().as_micros().min(total);
+        step((elapsed * u64::from(PROGRESS_MAX) / total) as u32)?;
+        if elapsed == total {
+            return Ok(());
+        }
+        Timer::after(STEP).await;
+    }
+}
+
+/// Every number below a limit exactly once, in a scrambled order, from a
+/// 16-bit maximal-length LFSR.
+struct Scatter {
+    state: u16,
+    limit: u32,
+    /// The LFSR never produces 0, so it is handed out first.
+    zero_done: bool,
+}
+
+impl Scatter {
+    const fn new(limit: u32) -> Self {
+        Self {
+            state: 0xace1,
+            limit,
+            zero_done: false,
+        }
+    }
+}
+
+impl Iterator for Scatter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if !self.zero_done {
+            self.zero_done = true;
+            return (self.limit > 0).then_some(0);
+        }
+        // The sequence has 65535 states; stop once it comes back round.
+        loop {
+            let bit = (self.state ^ (self.state >> 2) ^ (self.state >> 3) ^ (self.state >> 5)) & 1;
+            self.state = (self.state >> 1) | (bit << 15);
+            if self.state == 0xace1 {
+                return None;
+            }
+            if u32::from(self.state) < self.limit {
+                return Some(u32::from(self.state));
+            }
+        }
+    }
+}
@@ -0,0 +1,696 @@
+//! Simple screens of labels, lists and buttons, driven by the D-pad.
+//!
+//! Build one with [`screen!`](crate::screen), draw it, and feed it button
+//! presses; it moves the focus and tells you what was chosen:
+//!
+//! ```rust,ignore
+//! let mut screen = screen! { column { label("Brightness"), list(&LEVELS), button("OK") } };
+//! loop {
+//!     display.frame(|fb| screen.draw(fb).unwrap())?;
+//!     match screen.handle(buttons.wait_for_press().await) {
+//!         Some(Event::Pressed(_)) => break,
+//!         Some(Event::Back) => return,
+//!         _ => {}
+//!     }
+//! }
+//! let level = screen.selected(1);
+//! ```
+//!
+//! Screens are drawn in a [`Theme`], and badge-wide [`Accessibility`]
+//! settings can override it with large, high-contrast text, slow down
+//! [`transition`](crate::transition)s and buzz on every focus change.
+
+use core::cell::Cell;
+
+use embassy_sync::{
+    blocking_mutex::{
+        Mutex,
+        raw::CriticalSectionRawMutex,
+    },
+    signal::Signal,
+};
+use embassy_time::{
+    Duration,
+    Timer,
+};
+use embedded_graphics::{
+    mono_font::{
+        MonoFont,
+        MonoTextStyle,
+        ascii::FONT_10X20,
+    },
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::{
+        PrimitiveStyle,
+        Rectangle,
+    },
+    text::{
+        Alignment,
+        Baseline,
+        Text,
+        TextStyleBuilder,
+    },
+};
+
+use crate::{
+    Button,
+    Vibration,
+    font::FONT_20X40,
+};
+
+/// Space between widgets.
+const GAP: u32 = 6;
+/// How much longer animations take with
+/// [`slow_animations`](Accessibility::slow_animations).
+const SLOW_FACTOR: u32 = 3;
+/// Length of one buzz of a focus cue.
+const CUE_BUZZ: Duration = Duration::from_millis(25);
+
+// ── Themes ──────────────────────────────────────────────────────────────────
+
+/// How a [`Screen`] looks.
+#[derive(Clone, Copy)]
+pub struct Theme {
+    pub text: Rgb565,
+    /// Marks the focus.
+    pub accent: Rgb565,
+    pub background: Rgb565,
+    pub font: &'static MonoFont<'static>,
+    /// Lines of a list shown at once.
+    pub list_rows: usize,
+    /// Width of the outline of an unfocused button.
+    pub border: u32,
+}
+
+impl Theme {
+    /// White and orange on black in 10×20 text.
+    pub const DEFAULT: Self = Self {
+        text: Rgb565::WHITE,
+        accent: Rgb565::CSS_ORANGE,
+        background: Rgb565::BLACK,
+        font: &FONT_10X20,
+        list_rows: 4,
+        border: 1,
+    };
+
+    /// White and yellow on black in 20×40 text with heavy outlines, for
+    /// reading at a glance or with poor eyesight. Fewer widgets fit.
+    pub const HIGH_VISIBILITY: Self = Self {
+        text: Rgb565::WHITE,
+        accent: Rgb565::YELLOW,
+        background: Rgb565::BLACK,
+        font: &FONT_20X40,
+        list_rows: 2,
+        border: 3,
+    };
+
+    /// Height of a line of text.
+    const fn line(&self) -> u32 {
+        self.font.character_size.height
+    }
+}
+
+// ── Accessibility ───────────────────────────────────────────────────────────
+
+/// Accessibility settings for the whole badge, set once with
+/// [`set_accessibility`] and followed by every [`Screen`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, defmt::Format)]
+pub struct Accessibility {
+    /// Draw every screen in [`Theme::HIGH_VISIBILITY`], whatever theme the
+    /// app chose.
+    pub high_visibility: bool,
+    /// Make [`transition`](crate::transition)s and anything else timed
+    /// with [`animation_time`] take three times as long.
+    pub slow_animations: bool,
+    /// Buzz the vibration motor when the focus moves, once for a new list
+    /// item and twice for a new widget. The badge has no speaker, so these
+    /// take the place of audio cues. Needs [`focus_cue_task`] running.
+    pub focus_cues: bool,
+}
+
+impl Accessibility {
+    /// Everything off, the default.
+    pub const OFF: Self = Self {
+        high_visibility: false,
+        slow_animations: false,
+        focus_cues: false,
+    };
+    /// Everything on.
+    pub const ALL: Self = Self {
+        high_visibility: true,
+        slow_animations: true,
+        focus_cues: true,
+    };
+
+    /// The settings as one byte, to keep with the app's other settings.
+    pub const fn to_bits(self) -> u8 {
+        self.high_visibility as u8
+            | (self.slow_animations as u8) << 1
+            | (self.focus_cues as u8) << 2
+    }
+
+    /// Settings saved with [`to_bits`](Self::to_bits).
+    pub const fn from_bits(bits: u8) -> Self {
+        Self {
+            high_visibility: bits & 1 != 0,
+            slow_animations: bits & 2 != 0,
+            focus_cues: bits & 4 != 0,
+        }
+    }
+}
+
+static ACCESSIBILITY: Mutex<CriticalSectionRawMutex, Cell<Accessibility>> =
+    Mutex::new(Cell::new(Accessibility::OFF));
+
+/// Buzzes waiting for [`focus_cue_task`].
+static FOCUS_CUE: Signal<CriticalSectionRawMutex, u8> = Signal::new();
+
+/// Use `settings` from the next draw, press and animation on.
+pub fn set_accessibility(settings: Accessibility) {
+    ACCESSIBILITY.lock(|a| a.set(settings));
+}
+
+/// The settings set with [`set_accessibility`].
+pub fn accessibility() -> Accessibility {
+    ACCESSIBILITY.lock(Cell::get)
+}
+
+/// `duration` stretched for
+/// [`slow_animations`](Accessibility::slow_animations), or as it is.
+pub fn animation_time(duration: Duration) -> Duration {
+    if accessibility().slow_animations {
+        duration * SLOW_FACTOR
+    } else {
+        duration
+    }
+}
+
+/// Buzz `times` for a focus change, if focus cues are on.
+fn cue(times: u8) {
+    if accessibility().focus_cues {
+        FOCUS_CUE.signal(times);
+    }
+}
+
+/// Buzz `vibration` for the focus changes of every [`Screen`], while
+/// [`focus_cues`](Accessibility::focus_cues) is on.
+#[embassy_executor::task]
+pub async fn focus_cue_task(vibration: &'static mut Vibration) -> ! {
+    loop {
+        let times = FOCUS_CUE.wait().await;
+        for _ in 0..times {
+            vibration.pulse(CUE_BUZZ).await;
+            Timer::after(CUE_BUZZ * 2).await;
+        }
+    }
+}
+
+// ── Widgets ─────────────────────────────────────────────────────────────────
+
+/// One element of a [`Screen`].
+#[derive(Clone, Copy, Debug)]
+pub enum Widget<'a> {
+    /// Text that can't be focused.
+    Label(&'a str),
+    /// Items to pick one of, with Up/Down while focused.
+    List {
+        items: &'a [&'a str],
+        selected: usize,
+    },
+    /// A button, pressed with A while focused.
+    Button(&'a str),
+    /// The start of a nested column or row, holding the widgets up to the
+    /// matching [`End`](Self::End).
+    Group(Layout),
+    /// The end of the innermost [`Group`](Self::Group).
+    End,
+}
+
+impl<'a> Widget<'a> {
+    /// A label.
+    pub const fn label(text: &'a str) -> Self {
+        Self::Label(text)
+    }
+
+    /// A list with its first item selected.
+    pub const fn list(items: &'a [&'a str]) -> Self {
+        Self::List { items, selected: 0 }
+    }
+
+    /// A button.
+    pub const fn button(text: &'a str) -> Self {
+        Self::Button(text)
+    }
+
+    const fn is_focusable(&self) -> bool {
+        matches!(self, Self::List { .. } | Self::Button(_))
+    }
+
+    /// Height of the widget in a column. Groups are measured by the
+    /// [`Screen`].
+    fn height(&self, theme: &Theme) -> u32 {
+        match self {
+            Self::Label(_) => theme.line(),
+            Self::List { items, .. } => items.len().min(theme.list_rows) as u32 * theme.line(),
+            Self::Button(_) => theme.line() + 6,
+            Self::Group(_) | Self::End => 0,
+        }
+    }
+}
+
+/// How a [`Screen`] lays out its widgets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum Layout {
+    /// Top to bottom; Up/Down move the focus.
+    Column,
+    /// Side by side in equal widths; Left/Right move the focus.
+    Row,
+}
+
+/// What the user did on a [`Screen`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum Event {
+    /// A was pressed on the button at this widget index.
+    Pressed(usize),
+    /// A was pressed on a list: the widget index and the item picked.
+    Chosen { widget: usize, item: usize },
+    /// B was pressed, to leave the screen.
+    Back,
+}
+
+/// A screen of `N` widgets with a focus, built with
+/// [`screen!`](crate::screen).
+///
+/// Focus moves between lists and buttons in the order they're listed, with
+/// the buttons along the focused widget's column or row. The buttons across
+/// it leave a nested column or row for the widgets before or after it.
+/// Inside a focused list, Up/Down move through its items before moving on.
+///
+/// Widgets are numbered in the order they're written, and a nested column
+/// or row counts as two: its [`Group`](Widget::Group) before its widgets
+/// and its [`End`](Widget::End) after them.
+pub struct Screen<'a, const N: usize> {
+    widgets: [Widget<'a>; N],
+    layout: Layout,
+    focus: Option<usize>,
+    area: Rectangle,
+    theme: Theme,
+}
+
+impl<'a, const N: usize> Screen<'a, N> {
+    /// `widgets` laid out by `layout` over the whole 320×170 screen, with
+    /// the first list or button focused.
+    pub const fn new(layout: Layout, widgets: [Widget<'a>; N]) -> Self {
+        let mut focus = None;
+        let mut i = 0;
+        while i < N {
+            if widgets[i].is_focusable() {
+                focus = Some(i);
+                break;
+            }
+            i += 1;
+        }
+        Self {
+            widgets,
+            layout,
+            focus,
+            area: Rectangle::new(Point::zero(), Size::new(320, 170)),
+            theme: Theme::DEFAULT,
+        }
+    }
+
+    /// Draw inside `area` instead of the whole screen.
+    #[must_use]
+    pub const fn with_area(mut self, area: Rectangle) -> Self {
+        self.area = area;
+        self
+    }
+
+    /// Draw text in `text` and mark the focus with `accent`.
+    #[must_use]
+    pub const fn with_colors(mut self, text: Rgb565, accent: Rgb565) -> Self {
+        self.theme.text = text;
+        self.theme.accent = accent;
+        self
+    }
+
+    /// Draw in `theme` instead of [`Theme::DEFAULT`].
+    #[must_use]
+    pub const fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// The theme it's drawn in, [`Theme::HIGH_VISIBILITY`] if the
+    /// accessibility settings ask for it.
+    pub fn theme(&self) -> Theme {
+        if accessibility().high_visibility {
+            Theme::HIGH_VISIBILITY
+        } else {
+            self.theme
+        }
+    }
+
+    /// Index of the focused widget, if any can be focused.
+    pub const fn focus(&self) -> Option<usize> {
+        self.focus
+    }
+
+    /// The item selected in the list at widget `index`, or 0 if that's not
+    /// a list.
+    pub fn selected(&self, index: usize) -> usize {
+        match self.widgets.get(index) {
+            Some(Widget::List { selected, .. }) => *selected,
+            _ => 0,
+        }
+    }
+
+    /// Act on a button press, returning what it chose, if anything.
+    pub fn handle(&mut self, button: Button) -> Option<Event> {
+        let focus = self.focus?;
+        match button {
+            Button::A => Some(match self.widgets[focus] {
+                Widget::List { selected, .. } => Event::Chosen {
+                    widget: focus,
+                    item: selected,
+                },
+                _ => Event::Pressed(focus),
+            }),
+            Button::B => Some(Event::Back),
+            Button::Up | Button::Down => {
+                let up = button == Button::Up;
+                if let Widget::List { items, selected } = &mut self.widgets[focus] {
+                    if up && *selected > 0 {
+                        *selected -= 1;
+                        cue(1);
+                        return None;
+                    }
+                    if !up && *selected + 1 < items.len() {
+                        *selected += 1;
+                        cue(1);
+                        return None;
+                    }
+                }
+                self.move_focus(focus, button);
+                None
+            }
+            _ => {
+                self.move_focus(focus, button);
+                None
+            }
+        }
+    }
+
+    /// Move the focus for `button`: along the column or row holding
+    /// `focus`, or across it and out of it if it's nested.
+    fn move_focus(&mut self, focus: usize, button: Button) {
+        let (layout, group) = self.parent(focus);
+        let (back, forward) = match layout {
+            Layout::Column => (Button::Up, Button::Down),
+            Layout::Row => (Button::Left, Button::Right),
+        };
+        if button == back {
+            self.focus_first((0..focus).rev());
+        } else if button == forward {
+            self.focus_first(focus + 1..N);
+        } else if let Some(group) = group {
+            match button {
+                Button::Up | Button::Left => self.focus_first((0..group).rev()),
+                Button::Down | Button::Right => self.focus_first(self.skip(group)..N),
+                _ => {}
+            }
+        }
+    }
+
+    /// Focus the first focusable widget of `indices`, if there is one.
+    fn focus_first(&mut self, mut indices: impl Iterator<Item = usize>) {
+        if let Some(next) = indices.find(|&i| self.widgets[i].is_focusable()) {
+            self.focus = Some(next);
+            cue(2);
+        }
+    }
+
+    /// The index just past the widget or whole group at `index`.
+    fn skip(&self, index: usize) -> usize {
+        let mut depth = 0;
+        for i in index..N {
+            match self.widgets[i] {
+                Widget::Group(_) => depth += 1,
+                Widget::End => depth -= 1,
+                _ => {}
+            }
+            if depth <= 0 {
+                return i + 1;
+            }
+        }
+        N
+    }
+
+    /// The layout of the group holding widget `index` and the index of
+    /// its [`Group`](Widget::Group), or the screen's layout at the top.
+    fn parent(&self, index: usize) -> (Layout, Option<usize>) {
+        let mut depth = 0;
+        for i in (0..index).rev() {
+            match self.widgets[i] {
+                Widget::End => depth += 1,
+                Widget::Group(layout) if depth == 0 => return (layout, Some(i)),
+                Widget::Group(_) => depth -= 1,
+                _ => {}
+            }
+        }
+        (self.layout, None)
+    }
+
+    /// The widgets and groups directly in `start..end`.
+    fn items(&self, start: usize, end: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut i = start;
+        core::iter::from_fn(move || {
+            let item = i;
+            i = self.skip(i);
+            (item < end).then_some(item)
+        })
+    }
+
+    /// The widgets inside the group at `index`.
+    fn inside(&self, index: usize) -> (usize, usize) {
+        let end = self.skip(index);
+        match self.widgets[end - 1] {
+            Widget::End if end - 1 > index => (index + 1, end - 1),
+            _ => (index + 1, end),
+        }
+    }
+
+    /// Height of the widget or group at `index`.
+    fn height(&self, index: usize, theme: &Theme) -> u32 {
+        let Widget::Group(layout) = self.widgets[index] else {
+            return self.widgets[index].height(theme);
+        };
+        let (start, end) = self.inside(index);
+        let heights = self.items(start, end).map(|i| self.height(i, theme));
+        match layout {
+            Layout::Column => heights.map(|h| h + GAP).sum::<u32>().saturating_sub(GAP),
+            Layout::Row => heights.max().unwrap_or(0),
+        }
+    }
+
+    /// How far a column too tall for the area is scrolled up to show the
+    /// focused widget, or the group holding it.
+    fn scroll(&self, theme: &Theme) -> u32 {
+        let (Layout::Column, Some(focus)) = (self.layout, self.focus) else {
+            return 0;
+        };
+        let mut bottom = 0;
+        for i in self.items(0, N) {
+            bottom += self.height(i, theme) + GAP;
+            if self.skip(i) > focus {
+                break;
+            }
+        }
+        bottom
+            .saturating_sub(GAP)
+            .saturating_sub(self.area.size.height)
+    }
+
+    /// Clear the screen's area and draw the widgets, scrolling a column
+    /// that doesn't fit to keep the focus in view.
+    pub fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let theme = self.theme();
+        let target = &mut target.clipped(&self.area);
+        target.fill_solid(&self.area, theme.background)?;
+        let area = Rectangle::new(
+            self.area.top_left - Point::new(0, self.scroll(&theme) as i32),
+            self.area.size,
+        );
+        self.draw_items(target, &theme, self.layout, (0, N), area)
+    }
+
+    /// Draw the widgets and groups directly in `start..end` laid out by
+    /// `layout` in `area`.
+    fn draw_items<D>(
+        &self,
+        target: &mut D,
+        theme: &Theme,
+        layout: Layout,
+        (start, end): (usize, usize),
+        area: Rectangle,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let count = self.items(start, end).count().max(1) as u32;
+        let mut y = area.top_left.y;
+        for (n, i) in self.items(start, end).enumerate() {
+            let height = self.height(i, theme);
+            let item = match layout {
+                Layout::Column => {
+                    let item = Rectangle::new(
+                        Point::new(area.top_left.x, y),
+                        Size::new(area.size.width, height),
+                    );
+                    y += (height + GAP) as i32;
+                    item
+                }
+                Layout::Row => {
+                    let width = area.size.width / count;
+                    Rectangle::new(
+                        area.top_left + Point::new((width * n as u32) as i32, 0),
+                        Size::new(width.saturating_sub(GAP), height),
+                    )
+                }
+            };
+            match self.widgets[i] {
+                Widget::Group(inner) => {
+                    self.draw_items(target, theme, inner, self.inside(i), item)?;
+                }
+                ref widget => {
+                    Self::draw_widget(target, theme, widget, item, self.focus == Some(i))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn draw_widget<D>(
+        target: &mut D,
+        theme: &Theme,
+        widget: &Widget<'_>,
+        area: Rectangle,
+        focused: bool,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let line = theme.line();
+        let text = MonoTextStyle::new(theme.font, theme.text);
+        let accent = MonoTextStyle::new(theme.font, theme.accent);
+        let on_accent = MonoTextStyle::new(theme.font, theme.background);
+        match *widget {
+            Widget::Label(label) => {
+                Text::with_baseline(label, area.top_left, text, Baseline::Top).draw(target)?;
+            }
+            Widget::List { items, selected } => {
+                // Scroll so the selected item stays in view.
+                let top = selected.saturating_sub(theme.list_rows.saturating_sub(1));
+                for (row, (i, item)) in items
+                    .iter()
+                    .enumerate()
+                    .skip(top)
+                    .take(theme.list_rows)
+                    .enumerate()
+                {
+                    let at = area.top_left + Point::new(0, (row as u32 * line) as i32);
+                    let style = if i == selected {
+                        if focused {
+                            let bar = Rectangle::new(at, Size::new(area.size.width, line));
+                            target.fill_solid(&bar, theme.accent)?;
+                            on_accent
+                        } else {
+                            accent
+                        }
+                    } else {
+                        text
+                    };
+                    Text::with_baseline(item, at + Point::new(4, 0), style, Baseline::Top)
+                        .draw(target)?;
+                }
+            }
+            Widget::Button(label) => {
+                let style = if focused {
+                    area.into_styled(PrimitiveStyle::with_fill(theme.accent))
+                        .draw(target)?;
+                    on_accent
+                } else {
+                    area.into_styled(PrimitiveStyle::with_stroke(theme.text, theme.border))
+                        .draw(target)?;
+                    text
+                };
+                let centered = TextStyleBuilder::new()
+                    .alignment(Alignment::Center)
+                    .baseline(Baseline::Middle)
+                    .build();
+                Text::with_text_style(label, area.center(), style, centered).draw(target)?;
+            }
+            Widget::Group(_) | Widget::End => {}
+        }
+        Ok(())
+    }
+}
+
+/// Build a [`Screen`](crate::ui::Screen) from a column or row of
+/// widgets, each written as its constructor on
+/// [`Widget`](crate::ui::Widget). Columns and rows nest:
+///
+/// ```rust,ignore
+/// let screen = screen! { column { label("Score"), list(&items), button("OK") } };
+/// let buttons = screen! { row { button("Yes"), button("No") } };
+/// let dialog = screen! {
+///     column {
+///         label("Save changes?"),
+///         row { button("Save"), button("Discard") },
+///     }
+/// };
+/// ```
+#[macro_export]
+macro_rules! screen {
+    (column { $($body:tt)* }) => {
+        $crate::ui::Screen::new(
+            $crate::ui::Layout::Column,
+            $crate::screen!(@widgets [] $($body)*),
+        )
+    };
+    (row { $($body:tt)* }) => {
+        $crate::ui::Screen::new(
+            $crate::ui::Layout::Row,
+            $crate::screen!(@widgets [] $($body)*),
+        )
+    };
+    // Flattens nested columns and rows into one array, in focus order.
+    (@widgets [$($out:tt)*]) => {
+        [$($out)*]
+    };
+    (@widgets [$($out:tt)*] , $($rest:tt)*) => {
+        $crate::screen!(@widgets [$($out)*] $($rest)*)
+    };
+    (@widgets [$($out:tt)*] @end $($rest:tt)*) => {
+        $crate::screen!(@widgets [$($out)* $crate::ui::Widget::End,] $($rest)*)
+    };
+    (@widgets [$($out:tt)*] column { $($inner:tt)* } $($rest:tt)*) => {
+        $crate::screen!(
+            @widgets [$($out)* $crate::ui::Widget::Group($crate::ui::Layout::Column),]
+            $($inner)* , @end $($rest)*
+        )
+    };
+    (@widgets [$($out:tt)*] row { $($inner:tt)* } $($rest:tt)*) => {
+        $crate::screen!(
+            @widgets [$($out)* $crate::ui::Widget::Group($crate::ui::Layout::Row),]
+            $($inner)* , @end $($rest)*
+        )
+    };
+    (@widgets [$($out:tt)*] $widget:ident ( $($arg:expr),* $(,)? ) $($rest:tt)*) => {
+        $crate::screen!(@widgets [$($out)* $crate::ui::Widget::$widget($($arg),*),] $($rest)*)
+    };
+}
@@ -0,0 +1,386 @@
+//! Watch faces described as data rather than code.
+//!
+//! A face is a plain-text list of `key = value` lines in the style of
+//! [`level`](crate::level), one drawing layer per line, painted in order:
+//!
+//! ```text
+//! # White hands on a dark dial, with the time below.
+//! name = Classic
+//! background = 000010
+//! circle = 160 75 130 404060 2
+//! ticks = 160 75 64 8 12 8080a0 2
+//! hand = hour 160 75 34 ffffff 5
+//! hand = minute 160 75 52 ffffff 3
+//! hand = second 160 75 58 ff4000 1
+//! text = 160 160 center small 8080a0 {hh}:{mm}  {battery}
+//! ```
+//!
+//! The layers are:
+//!
+//! - `background = color`
+//! - `rect = x y width height color`
+//! - `circle = x y diameter color [stroke]`, filled without a stroke width
+//! - `ticks = x y radius length count color [width]`, dial marks pointing
+//!   inwards from `radius`
+//! - `hand = hour|minute|second x y length color [width]`
+//! - `text = x y left|center|right small|medium|large color template`
+//!
+//! Lengths, diameters and widths go up to 1024 pixels, positions up to 4096
+//! from the origin, and `ticks` draws at most 360 marks; a layer outside
+//! those is an error on its line.
+//!
+//! Colours are as in [`level::parse_color`](crate::level::parse_color).
+//! Text templates fill in `{hh}`, `{mm}` and `{ss}` from the clock,
+//! `{h12}` and `{ampm}` for a 12-hour clock, and any other `{name}` from
+//! values the app passes to [`WatchFace::draw`], such as a battery level.
+//! Fields nobody provides are left blank.
+//!
+//! A face is a [`Document`], so it loads from anywhere a level does.
+
+use core::fmt::{
+    self,
+    Write as _,
+};
+
+use embedded_graphics::{
+    mono_font::{
+        MonoFont,
+        MonoTextStyle,
+        ascii::{
+            FONT_6X10,
+            FONT_10X20,
+        },
+    },
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::{
+        Circle,
+        Line,
+        PrimitiveStyle,
+        Rectangle,
+    },
+    text::{
+        Alignment,
+        Text,
+    },
+};
+
+use crate::{
+    clock::Time,
+    dial::on_dial,
+    font::FONT_20X40,
+    level::{
+        Document,
+        Error,
+        Words,
+    },
+};
+
+/// Longest text a layer shows, in bytes; the rest is cut off.
+const TEXT_BYTES: usize = 64;
+
+/// Largest length, diameter or line width a layer takes, in pixels, so the
+/// dial maths can't overflow.
+const MAX_LENGTH: u32 = 1024;
+/// Farthest a layer's position may be from the origin on either axis.
+const MAX_COORDINATE: u32 = 4096;
+/// Most marks a `ticks` layer draws.
+const MAX_TICKS: i32 = 360;
+
+/// A parsed watch face, borrowing its text.
+#[derive(Clone, Copy)]
+pub struct WatchFace<'a> {
+    document: Document<'a>,
+}
+
+impl<'a> WatchFace<'a> {
+    /// Parse a face from `text`, checking every layer.
+    pub fn parse(text: &'a str) -> Result<Self, Error> {
+        let document = Document::parse(text, |entry| Layer::parse(entry).is_some())?;
+        Ok(Self { document })
+    }
+
+    /// Parse a face from raw bytes, such as a flash region.
+    pub fn from_bytes(data: &'a [u8]) -> Result<Self, Error> {
+        let document = Document::from_bytes(data, |entry| Layer::parse(entry).is_some())?;
+        Ok(Self { document })
+    }
+
+    /// The face's `name`, if it has one.
+    pub fn name(&self) -> Option<&'a str> {
+        self.document.name()
+    }
+
+    /// Draw the face showing `time`, filling other text fields from
+    /// `values`.
+    ///
+    /// ```rust,ignore
+    /// let battery = alloc::format!("{percent}%");
+    /// face.draw(fb, clock::now(), &[("battery", &battery), ("nick", NICK)])?;
+    /// ```
+    pub fn draw<D>(
+        &self,
+        target: &mut D,
+        time: Time,
+        values: &[(&str, &str)],
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        for layer in self.document.entries().filter_map(Layer::parse) {
+            layer.draw(target, time, values)?;
+        }
+        Ok(())
+    }
+}
+
+/// Which clock hand a `hand` layer is.
+#[derive(Clone, Copy)]
+enum Hand {
+    Hour,
+    Minute,
+    Second,
+}
+
+impl Hand {
+    /// Angle of the hand at `time`, 1024 per turn clockwise from 12.
+    const fn angle(self, time: Time) -> i32 {
+        let seconds = time.seconds_of_day();
+        (match self {
+            Self::Hour => seconds % (12 * 3600) * 1024 / (12 * 3600),
+            Self::Minute => seconds % 3600 * 1024 / 3600,
+            Self::Second => seconds % 60 * 1024 / 60,
+        }) as i32
+    }
+}
+
+/// One line of a face.
+enum Layer<'a> {
+    Name,
+    Background(Rgb565),
+    Rect(Rectangle, Rgb565),
+    Circle(Circle, PrimitiveStyle<Rgb565>),
+    Ticks {
+        center: Point,
+        radius: i32,
+        length: i32,
+        count: i32,
+        style: PrimitiveStyle<Rgb565>,
+    },
+    Hand {
+        hand: Hand,
+        center: Point,
+        length: i32,
+        style: PrimitiveStyle<Rgb565>,
+    },
+    Text {
+        position: Point,
+        alignment: Alignment,
+        style: MonoTextStyle<'static, Rgb565>,
+        template: &'a str,
+    },
+}
+
+impl<'a> Layer<'a> {
+    fn parse((key, value): (&str, &'a str)) -> Option<Self> {
+        let mut words = Words(value);
+        let layer = match key {
+            "name" => return Some(Self::Name),
+            "background" => Self::Background(words.color()?),
+            "rect" => {
+                let top_left = point(&mut words)?;
+                let size = Size::new(size(&mut words)?, size(&mut words)?);
+                Self::Rect(Rectangle::new(top_left, size), words.color()?)
+            }
+            "circle" => {
+                let (center, diameter, color) =
+                    (point(&mut words)?, size(&mut words)?, words.color()?);
+                let style = match words.next() {
+                    Some(width) => PrimitiveStyle::with_stroke(color, line_width(width)?),
+                    None => PrimitiveStyle::with_fill(color),
+                };
+                Self::Circle(Circle::with_center(center, diameter), style)
+            }
+            "ticks" => Self::Ticks {
+                center: point(&mut words)?,
+                radius: length(&mut words)?,
+                length: length(&mut words)?,
+                count: words.number::<i32>().filter(|&n| n <= MAX_TICKS)?.max(1),
+                style: stroke(&mut words)?,
+            },
+            "hand" => Self::Hand {
+                hand: match words.next()? {
+                    "hour" => Hand::Hour,
+                    "minute" => Hand::Minute,
+                    "second" => Hand::Second,
+                    _ => return None,
+                },
+                center: point(&mut words)?,
+                length: length(&mut words)?,
+                style: stroke(&mut words)?,
+            },
+            "text" => {
+                let position = point(&mut words)?;
+                let alignment = match words.next()? {
+                    "left" => Alignment::Left,
+                    "center" => Alignment::Center,
+                    "right" => Alignment::Right,
+                    _ => return None,
+                };
+                let font: &'static MonoFont<'static> = match words.next()? {
+                    "small" => &FONT_6X10,
+                    "medium" => &FONT_10X20,
+                    "large" => &FONT_20X40,
+                    _ => return None,
+                };
+                let style = MonoTextStyle::new(font, words.color()?);
+                return Some(Self::Text {
+                    position,
+                    alignment,
+                    style,
+                    template: words.0.trim(),
+                });
+            }
+            _ => return None,
+        };
+        words.end(layer)
+    }
+
+    fn draw<D>(&self, target: &mut D, time: Time, values: &[(&str, &str)]) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        match *self {
+            Self::Name => {}
+            Self::Background(color) => target.clear(color)?,
+            Self::Rect(area, color) => target.fill_solid(&area, color)?,
+            Self::Circle(circle, style) => circle.into_styled(style).draw(target)?,
+            Self::Ticks {
+                center,
+                radius,
+                length,
+                count,
+                style,
+            } => {
+                for tick in 0..count {
+                    let angle = tick * 1024 / count;
+                    let outer = on_dial(center, angle, radius);
+                    let inner = on_dial(center, angle, radius - length);
+                    Line::new(inner, outer).into_styled(style).draw(target)?;
+                }
+            }
+            Self::Hand {
+                hand,
+                center,
+                length,
+                style,
+            } => {
+                let tip = on_dial(center, hand.angle(time), length);
+                Line::new(center, tip).into_styled(style).draw(target)?;
+            }
+            Self::Text {
+                position,
+                alignment,
+                style,
+                template,
+            } => {
+                let mut text = TextBuffer::new();
+                fill_template(&mut text, template, time, values);
+                Text::with_alignment(text.as_str(), position, style, alignment).draw(target)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A colour and an optional line width, 1 if left out.
+fn stroke(words: &mut Words<'_>) -> Option<PrimitiveStyle<Rgb565>> {
+    let color = words.color()?;
+    let width = match words.next() {
+        Some(width) => line_width(width)?,
+        None => 1,
+    };
+    Some(PrimitiveStyle::with_stroke(color, width))
+}
+
+/// The next word as a size of at most [`MAX_LENGTH`].
+fn size(words: &mut Words<'_>) -> Option<u32> {
+    words.number().filter(|&n| n <= MAX_LENGTH)
+}
+
+/// `word` as a line width of at most [`MAX_LENGTH`].
+fn line_width(word: &str) -> Option<u32> {
+    word.parse().ok().filter(|&n| n <= MAX_LENGTH)
+}
+
+/// The next word as a length of at most [`MAX_LENGTH`] either way.
+fn length(words: &mut Words<'_>) -> Option<i32> {
+    words
+        .number::<i32>()
+        .filter(|n| n.unsigned_abs() <= MAX_LENGTH)
+}
+
+/// The next two words as a point within [`MAX_COORDINATE`] of the origin.
+fn point(words: &mut Words<'_>) -> Option<Point> {
+    words
+        .point()
+        .filter(|p| p.x.unsigned_abs() <= MAX_COORDINATE && p.y.unsigned_abs() <= MAX_COORDINATE)
+}
+
+/// Write `template` to `out` with its `{fields}` filled in.
+fn fill_template(out: &mut TextBuffer, template: &str, time: Time, values: &[(&str, &str)]) {
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open..].find('}') else {
+            break;
+        };
+        let _ = out.write_str(&rest[..open]);
+        let (hour, minute, second) = (time.hour(), time.minute(), time.second());
+        let _ = match &rest[open + 1..open + close] {
+            "hh" => write!(out, "{hour:02}"),
+            "mm" => write!(out, "{minute:02}"),
+            "ss" => write!(out, "{second:02}"),
+            "h12" => write!(out, "{}", (hour + 11) % 12 + 1),
+            "ampm" => out.write_str(if hour < 12 { "AM" } else { "PM" }),
+            name => values
+                .iter()
+                .find(|&&(key, _)| key == name)
+                .map_or(Ok(()), |&(_, value)| out.write_str(value)),
+        };
+        rest = &rest[open + close + 1..];
+    }
+    let _ = out.write_str(rest);
+}
+
+/// Text of one layer, cut off at [`TEXT_BYTES`].
+struct TextBuffer {
+    bytes: [u8; TEXT_BYTES],
+    len: usize,
+}
+
+impl TextBuffer {
+    const fn new() -> Self {
+        Self {
+            bytes: [0; TEXT_BYTES],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or("")
+    }
+}
+
+impl fmt::Write for TextBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            let len = c.len_utf8();
+            if self.len + len > TEXT_BYTES {
+                return Err(fmt::Error);
+            }
+            c.encode_utf8(&mut self.bytes[self.len..]);
+            self.len += len;
+        }
+        Ok(())
+    }
+}